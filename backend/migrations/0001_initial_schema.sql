@@ -0,0 +1,63 @@
+-- The tables the repositories used to create for themselves on startup.
+-- IF NOT EXISTS so databases that predate migrations can adopt this as their baseline.
+
+CREATE TABLE IF NOT EXISTS users (
+	id SERIAL PRIMARY KEY,
+	name TEXT NOT NULL,
+	email TEXT NOT NULL,
+	atype TEXT NOT NULL,
+	num_league_tokens INTEGER DEFAULT 0
+);
+
+ALTER TABLE users ADD COLUMN IF NOT EXISTS num_league_tokens INTEGER DEFAULT 0;
+
+CREATE TABLE IF NOT EXISTS game_shows (
+	game_show_id SERIAL PRIMARY KEY,
+	name TEXT DEFAULT 'Jeffs Jamboree'
+);
+
+CREATE TABLE IF NOT EXISTS contestants (
+	contestant_id SERIAL PRIMARY KEY,
+	name TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS game_show_contestants (
+	contestant_id INTEGER,
+	game_show_id INTEGER,
+	nickname TEXT,
+	PRIMARY KEY (contestant_id, game_show_id),
+	FOREIGN KEY (contestant_id) REFERENCES contestants(contestant_id)
+		ON DELETE CASCADE,
+	FOREIGN KEY (game_show_id) REFERENCES game_shows(game_show_id)
+		ON DELETE CASCADE,
+	was_medically_evacuated BOOLEAN DEFAULT FALSE,
+	eliminated_on_round INTEGER DEFAULT -1
+);
+
+CREATE TABLE IF NOT EXISTS leagues (
+	id SERIAL PRIMARY KEY,
+	name TEXT NOT NULL,
+	id_showseason INTEGER
+);
+
+CREATE TABLE IF NOT EXISTS league_members (
+	id SERIAL PRIMARY KEY,
+	league_id INTEGER NOT NULL REFERENCES leagues(id)
+		ON DELETE CASCADE,
+	user_id INTEGER NOT NULL REFERENCES users(id)
+		ON DELETE CASCADE,
+	UNIQUE(league_id, user_id)
+);
+
+CREATE TABLE IF NOT EXISTS league_picks (
+	id SERIAL PRIMARY KEY,
+	league_id INTEGER NOT NULL REFERENCES leagues(id)
+		ON DELETE CASCADE,
+	user_id INTEGER NOT NULL REFERENCES users(id)
+		ON DELETE CASCADE,
+	round_number INTEGER NOT NULL,
+	contestant_id INTEGER NOT NULL REFERENCES contestants(contestant_id)
+		ON DELETE CASCADE,
+	rank_pick INTEGER NOT NULL,
+	UNIQUE(league_id, user_id, round_number, contestant_id)
+);
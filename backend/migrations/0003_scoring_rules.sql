@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS scoring_rules (
+	id SERIAL PRIMARY KEY,
+	league_id INTEGER NOT NULL UNIQUE REFERENCES leagues(id)
+		ON DELETE CASCADE,
+	survival_points INTEGER NOT NULL DEFAULT 1,
+	boot_prediction_bonus INTEGER NOT NULL DEFAULT 3,
+	medevac_points INTEGER NOT NULL DEFAULT 0,
+	medevac_counts_as_boot BOOLEAN NOT NULL DEFAULT FALSE
+);
@@ -0,0 +1,23 @@
+CREATE TABLE IF NOT EXISTS rounds (
+	round_id SERIAL PRIMARY KEY,
+	game_show_id INTEGER NOT NULL REFERENCES game_shows(game_show_id)
+		ON DELETE CASCADE,
+	round_number INTEGER NOT NULL,
+	title TEXT NOT NULL DEFAULT '',
+	air_date TIMESTAMPTZ,
+	lock_time TIMESTAMPTZ,
+	status TEXT NOT NULL DEFAULT 'upcoming'
+		CHECK (status IN ('upcoming', 'open', 'locked', 'scored')),
+	UNIQUE (game_show_id, round_number)
+);
+
+-- Rounds replaced the round_schedules table. Carry any scheduled rounds across.
+DO $$
+BEGIN
+	IF EXISTS (SELECT 1 FROM information_schema.tables WHERE table_name = 'round_schedules') THEN
+		INSERT INTO rounds (game_show_id, round_number, air_date, lock_time)
+			SELECT game_show_id, round_number, air_time, lock_time FROM round_schedules
+			ON CONFLICT (game_show_id, round_number) DO NOTHING;
+		DROP TABLE round_schedules;
+	END IF;
+END $$;
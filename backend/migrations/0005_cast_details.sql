@@ -0,0 +1,5 @@
+-- Filled in by cast list imports. The tribe is the one a contestant starts the season on.
+
+ALTER TABLE contestants ADD COLUMN IF NOT EXISTS bio TEXT;
+
+ALTER TABLE game_show_contestants ADD COLUMN IF NOT EXISTS tribe TEXT;
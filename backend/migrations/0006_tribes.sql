@@ -0,0 +1,40 @@
+CREATE TABLE IF NOT EXISTS tribes (
+	tribe_id SERIAL PRIMARY KEY,
+	game_show_id INTEGER NOT NULL REFERENCES game_shows(game_show_id)
+		ON DELETE CASCADE,
+	name TEXT NOT NULL,
+	color TEXT NOT NULL DEFAULT '#808080'
+		CHECK (color ~ '^#[0-9A-Fa-f]{6}$'),
+	UNIQUE (game_show_id, name),
+	-- Lets assignments check that the tribe is from the same season.
+	UNIQUE (tribe_id, game_show_id)
+);
+
+-- Which tribe a contestant is on from a given round onwards. A contestant's tribe for any round
+-- is the assignment with the latest from_round at or before it, so swaps and the merge are new
+-- rows rather than updates.
+CREATE TABLE IF NOT EXISTS tribe_assignments (
+	assignment_id SERIAL PRIMARY KEY,
+	game_show_id INTEGER NOT NULL,
+	contestant_id INTEGER NOT NULL,
+	tribe_id INTEGER NOT NULL,
+	from_round INTEGER NOT NULL CHECK (from_round >= 1),
+	FOREIGN KEY (contestant_id, game_show_id) REFERENCES game_show_contestants(contestant_id, game_show_id)
+		ON DELETE CASCADE,
+	FOREIGN KEY (tribe_id, game_show_id) REFERENCES tribes(tribe_id, game_show_id)
+		ON DELETE CASCADE,
+	UNIQUE (game_show_id, contestant_id, from_round)
+);
+
+-- The starting tribes from cast imports become real tribes.
+INSERT INTO tribes (game_show_id, name)
+	SELECT DISTINCT game_show_id, tribe FROM game_show_contestants WHERE tribe IS NOT NULL AND tribe <> ''
+	ON CONFLICT (game_show_id, name) DO NOTHING;
+
+INSERT INTO tribe_assignments (game_show_id, contestant_id, tribe_id, from_round)
+	SELECT gsc.game_show_id, gsc.contestant_id, t.tribe_id, 1
+	FROM game_show_contestants gsc
+	JOIN tribes t ON t.game_show_id = gsc.game_show_id AND t.name = gsc.tribe
+	ON CONFLICT (game_show_id, contestant_id, from_round) DO NOTHING;
+
+ALTER TABLE game_show_contestants DROP COLUMN IF EXISTS tribe;
@@ -0,0 +1,26 @@
+-- Everything that happens to a contestant during a season, in the order it happened. Rows are
+-- only ever added; where a contestant stands (still in, voted out, on the jury, ...) is worked
+-- out from their events instead of being stored.
+CREATE TABLE IF NOT EXISTS contestant_events (
+	event_id SERIAL PRIMARY KEY,
+	game_show_id INTEGER NOT NULL,
+	contestant_id INTEGER NOT NULL,
+	round_number INTEGER NOT NULL CHECK (round_number >= 1),
+	event_type TEXT NOT NULL
+		CHECK (event_type IN ('voted_out', 'medevac', 'quit', 'returned', 'joined_jury', 'won_immunity', 'found_idol')),
+	recorded_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+	FOREIGN KEY (contestant_id, game_show_id) REFERENCES game_show_contestants(contestant_id, game_show_id)
+		ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS contestant_events_by_show ON contestant_events (game_show_id, round_number, event_id);
+
+-- Eliminations and medevacs recorded so far become the first events.
+INSERT INTO contestant_events (game_show_id, contestant_id, round_number, event_type)
+	SELECT game_show_id, contestant_id, eliminated_on_round,
+		CASE WHEN was_medically_evacuated THEN 'medevac' ELSE 'voted_out' END
+	FROM game_show_contestants
+	WHERE eliminated_on_round >= 1;
+
+ALTER TABLE game_show_contestants DROP COLUMN IF EXISTS eliminated_on_round;
+ALTER TABLE game_show_contestants DROP COLUMN IF EXISTS was_medically_evacuated;
@@ -0,0 +1,25 @@
+-- What each contestant did in a round, for leagues that score more than survival. One row per
+-- contestant and round; the gamemaster overwrites it when a stat is corrected.
+CREATE TABLE IF NOT EXISTS contestant_round_stats (
+	stat_id SERIAL PRIMARY KEY,
+	game_show_id INTEGER NOT NULL,
+	contestant_id INTEGER NOT NULL,
+	round_number INTEGER NOT NULL CHECK (round_number >= 1),
+	won_individual_immunity BOOLEAN NOT NULL DEFAULT FALSE,
+	won_reward BOOLEAN NOT NULL DEFAULT FALSE,
+	idols_found INTEGER NOT NULL DEFAULT 0 CHECK (idols_found >= 0),
+	idols_played INTEGER NOT NULL DEFAULT 0 CHECK (idols_played >= 0),
+	votes_received INTEGER NOT NULL DEFAULT 0 CHECK (votes_received >= 0),
+	cast_correct_vote BOOLEAN NOT NULL DEFAULT FALSE,
+	FOREIGN KEY (contestant_id, game_show_id) REFERENCES game_show_contestants(contestant_id, game_show_id)
+		ON DELETE CASCADE,
+	UNIQUE (game_show_id, contestant_id, round_number)
+);
+
+-- Every stat is worth nothing until a league says otherwise, so existing scores don't move.
+ALTER TABLE scoring_rules ADD COLUMN IF NOT EXISTS immunity_win_points INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE scoring_rules ADD COLUMN IF NOT EXISTS reward_win_points INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE scoring_rules ADD COLUMN IF NOT EXISTS idol_found_points INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE scoring_rules ADD COLUMN IF NOT EXISTS idol_played_points INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE scoring_rules ADD COLUMN IF NOT EXISTS vote_received_points INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE scoring_rules ADD COLUMN IF NOT EXISTS correct_vote_points INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,12 @@
+-- Contestants are told apart by name plus a disambiguator, e.g. "Rob" / "Mariano" and
+-- "Rob" / "Cesternino". Most contestants leave it empty.
+ALTER TABLE contestants ADD COLUMN IF NOT EXISTS disambiguator TEXT NOT NULL DEFAULT '';
+
+-- Contestants that already share a name keep the lowest id as is, the others get their id.
+UPDATE contestants c SET disambiguator = c.contestant_id::TEXT
+	WHERE EXISTS (
+		SELECT 1 FROM contestants other
+		WHERE other.name = c.name AND other.disambiguator = c.disambiguator AND other.contestant_id < c.contestant_id
+	);
+
+CREATE UNIQUE INDEX IF NOT EXISTS contestants_name_disambiguator ON contestants (name, disambiguator);
@@ -0,0 +1,7 @@
+-- What players see about a contestant when ranking them. Prior seasons are free text, e.g.
+-- "Pearl Islands". The photo is the file name of the uploaded image in the photo directory.
+ALTER TABLE contestants ADD COLUMN IF NOT EXISTS age INTEGER CHECK (age > 0);
+ALTER TABLE contestants ADD COLUMN IF NOT EXISTS hometown TEXT;
+ALTER TABLE contestants ADD COLUMN IF NOT EXISTS occupation TEXT;
+ALTER TABLE contestants ADD COLUMN IF NOT EXISTS prior_seasons TEXT[] NOT NULL DEFAULT '{}';
+ALTER TABLE contestants ADD COLUMN IF NOT EXISTS photo TEXT;
@@ -0,0 +1,9 @@
+-- Seasons move from draft (casting) to enrollment (players join leagues) to live (episodes
+-- airing, picks and eliminations) to finished. Seasons that already exist were being played,
+-- so they start out live. New seasons start in draft.
+ALTER TABLE game_shows ADD COLUMN IF NOT EXISTS status TEXT NOT NULL DEFAULT 'live'
+	CHECK (status IN ('draft', 'enrollment', 'live', 'finished'));
+ALTER TABLE game_shows ALTER COLUMN status SET DEFAULT 'draft';
+
+ALTER TABLE game_shows ADD COLUMN IF NOT EXISTS start_date TIMESTAMPTZ;
+ALTER TABLE game_shows ADD COLUMN IF NOT EXISTS end_date TIMESTAMPTZ;
@@ -0,0 +1,11 @@
+-- A mistake in the event log is taken back by adding a `reversed` event that points at the
+-- event it cancels, so the log stays append-only and keeps a record of the correction.
+ALTER TABLE contestant_events ADD COLUMN IF NOT EXISTS reverses_event_id INTEGER
+	REFERENCES contestant_events(event_id) ON DELETE CASCADE;
+
+ALTER TABLE contestant_events DROP CONSTRAINT IF EXISTS contestant_events_event_type_check;
+ALTER TABLE contestant_events ADD CONSTRAINT contestant_events_event_type_check
+	CHECK (event_type IN ('voted_out', 'medevac', 'quit', 'returned', 'joined_jury', 'won_immunity', 'found_idol', 'reversed'));
+
+ALTER TABLE contestant_events ADD CONSTRAINT contestant_events_reversal_check
+	CHECK ((event_type = 'reversed') = (reverses_event_id IS NOT NULL));
@@ -0,0 +1,194 @@
+use argon2::{ Argon2, PasswordHash, PasswordHasher, PasswordVerifier };
+use argon2::password_hash::{ SaltString, rand_core::OsRng };
+use rocket::http::{ Cookie, CookieJar, Status };
+use rocket::request::{ FromRequest, Outcome, Request };
+use rocket::serde::{ Deserialize, Serialize, json::Json };
+
+use crate::user_manager::{ User, UserManager };
+use crate::utilities::errors::ErrorBody;
+
+pub const SESSION_COOKIE : &str = "session_user_id";
+
+// users.atype values that are allowed to run the game. Everything else is a player.
+const GAMEMASTER_ACCOUNT_TYPES : [&str; 2] = ["GameMaster", "Admin"];
+
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub enum Role
+{
+	GameMaster,
+	Player,
+}
+
+impl Role
+{
+	pub fn from_account_type(account_type: &str) -> Self
+	{
+		if GAMEMASTER_ACCOUNT_TYPES.iter().any(|gamemaster_type: &&str| gamemaster_type.eq_ignore_ascii_case(account_type))
+		{
+			return Role::GameMaster;
+		}
+
+		return Role::Player;
+	}
+}
+
+// Guards stash the reason they refused a request here for the catchers to pick up.
+struct AuthFailure(String);
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LoginRequest
+{
+	pub email: String,
+	pub password: String,
+}
+
+// The user behind the private session cookie. Rocket encrypts and signs private
+// cookies with the configured secret_key, so the id inside can't be forged by the browser.
+pub struct SessionUser
+{
+	pub user: User,
+}
+
+impl SessionUser
+{
+	pub fn id(&self) -> i32
+	{
+		return self.user.id.unwrap_or(-1);
+	}
+
+	pub fn role(&self) -> Role
+	{
+		return Role::from_account_type(&self.user.account_type);
+	}
+}
+
+// Any logged in account. Gamemasters can play in leagues too.
+pub struct Player
+{
+	pub session: SessionUser,
+}
+
+pub struct GameMaster
+{
+	pub session: SessionUser,
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for SessionUser
+{
+	type Error = String;
+
+	async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error>
+	{
+		let user_id: Option<i32> = request.cookies()
+			.get_private(SESSION_COOKIE)
+			.and_then(|cookie: Cookie<'static>| cookie.value().parse::<i32>().ok());
+
+		let Some(user_id) = user_id else
+		{
+			return refuse(request, Status::Unauthorized, "Not logged in");
+		};
+
+		let Some(manager) = request.rocket().state::<UserManager>() else
+		{
+			return Outcome::Error((Status::InternalServerError, "User manager unavailable".to_string()));
+		};
+
+		match manager.select_user(user_id).await
+		{
+			Ok(user) => return Outcome::Success(SessionUser { user }),
+			Err(_) =>
+			{
+				request.cookies().remove_private(SESSION_COOKIE);
+				return refuse(request, Status::Unauthorized, "Session is no longer valid");
+			}
+		}
+	}
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Player
+{
+	type Error = String;
+
+	async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error>
+	{
+		return SessionUser::from_request(request).await.map(|session: SessionUser| Player { session });
+	}
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for GameMaster
+{
+	type Error = String;
+
+	async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error>
+	{
+		let session: SessionUser = match SessionUser::from_request(request).await
+		{
+			Outcome::Success(session) => session,
+			Outcome::Error(failure) => return Outcome::Error(failure),
+			Outcome::Forward(status) => return Outcome::Forward(status),
+		};
+
+		if session.role() != Role::GameMaster
+		{
+			return refuse(request, Status::Forbidden, "Only gamemasters can do that");
+		}
+
+		return Outcome::Success(GameMaster { session });
+	}
+}
+
+fn refuse<T>(request: &Request<'_>, status: Status, reason: &str) -> Outcome<T, String>
+{
+	request.local_cache(|| AuthFailure(reason.to_string()));
+	return Outcome::Error((status, reason.to_string()));
+}
+
+// The 401 and 403 catchers answer with the same body as ApiError so the frontend can show why a request was refused.
+#[catch(401)]
+pub fn unauthorized(request: &Request<'_>) -> Json<ErrorBody>
+{
+	return Json(auth_error(request, "unauthorized", "Not logged in"));
+}
+
+#[catch(403)]
+pub fn forbidden(request: &Request<'_>) -> Json<ErrorBody>
+{
+	return Json(auth_error(request, "forbidden", "You are not allowed to do that"));
+}
+
+fn auth_error(request: &Request<'_>, error: &str, fallback_message: &str) -> ErrorBody
+{
+	let AuthFailure(reason) = request.local_cache(|| AuthFailure(fallback_message.to_string()));
+	return ErrorBody { error: error.to_string(), message: reason.clone() };
+}
+
+pub fn start_session(cookies: &CookieJar<'_>, user: &User)
+{
+	let user_id: String = user.id.unwrap_or(-1).to_string();
+	cookies.add_private(Cookie::build((SESSION_COOKIE, user_id)).path("/"));
+}
+
+pub fn end_session(cookies: &CookieJar<'_>)
+{
+	cookies.remove_private(Cookie::build(SESSION_COOKIE).path("/"));
+}
+
+pub fn hash_password(password: &str) -> Result<String, String>
+{
+	let salt: SaltString = SaltString::generate(&mut OsRng);
+
+	return Argon2::default()
+		.hash_password(password.as_bytes(), &salt)
+		.map(|hash: PasswordHash| hash.to_string())
+		.map_err(|e: argon2::password_hash::Error| e.to_string());
+}
+
+pub fn verify_password(password: &str, password_hash: &str) -> bool
+{
+	let Ok(parsed_hash) = PasswordHash::new(password_hash) else { return false; };
+
+	return Argon2::default().verify_password(password.as_bytes(), &parsed_hash).is_ok();
+}
@@ -0,0 +1,118 @@
+use rocket::serde::{ Deserialize, Serialize };
+
+use crate::gameshow_manager::{ Contestant, ContestantStatus, GameShow, LeaguePick, SeasonStatus };
+use crate::scoring_engine::NOT_ELIMINATED;
+
+// A contestant's record across every season they were cast on. Where they finished comes from
+// the season's event log: everyone still in the game when they left finishes ahead of them,
+// and contestants leaving in the same round share a finish. Once the season is finished whoever
+// is left shares 1st, before that nobody still in the game has a finish. On the fantasy side a
+// pick is one player's ranking of them for one round, rank 1 being the pick trusted most to
+// survive. They count as picked when a player put them first; the average rank is over every
+// pick that ranked them at all.
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SeasonAppearance
+{
+	pub game_show_id: i32,
+	pub game_show_name: String,
+	pub nickname: Option<String>,
+	pub status: ContestantStatus,
+	// The round they went out on, None while still in the game.
+	pub eliminated_in_round: Option<i32>,
+	pub was_medically_evacuated: bool,
+	// 1 for the winner, the cast size for the first one out.
+	pub finish: Option<i32>,
+	pub cast_size: usize,
+	// Picks that put them first, one for each player and round.
+	pub times_picked: usize,
+	// Every pick naming them, whatever the rank.
+	pub times_ranked: usize,
+	pub average_rank: Option<f64>
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ContestantHistory
+{
+	pub contestant: Contestant,
+	pub seasons: Vec<SeasonAppearance>,
+	// Over all seasons.
+	pub times_picked: usize,
+	pub times_ranked: usize,
+	pub average_rank: Option<f64>
+}
+
+// `cast` is everyone on the season with their events applied, `picks` every league pick made
+// on it. Returns None if the contestant isn't in the cast.
+pub fn season_appearance(game_show: &GameShow, cast: &[Contestant], picks: &[LeaguePick], contestant_id: i32) -> Option<SeasonAppearance>
+{
+	let contestant: &Contestant = cast.iter().find(|member: &&Contestant| member.id == Some(contestant_id))?;
+	let eliminated_in_round: Option<i32> = exit_round(contestant);
+
+	let finish: Option<i32> = match eliminated_in_round
+	{
+		Some(round_number) =>
+		{
+			let outlasted_by: usize = cast.iter()
+				.filter(|member: &&Contestant| exit_round(member).is_none_or(|other_round: i32| other_round > round_number))
+				.count();
+			Some(outlasted_by as i32 + 1)
+		}
+		None if game_show.status == SeasonStatus::Finished => Some(1),
+		None => None,
+	};
+
+	let ranks: Vec<i32> = picks.iter()
+		.filter(|pick: &&LeaguePick| pick.contestant_id == contestant_id)
+		.map(|pick: &LeaguePick| pick.rank_pick)
+		.collect();
+
+	return Some(SeasonAppearance
+	{
+		game_show_id: game_show.id.unwrap_or_default(),
+		game_show_name: game_show.name.clone(),
+		nickname: contestant.nickname.clone().filter(|nickname: &String| !nickname.is_empty()),
+		status: contestant.status,
+		eliminated_in_round,
+		was_medically_evacuated: contestant.was_medically_evacuated,
+		finish,
+		cast_size: cast.len(),
+		times_picked: ranks.iter().filter(|rank: &&i32| **rank == 1).count(),
+		times_ranked: ranks.len(),
+		average_rank: average(&ranks)
+	});
+}
+
+pub fn summarize(contestant: Contestant, seasons: Vec<SeasonAppearance>) -> ContestantHistory
+{
+	let times_picked: usize = seasons.iter().map(|season: &SeasonAppearance| season.times_picked).sum();
+	let times_ranked: usize = seasons.iter().map(|season: &SeasonAppearance| season.times_ranked).sum();
+
+	// Weighted by how often they were ranked each season, so it is the average over every pick.
+	let rank_total: f64 = seasons.iter()
+		.filter_map(|season: &SeasonAppearance| season.average_rank.map(|rank: f64| rank * season.times_ranked as f64))
+		.sum();
+	let average_rank: Option<f64> = if times_ranked == 0 { None } else { Some(rank_total / times_ranked as f64) };
+
+	return ContestantHistory { contestant, seasons, times_picked, times_ranked, average_rank };
+}
+
+fn exit_round(contestant: &Contestant) -> Option<i32>
+{
+	if contestant.round_number == NOT_ELIMINATED
+	{
+		return None;
+	}
+
+	return Some(contestant.round_number);
+}
+
+fn average(ranks: &[i32]) -> Option<f64>
+{
+	if ranks.is_empty()
+	{
+		return None;
+	}
+
+	return Some(ranks.iter().map(|rank: &i32| *rank as f64).sum::<f64>() / ranks.len() as f64);
+}
@@ -0,0 +1,173 @@
+use rocket::serde::{ Deserialize, Serialize };
+
+use crate::gameshow_manager::Contestant;
+use crate::utilities::errors::ApiError;
+
+// Name matching for the contestant search. Names are compared case-insensitively, and a
+// contestant matches when the query is their whole name, the start of it, the start of one of
+// its words, somewhere inside it, or a typo or two away from the name or one of its words.
+// Closer kinds of match sort first.
+
+pub const DEFAULT_PAGE_SIZE : usize = 25;
+pub const MAX_PAGE_SIZE : usize = 100;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ContestantPage
+{
+	// How many contestants matched in all, not just on this page.
+	pub total: usize,
+	pub limit: usize,
+	pub offset: usize,
+	pub contestants: Vec<Contestant>
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum MatchKind
+{
+	Exact,
+	Prefix,
+	WordPrefix,
+	Substring,
+	Fuzzy,
+}
+
+pub fn page_bounds(limit: Option<i64>, offset: Option<i64>) -> Result<(usize, usize), ApiError>
+{
+	let limit: i64 = limit.unwrap_or(DEFAULT_PAGE_SIZE as i64);
+	if limit < 1 || limit > MAX_PAGE_SIZE as i64
+	{
+		return Err(ApiError::Validation(format!("limit has to be between 1 and {}, not [{}]", MAX_PAGE_SIZE, limit)));
+	}
+
+	let offset: i64 = offset.unwrap_or(0);
+	if offset < 0
+	{
+		return Err(ApiError::Validation(format!("offset can't be negative, got [{}]", offset)));
+	}
+
+	return Ok((limit as usize, offset as usize));
+}
+
+// The contestants matching `query`, best matches first, then by name. A blank query matches everyone.
+pub fn rank_contestants(contestants: Vec<Contestant>, query: &str) -> Vec<Contestant>
+{
+	let query: String = query.trim().to_lowercase();
+
+	let mut matches: Vec<(Option<MatchKind>, Contestant)> = contestants.into_iter()
+		.filter_map(|contestant: Contestant|
+		{
+			if query.is_empty()
+			{
+				return Some((None, contestant));
+			}
+
+			let display_name: String = contestant.display_name().to_lowercase();
+			return match_kind(&display_name, &query).map(|kind: MatchKind| (Some(kind), contestant));
+		})
+		.collect();
+
+	matches.sort_by(|(kind, contestant), (other_kind, other)|
+	{
+		return kind.cmp(other_kind)
+			.then_with(|| contestant.name.to_lowercase().cmp(&other.name.to_lowercase()))
+			.then_with(|| contestant.id.cmp(&other.id));
+	});
+
+	return matches.into_iter().map(|(_, contestant)| contestant).collect();
+}
+
+// Pieces of the query at least one of which appears in every name `match_kind` would accept, so
+// storage can drop the rest before ranking. Each typo changes at most two neighbouring pieces,
+// so cutting the query into one more than twice the allowed typos leaves one piece untouched.
+// Empty for a blank query, which matches everyone.
+pub fn prefilter_fragments(query: &str) -> Vec<String>
+{
+	let query: Vec<char> = query.trim().to_lowercase().chars().collect();
+	if query.is_empty()
+	{
+		return Vec::new();
+	}
+
+	let pieces: usize = 2 * allowed_typos(&query.iter().collect::<String>()) + 1;
+	return (0..pieces)
+		.map(|piece: usize| query[piece * query.len() / pieces..(piece + 1) * query.len() / pieces].iter().collect::<String>())
+		.collect();
+}
+
+pub fn match_kind(name: &str, query: &str) -> Option<MatchKind>
+{
+	let words: Vec<&str> = name.split(|character: char| !character.is_alphanumeric()).filter(|word: &&str| !word.is_empty()).collect();
+
+	if name == query
+	{
+		return Some(MatchKind::Exact);
+	}
+
+	if name.starts_with(query)
+	{
+		return Some(MatchKind::Prefix);
+	}
+
+	if words.iter().any(|word: &&str| word.starts_with(query))
+	{
+		return Some(MatchKind::WordPrefix);
+	}
+
+	if name.contains(query)
+	{
+		return Some(MatchKind::Substring);
+	}
+
+	let allowed_typos: usize = allowed_typos(query);
+	let is_close = |candidate: &str| allowed_typos > 0 && edit_distance(candidate, query) <= allowed_typos;
+	if is_close(name) || words.iter().any(|word: &&str| is_close(word))
+	{
+		return Some(MatchKind::Fuzzy);
+	}
+
+	return None;
+}
+
+// Short queries have to be spelled right, otherwise everything would match them.
+fn allowed_typos(query: &str) -> usize
+{
+	match query.chars().count()
+	{
+		0..=3 => return 0,
+		4..=7 => return 1,
+		_ => return 2,
+	}
+}
+
+// Edit distance where swapping two neighbouring letters counts as one typo, like any other
+// added, missing or wrong letter. Counted in characters.
+fn edit_distance(left: &str, right: &str) -> usize
+{
+	let left: Vec<char> = left.chars().collect();
+	let right: Vec<char> = right.chars().collect();
+	let mut distances: Vec<Vec<usize>> = vec![vec![0; right.len() + 1]; left.len() + 1];
+	distances[0] = (0..=right.len()).collect();
+
+	for (i, row) in distances.iter_mut().enumerate()
+	{
+		row[0] = i;
+	}
+
+	for i in 1..=left.len()
+	{
+		for j in 1..=right.len()
+		{
+			let substitution: usize = distances[i - 1][j - 1] + usize::from(left[i - 1] != right[j - 1]);
+			let mut cost: usize = substitution.min(distances[i - 1][j] + 1).min(distances[i][j - 1] + 1);
+
+			if i > 1 && j > 1 && left[i - 1] == right[j - 2] && left[i - 2] == right[j - 1]
+			{
+				cost = cost.min(distances[i - 2][j - 2] + 1);
+			}
+
+			distances[i][j] = cost;
+		}
+	}
+
+	return distances[left.len()][right.len()];
+}
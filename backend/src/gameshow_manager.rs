@@ -1,13 +1,24 @@
+use chrono::{ DateTime, Utc };
+use rocket::http::ContentType;
 use rocket::serde::{ Deserialize, Serialize };
 
-use crate::{gameshows_accessor, league_accessor};
+use crate::{contestant_history, contestant_search, gameshows_accessor, league_accessor, scoring_engine};
+use crate::contestant_history::{ ContestantHistory, SeasonAppearance };
+use crate::contestant_search::ContestantPage;
+use crate::scoring_engine::{ GameExit, SeasonRecord, Standing, UserScore };
+use crate::user_manager::User;
+use crate::utilities::errors::ApiError;
+use crate::utilities::photo_store::PhotoStore;
+use crate::utilities::storage::StorageConnector;
+use crate::utilities::validation::required;
 
 use std::sync::Arc;
 
 pub struct GameShowManager
 {
-	pub repo: Arc<gameshows_accessor::GameShowRepository>,
-	pub league_repository: Arc<league_accessor::LeagueRepository>,
+	pub repo: Arc<dyn gameshows_accessor::GameShowRepository>,
+	pub league_repository: Arc<dyn league_accessor::LeagueRepository>,
+	pub storage: Arc<StorageConnector>,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -15,138 +26,1855 @@ pub struct GameShow
 {
 	pub id: Option<i32>,
 	pub name: String,
+	// Only ever changed along SeasonStatus::can_become. New seasons start in draft.
+	#[serde(default)]
+	pub status: SeasonStatus,
+	#[serde(default)]
+	pub start_date: Option<DateTime<Utc>>,
+	#[serde(default)]
+	pub end_date: Option<DateTime<Utc>>
 }
 
+// Where a season is in its life. The cast is put together in draft, players join leagues during
+// enrollment, episodes air and picks are made while live, and nothing changes once finished.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SeasonStatus
+{
+	#[default]
+	Draft,
+	Enrollment,
+	Live,
+	Finished,
+}
+
+impl SeasonStatus
+{
+	pub fn as_str(&self) -> &'static str
+	{
+		match self
+		{
+			SeasonStatus::Draft => return "draft",
+			SeasonStatus::Enrollment => return "enrollment",
+			SeasonStatus::Live => return "live",
+			SeasonStatus::Finished => return "finished",
+		}
+	}
+
+	pub fn parse(status: &str) -> Result<Self, ApiError>
+	{
+		match status
+		{
+			"draft" => return Ok(SeasonStatus::Draft),
+			"enrollment" => return Ok(SeasonStatus::Enrollment),
+			"live" => return Ok(SeasonStatus::Live),
+			"finished" => return Ok(SeasonStatus::Finished),
+			_ => return Err(ApiError::Validation(format!("Unknown season status [{}]", status))),
+		}
+	}
+
+	// One step forward at a time. Enrollment can go back to draft to fix the cast, and a
+	// finished season can go back to live to correct its results.
+	pub fn can_become(&self, next: SeasonStatus) -> bool
+	{
+		return matches!((self, next),
+			(SeasonStatus::Draft, SeasonStatus::Enrollment)
+			| (SeasonStatus::Enrollment, SeasonStatus::Draft)
+			| (SeasonStatus::Enrollment, SeasonStatus::Live)
+			| (SeasonStatus::Live, SeasonStatus::Finished)
+			| (SeasonStatus::Finished, SeasonStatus::Live));
+	}
+}
+
+// Rounds, tribes and scoring rules can be added to and adjusted until the season is finished,
+// but only taken away before it goes live, since picks, events and stats hang off them.
+const UNTIL_FINISHED : [SeasonStatus; 3] = [SeasonStatus::Draft, SeasonStatus::Enrollment, SeasonStatus::Live];
+const UNTIL_LIVE : [SeasonStatus; 2] = [SeasonStatus::Draft, SeasonStatus::Enrollment];
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Contestant
 {
 	pub id: Option<i32>,
 	pub name: String,
+	// Tells apart contestants who share a name, e.g. "Mariano" for one Rob and "Cesternino" for
+	// another. Empty for most. Name and disambiguator together are unique.
+	#[serde(default)]
+	pub disambiguator: String,
 	pub id_showseason: Option<i32>,
 	pub nickname: Option<String>,
+	// The round they went out on and whether it was a medevac, both worked out from their events.
 	pub round_number: i32,
-	pub was_medically_evacuated: bool
+	pub was_medically_evacuated: bool,
+	#[serde(default)]
+	pub status: ContestantStatus,
+	// The tribe they are on as of their latest assignment, only filled in for contestants on a show.
+	#[serde(default)]
+	pub tribe: Option<String>,
+	#[serde(default)]
+	pub tribe_color: Option<String>,
+	#[serde(flatten)]
+	pub profile: ContestantProfile,
+	// File name of their uploaded photo, served under /api/contestant_photos. Only set by an upload.
+	#[serde(default)]
+	pub photo: Option<String>
+}
+
+// What players read about a contestant while ranking them. Sent as is to update a profile,
+// where anything left out is cleared.
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Debug)]
+#[serde(default)]
+pub struct ContestantProfile
+{
+	pub bio: Option<String>,
+	pub age: Option<i32>,
+	pub hometown: Option<String>,
+	pub occupation: Option<String>,
+	// Earlier seasons they played, by name, e.g. "Pearl Islands".
+	pub prior_seasons: Vec<String>
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ContestantStatus
+{
+	#[default]
+	Active,
+	VotedOut,
+	Medevaced,
+	Quit,
+	Jury,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ContestantEventType
+{
+	VotedOut,
+	Medevac,
+	Quit,
+	// Back in the game after being out, e.g. from Edge of Extinction.
+	Returned,
+	JoinedJury,
+	WonImmunity,
+	FoundIdol,
+	// Cancels the event in reverses_event_id, for taking back a mistake without rewriting the log.
+	Reversed,
+}
+
+impl ContestantEventType
+{
+	pub fn as_str(&self) -> &'static str
+	{
+		match self
+		{
+			ContestantEventType::VotedOut => return "voted_out",
+			ContestantEventType::Medevac => return "medevac",
+			ContestantEventType::Quit => return "quit",
+			ContestantEventType::Returned => return "returned",
+			ContestantEventType::JoinedJury => return "joined_jury",
+			ContestantEventType::WonImmunity => return "won_immunity",
+			ContestantEventType::FoundIdol => return "found_idol",
+			ContestantEventType::Reversed => return "reversed",
+		}
+	}
+
+	pub fn parse(event_type: &str) -> Result<Self, ApiError>
+	{
+		match event_type
+		{
+			"voted_out" => return Ok(ContestantEventType::VotedOut),
+			"medevac" => return Ok(ContestantEventType::Medevac),
+			"quit" => return Ok(ContestantEventType::Quit),
+			"returned" => return Ok(ContestantEventType::Returned),
+			"joined_jury" => return Ok(ContestantEventType::JoinedJury),
+			"won_immunity" => return Ok(ContestantEventType::WonImmunity),
+			"found_idol" => return Ok(ContestantEventType::FoundIdol),
+			"reversed" => return Ok(ContestantEventType::Reversed),
+			_ => return Err(ApiError::Validation(format!("Unknown contestant event [{}]", event_type))),
+		}
+	}
+
+	pub fn is_exit(&self) -> bool
+	{
+		return matches!(self, ContestantEventType::VotedOut | ContestantEventType::Medevac | ContestantEventType::Quit);
+	}
 }
 
+// One entry in a season's contestant event log. The log is only ever added to.
 #[derive(Serialize, Deserialize, Clone)]
-pub struct League
+pub struct ContestantEvent
 {
+	#[serde(default)]
 	pub id: Option<i32>,
-	pub name: String,
-	pub id_showseason: Option<i32>
+	pub contestant_id: i32,
+	pub round_number: i32,
+	pub event_type: ContestantEventType,
+	#[serde(default)]
+	pub recorded_at: Option<DateTime<Utc>>,
+	// Only set on `reversed` events.
+	#[serde(default)]
+	pub reverses_event_id: Option<i32>
 }
 
-impl GameShowManager
+impl ContestantEvent
 {
-	pub async fn create(		repository : Arc<gameshows_accessor::GameShowRepository>, 
-								league_repository_in : Arc<league_accessor::LeagueRepository>) -> Self
-	{	
-		let game_repository: GameShowManager = GameShowManager
+	// The events that still count, leaving out reversals and the events they cancel.
+	pub fn still_standing(events: &[ContestantEvent]) -> Vec<&ContestantEvent>
+	{
+		let reversed_ids: Vec<i32> = events.iter().filter_map(|event: &ContestantEvent| event.reverses_event_id).collect();
+
+		return events.iter()
+			.filter(|event: &&ContestantEvent| event.event_type != ContestantEventType::Reversed)
+			.filter(|event: &&ContestantEvent| !event.id.is_some_and(|id: i32| reversed_ids.contains(&id)))
+			.collect();
+	}
+}
+
+// What a contestant did in one round, for leagues that award points for it. Counts default to
+// zero and flags to false, so a gamemaster only has to send what happened.
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct ContestantRoundStats
+{
+	pub contestant_id: i32,
+	pub round_number: i32,
+	#[serde(default)]
+	pub won_individual_immunity: bool,
+	#[serde(default)]
+	pub won_reward: bool,
+	#[serde(default)]
+	pub idols_found: i32,
+	#[serde(default)]
+	pub idols_played: i32,
+	#[serde(default)]
+	pub votes_received: i32,
+	#[serde(default)]
+	pub cast_correct_vote: bool
+}
+
+impl ContestantRoundStats
+{
+	pub fn validate(&self) -> Result<(), ApiError>
+	{
+		for (count, stat) in [(self.idols_found, "idols_found"), (self.idols_played, "idols_played"), (self.votes_received, "votes_received")]
 		{
-			repo : repository,
-			league_repository : league_repository_in
-		};
+			if count < 0
+			{
+				return Err(ApiError::Validation(format!("[{}] can't be negative, got [{}]", stat, count)));
+			}
+		}
 
-		return game_repository;
+		return Ok(());
 	}
+}
 
-	pub async fn collect_gameshows(&self) -> Result<Vec<GameShow>, String>
+pub const MAX_CONTESTANT_AGE : i32 = 120;
+
+impl ContestantProfile
+{
+	// Blank text is dropped rather than stored, and the age has to be believable.
+	pub fn normalized(&self) -> Result<ContestantProfile, ApiError>
 	{
-		return self.repo.collect_game_shows().await;
+		if let Some(age) = self.age && !(1..=MAX_CONTESTANT_AGE).contains(&age)
+		{
+			return Err(ApiError::Validation(format!("age has to be between 1 and {}, not [{}]", MAX_CONTESTANT_AGE, age)));
+		}
+
+		return Ok(ContestantProfile
+		{
+			bio: non_empty(&self.bio),
+			age: self.age,
+			hometown: non_empty(&self.hometown),
+			occupation: non_empty(&self.occupation),
+			prior_seasons: self.prior_seasons.iter()
+				.map(|season: &String| season.trim().to_string())
+				.filter(|season: &String| !season.is_empty())
+				.collect()
+		});
 	}
+}
 
-	pub async fn add_gameshow_and_refresh(&self, gameshow: &GameShow) -> Result<Vec<GameShow>, String>
+impl Contestant
+{
+	// "Rob (Mariano)", or just the name when there is no disambiguator.
+	pub fn display_name(&self) -> String
 	{
-		self.repo.add_gameshow(&gameshow).await?;
-		return self.repo.collect_game_shows().await;
+		if self.disambiguator.is_empty()
+		{
+			return self.name.clone();
+		}
+
+		return format!("{} ({})", self.name, self.disambiguator);
 	}
 
-	pub async fn delete_gameshow_and_refresh(&self, id: i32) -> Result<Vec<GameShow>, String>
+	// Replays the contestant's events, in round order, to fill in where they stand now.
+	// Reversed events are skipped as if they never happened.
+	pub fn apply_events(&mut self, events: &[ContestantEvent])
 	{
-		self.delete_gameshow(id).await.map_err(|e| e.to_string())?;
-		return self.collect_gameshows().await;
+		let mut own_events: Vec<&ContestantEvent> = ContestantEvent::still_standing(events).into_iter()
+			.filter(|event: &&ContestantEvent| Some(event.contestant_id) == self.id)
+			.collect();
+		own_events.sort_by_key(|event: &&ContestantEvent| (event.round_number, event.id));
+
+		self.status = ContestantStatus::Active;
+		self.round_number = scoring_engine::NOT_ELIMINATED;
+		self.was_medically_evacuated = false;
+
+		for event in own_events
+		{
+			match event.event_type
+			{
+				ContestantEventType::VotedOut | ContestantEventType::Medevac | ContestantEventType::Quit =>
+				{
+					self.round_number = event.round_number;
+					self.was_medically_evacuated = event.event_type == ContestantEventType::Medevac;
+					self.status = match event.event_type
+					{
+						ContestantEventType::Medevac => ContestantStatus::Medevaced,
+						ContestantEventType::Quit => ContestantStatus::Quit,
+						_ => ContestantStatus::VotedOut,
+					};
+				}
+				ContestantEventType::Returned =>
+				{
+					self.round_number = scoring_engine::NOT_ELIMINATED;
+					self.was_medically_evacuated = false;
+					self.status = ContestantStatus::Active;
+				}
+				ContestantEventType::JoinedJury => self.status = ContestantStatus::Jury,
+				ContestantEventType::WonImmunity | ContestantEventType::FoundIdol | ContestantEventType::Reversed => {}
+			}
+		}
 	}
 
-	pub async fn delete_gameshow(&self, id: i32) -> Result<(), String>
+	// Whether `event_type` makes sense for someone in the contestant's current state.
+	pub fn check_event_allowed(&self, event_type: ContestantEventType) -> Result<(), ApiError>
 	{
-		return self.repo.delete_game_show(id).await;
+		let is_in_game: bool = self.status == ContestantStatus::Active;
+		let allowed: bool = match event_type
+		{
+			ContestantEventType::Returned => !is_in_game,
+			ContestantEventType::JoinedJury => !is_in_game && self.status != ContestantStatus::Jury,
+			_ => is_in_game,
+		};
+
+		if !allowed
+		{
+			let state: &str = if is_in_game { "still in the game" } else { "out of the game" };
+			return Err(ApiError::Conflict(format!("Contestant [{}] is {}, so they can't have a [{}] event", self.name, state, event_type.as_str())));
+		}
+
+		return Ok(());
+	}
+}
+
+// Tribes belong to one season. Colors are #RRGGBB so the portals can use them as they are.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Tribe
+{
+	pub id: Option<i32>,
+	pub game_show_id: Option<i32>,
+	pub name: String,
+	pub color: String
+}
+
+// A contestant is on `tribe_id` from `from_round` until their next assignment, which is how
+// swaps and the merge are recorded.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TribeAssignment
+{
+	pub contestant_id: i32,
+	pub tribe_id: i32,
+	pub from_round: i32
+}
+
+// Bodies for the enroll, elimination and medevac routes. Every field is optional here so a
+// missing one can be reported by name instead of failing the whole body.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct EnrollRequest
+{
+	pub contestant_id: Option<i32>,
+	pub game_show_id: Option<i32>,
+	pub nickname: Option<String>,
+	pub tribe: Option<String>
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct EliminationRequest
+{
+	pub contestant_id: Option<i32>,
+	pub game_show_id: Option<i32>,
+	pub round_number: Option<i32>
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct MedevacRequest
+{
+	pub contestant_id: Option<i32>,
+	pub game_show_id: Option<i32>,
+	pub round_number: Option<i32>
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ReinstateRequest
+{
+	pub contestant_id: Option<i32>,
+	pub game_show_id: Option<i32>,
+	pub round_number: Option<i32>
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LeagueStandings
+{
+	pub league_id: i32,
+	pub name: String,
+	pub standings: Vec<Standing>
+}
+
+// The contestant as they stand after being reinstated, and the fresh standings of every league
+// on the season, since any of them may have scored the elimination.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Reinstatement
+{
+	pub contestant: Contestant,
+	pub rescored_leagues: Vec<LeagueStandings>
+}
+
+// One line of a cast list being imported into a season, either an element of a JSON array or
+// a CSV row with a header naming the columns. Only the name is required.
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct CastMember
+{
+	pub name: Option<String>,
+	pub disambiguator: Option<String>,
+	pub nickname: Option<String>,
+	pub tribe: Option<String>,
+	pub bio: Option<String>
+}
+
+impl CastMember
+{
+	pub fn parse_csv(csv_text: &str) -> Result<Vec<CastMember>, ApiError>
+	{
+		let mut reader: csv::Reader<&[u8]> = csv::ReaderBuilder::new()
+			.trim(csv::Trim::All)
+			.from_reader(csv_text.as_bytes());
+
+		let has_name_column: bool = reader.headers()
+			.map_err(|e: csv::Error| ApiError::Validation(format!("The CSV header could not be read: {}", e)))?
+			.iter()
+			.any(|column: &str| column.eq_ignore_ascii_case("name"));
+
+		if !has_name_column
+		{
+			return Err(ApiError::Validation("The CSV needs a header row with a name column".to_string()));
+		}
+
+		return reader.deserialize::<CastMember>()
+			.enumerate()
+			.map(|(index, record): (usize, Result<CastMember, csv::Error>)|
+				record.map_err(|e: csv::Error| ApiError::Validation(format!("Row {} of the CSV could not be read: {}", index + 1, e))))
+			.collect::<Result<Vec<CastMember>, ApiError>>();
 	}
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum CastImportOutcome
+{
+	// A new contestant was created and enrolled.
+	Created,
+	// An existing contestant with that name was enrolled.
+	Enrolled,
+	AlreadyEnrolled,
+	Failed,
+}
+
+// What happened to one row of an import. Rows are numbered from 1 in the order they were sent.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CastImportResult
+{
+	pub row: usize,
+	pub name: String,
+	pub contestant_id: Option<i32>,
+	pub outcome: CastImportOutcome,
+	pub message: Option<String>
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RoundStatus
+{
+	Upcoming,
+	Open,
+	Locked,
+	Scored,
+}
 
-	pub async fn create_contestant(&self, contestant: &Contestant) -> Result<(), String>
+impl RoundStatus
+{
+	pub fn as_str(&self) -> &'static str
 	{
-		return self.repo.create_contestant(contestant).await;
+		match self
+		{
+			RoundStatus::Upcoming => return "upcoming",
+			RoundStatus::Open => return "open",
+			RoundStatus::Locked => return "locked",
+			RoundStatus::Scored => return "scored",
+		}
 	}
 
-	pub async fn select_contestant_by_name(&self, name: String) -> Result<Contestant, String>
+	pub fn parse(status: &str) -> Result<Self, ApiError>
 	{
-		return self.repo.select_contestant_by_name(name).await;
+		match status
+		{
+			"upcoming" => return Ok(RoundStatus::Upcoming),
+			"open" => return Ok(RoundStatus::Open),
+			"locked" => return Ok(RoundStatus::Locked),
+			"scored" => return Ok(RoundStatus::Scored),
+			_ => return Err(ApiError::Validation(format!("Unknown round status [{}]", status))),
+		}
 	}
 
-	pub async fn delete_contestant(&self, name: &str) -> Result<(), String>
+	pub fn accepts_picks(&self) -> bool
 	{
-		return self.repo.delete_contestant(name).await;
+		return matches!(self, RoundStatus::Upcoming | RoundStatus::Open);
 	}
+}
+
+// One episode of a show season. Picks and eliminations both have to point at one of these.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Round
+{
+	pub id: Option<i32>,
+	pub game_show_id: Option<i32>,
+	pub round_number: i32,
+	pub title: String,
+	pub air_date: Option<DateTime<Utc>>,
+	pub lock_time: Option<DateTime<Utc>>,
+	pub status: RoundStatus
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct League
+{
+	pub id: Option<i32>,
+	pub name: String,
+	pub id_showseason: Option<i32>
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LeaguePick
+{
+	pub league_id: i32,
+	pub user_id: i32,
+	pub round_number: i32,
+	pub contestant_id: i32,
+	pub rank_pick: i32
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ScoringRules
+{
+	pub id: Option<i32>,
+	pub league_id: Option<i32>,
+	pub survival_points: i32,
+	pub boot_prediction_bonus: i32,
+	pub medevac_points: i32,
+	pub medevac_counts_as_boot: bool,
+	// Points for each picked contestant's round stats. Rules saved before these existed leave them at 0.
+	#[serde(default)]
+	pub immunity_win_points: i32,
+	#[serde(default)]
+	pub reward_win_points: i32,
+	#[serde(default)]
+	pub idol_found_points: i32,
+	#[serde(default)]
+	pub idol_played_points: i32,
+	#[serde(default)]
+	pub vote_received_points: i32,
+	#[serde(default)]
+	pub correct_vote_points: i32
+}
+
+// Used for tribes created from a tribe name alone, e.g. by a cast import.
+pub const DEFAULT_TRIBE_COLOR : &str = "#808080";
 
-	pub async fn collect_all_contestants(&self) -> Result<Vec<Contestant>, String>
+impl Tribe
+{
+	pub fn validate(&self) -> Result<(), ApiError>
 	{
-		return self.repo.collect_all_contestants().await;
+		if self.name.trim().is_empty()
+		{
+			return Err(ApiError::Validation("Tribes need a name".to_string()));
+		}
+
+		let is_hex_color: bool = self.color.len() == 7
+			&& self.color.starts_with('#')
+			&& self.color[1..].chars().all(|digit: char| digit.is_ascii_hexdigit());
+
+		if !is_hex_color
+		{
+			return Err(ApiError::Validation(format!("Tribe color [{}] has to look like #RRGGBB", self.color)));
+		}
+
+		return Ok(());
 	}
+}
 
-	pub async fn enter_contestant_onto_show(&self, contestant_id: i32, game_show_id: i32, nickname: String) -> Result<(), String>
+// With a disambiguator only that exact contestant matches. Without one the name has to be
+// unambiguous: a single contestant by that name is found whatever their disambiguator, several
+// are a Conflict the caller resolves by asking again with one.
+pub async fn find_contestant_by_name(repo: &dyn gameshows_accessor::GameShowRepository, name: &str, disambiguator: Option<&str>) -> Result<Contestant, ApiError>
+{
+	let mut matches: Vec<Contestant> = repo.select_contestants_by_name(name).await?;
+	if let Some(disambiguator) = disambiguator
 	{
-		return self.repo.enter_contestant_onto_show(contestant_id, game_show_id, nickname).await;
+		matches.retain(|contestant: &Contestant| contestant.disambiguator == disambiguator);
 	}
 
-	pub async fn eliminiate_contestant_from_show(&self, contestant_id: i32, game_show_id: i32, round_number : i32) -> Result<(), String>
+	match matches.len()
 	{
-		return self.repo.eliminate_contestant_from_show(contestant_id, game_show_id, round_number).await;
+		0 =>
+		{
+			let wanted: String = disambiguator.map_or(name.to_string(), |disambiguator: &str| format!("{} ({})", name, disambiguator));
+			return Err(ApiError::NotFound(format!("Contestant [{}] not found", wanted)));
+		}
+		1 => return Ok(matches.remove(0)),
+		count => return Err(ApiError::Conflict(format!("There are {} contestants named [{}], pick one by id or disambiguator", count, name))),
 	}
+}
+
+// Puts a newly enrolled contestant on the season's tribe called `tribe_name` from round 1,
+// creating the tribe if the season doesn't have one by that name yet.
+pub async fn assign_starting_tribe(repo: &dyn gameshows_accessor::GameShowRepository, game_show_id: i32, contestant_id: i32, tribe_name: &str) -> Result<(), ApiError>
+{
+	let existing: Option<Tribe> = repo.collect_tribes(game_show_id).await?
+		.into_iter()
+		.find(|tribe: &Tribe| tribe.name == tribe_name);
 
-	pub async fn medically_evacuate_contestant_from_show(&self, contestant_id: i32, game_show_id: i32, round_number : i32) -> Result<(), String>
+	let tribe_id: i32 = match existing
+	{
+		Some(tribe) => required(tribe.id, "tribe.id")?,
+		None => repo.create_tribe(game_show_id, &Tribe { id: None, game_show_id: Some(game_show_id), name: tribe_name.to_string(), color: DEFAULT_TRIBE_COLOR.to_string() }).await?,
+	};
+
+	return repo.assign_contestant_to_tribe(game_show_id, &TribeAssignment { contestant_id, tribe_id, from_round: 1 }).await;
+}
+
+// Blank cells in a cast list count as missing.
+fn non_empty(value: &Option<String>) -> Option<String>
+{
+	return value.as_deref().map(str::trim).filter(|value: &&str| !value.is_empty()).map(str::to_string);
+}
+
+impl ScoringRules
+{
+	// Used for leagues that have not saved a ruleset of their own.
+	pub fn default_for_league(league_id: i32) -> Self
 	{
-		let elimination_result = self.repo.eliminate_contestant_from_show(contestant_id, game_show_id, round_number).await;
-		if elimination_result.is_err()
+		ScoringRules
 		{
-			return Err(elimination_result.err().unwrap_or("Unable to Eliminate Candidate".to_string()));
+			id: None,
+			league_id: Some(league_id),
+			survival_points: 1,
+			boot_prediction_bonus: 3,
+			medevac_points: 0,
+			medevac_counts_as_boot: false,
+			immunity_win_points: 0,
+			reward_win_points: 0,
+			idol_found_points: 0,
+			idol_played_points: 0,
+			vote_received_points: 0,
+			correct_vote_points: 0
 		}
+	}
+}
+
+impl GameShowManager
+{
+	pub fn create(		repository : Arc<dyn gameshows_accessor::GameShowRepository>, 
+								league_repository_in : Arc<dyn league_accessor::LeagueRepository>,
+								storage_in : Arc<StorageConnector>) -> Self
+	{	
+		let game_repository: GameShowManager = GameShowManager
+		{
+			repo : repository,
+			league_repository : league_repository_in,
+			storage : storage_in
+		};
 
-		return self.repo.medically_evacuate_contestant_from_show(contestant_id, game_show_id).await;
+		return game_repository;
 	}
 
-	pub async fn create_league(&self, league: &League) -> Result<(), String>
+	pub async fn collect_gameshows(&self) -> Result<Vec<GameShow>, ApiError>
 	{
-		println!("G.S.M. create_league[{}], [{}]", league.name, league.id_showseason.unwrap_or(-1));
-		return self.league_repository.create_league(league).await;
+		return self.repo.collect_game_shows().await;
 	}
 
-	pub async fn collect_leagues(&self, id_showseason : i32) -> Result<Vec<League>, String>
+	// Every new season starts in draft, whatever status it was sent with.
+	pub async fn add_gameshow_and_refresh(&self, gameshow: &GameShow) -> Result<Vec<GameShow>, ApiError>
 	{
-		return self.league_repository.collect_leagues(id_showseason).await;
+		Self::validate_season_dates(gameshow)?;
+		self.repo.add_gameshow(&GameShow { status: SeasonStatus::Draft, ..gameshow.clone() }).await?;
+		return self.repo.collect_game_shows().await;
+	}
+
+	// Renames the season, moves its dates, or moves it to a new status if it may go there.
+	// Going live stamps the start date and finishing the end date, unless they are already set.
+	pub async fn update_gameshow(&self, id: i32, gameshow: &GameShow) -> Result<GameShow, ApiError>
+	{
+		let current: GameShow = self.repo.select_game_show(id).await?;
+		if gameshow.status != current.status && !current.status.can_become(gameshow.status)
+		{
+			return Err(ApiError::Conflict(format!("Showseason [{}] can't go from {} to {}", id, current.status.as_str(), gameshow.status.as_str())));
+		}
+
+		let mut updated: GameShow = GameShow { id: Some(id), ..gameshow.clone() };
+		if updated.status != current.status && updated.status == SeasonStatus::Live && updated.start_date.is_none()
+		{
+			updated.start_date = Some(Utc::now());
+		}
+
+		if updated.status != current.status && updated.status == SeasonStatus::Finished && updated.end_date.is_none()
+		{
+			updated.end_date = Some(Utc::now());
+		}
+
+		Self::validate_season_dates(&updated)?;
+		self.repo.update_game_show(id, &updated).await?;
+		return self.repo.select_game_show(id).await;
 	}
 
-	pub async fn delete_league(&self, id: i32) -> Result<(), String>
+	fn validate_season_dates(gameshow: &GameShow) -> Result<(), ApiError>
 	{
-		return self.league_repository.delete_league(id).await;
+		if let (Some(start_date), Some(end_date)) = (gameshow.start_date, gameshow.end_date) && end_date < start_date
+		{
+			return Err(ApiError::Validation(format!("Showseason [{}] can't end before it starts", gameshow.name)));
+		}
+
+		return Ok(());
 	}
 
-	pub async fn add_user_to_league(&self, user_id: i32, league_id: i32) -> Result<(), String>
+	// Fails with Conflict unless the season is in `status`. `action` finishes the message,
+	// e.g. "the cast can only be changed".
+	async fn require_season_status(&self, game_show_id: i32, status: SeasonStatus, action: &str) -> Result<(), ApiError>
 	{
-		return self.league_repository.add_user_to_league(user_id, league_id).await;
+		return self.require_season_status_in(game_show_id, &[status], action).await;
 	}
 
-	pub async fn remove_user_from_league(&self, user_id: i32, league_id: i32) -> Result<(), String>
+	async fn require_season_status_in(&self, game_show_id: i32, allowed: &[SeasonStatus], action: &str) -> Result<(), ApiError>
 	{
-		return self.league_repository.remove_user_from_league(user_id, league_id).await;
+		let game_show: GameShow = self.repo.select_game_show(game_show_id).await?;
+		if !allowed.contains(&game_show.status)
+		{
+			let allowed_names: Vec<&str> = allowed.iter().map(SeasonStatus::as_str).collect();
+			let allowed_list: String = match allowed_names.split_last()
+			{
+				Some((last, [])) => last.to_string(),
+				Some((last, rest)) => format!("{} or {}", rest.join(", "), last),
+				None => String::new(),
+			};
+
+			return Err(ApiError::Conflict(format!("Showseason [{}] is {}, {} while it is {}", game_show_id, game_show.status.as_str(), action, allowed_list)));
+		}
+
+		return Ok(());
 	}
 
-	pub async fn fetch_contestants_on_show(&self, game_show_id: i32) -> Result<Vec<Contestant>, String>
+	// Leagues without a season have nothing to protect.
+	async fn require_league_season_status_in(&self, league_id: i32, allowed: &[SeasonStatus], action: &str) -> Result<(), ApiError>
 	{
-		return self.repo.fetch_contestants_on_show(game_show_id).await;
+		let league: League = self.league_repository.select_league(league_id).await?;
+		if let Some(game_show_id) = league.id_showseason
+		{
+			self.require_season_status_in(game_show_id, allowed, action).await?;
+		}
+
+		return Ok(());
 	}
 
-	pub async fn set_league_pick(&self, league_id: i32, user_id: i32, round_number: i32, contestant_id: i32, rank_pick: i32) -> Result<(), String>
+	pub async fn delete_gameshow_and_refresh(&self, id: i32) -> Result<Vec<GameShow>, ApiError>
 	{
-		return self.league_repository.set_league_pick(league_id, user_id, round_number, contestant_id, rank_pick).await;
+		self.delete_gameshow(id).await?;
+		return self.collect_gameshows().await;
+	}
+
+	pub async fn delete_gameshow(&self, id: i32) -> Result<(), ApiError>
+	{
+		return self.repo.delete_game_show(id).await;
 	}
 
-}
\ No newline at end of file
+	pub async fn create_contestant(&self, contestant: &Contestant) -> Result<Contestant, ApiError>
+	{
+		let name: String = contestant.name.trim().to_string();
+		if name.is_empty()
+		{
+			return Err(ApiError::Validation("Missing required field [name]".to_string()));
+		}
+
+		let new_contestant: Contestant = Contestant
+		{
+			name,
+			disambiguator: contestant.disambiguator.trim().to_string(),
+			profile: contestant.profile.normalized()?,
+			photo: None,
+			..contestant.clone()
+		};
+		return self.repo.create_contestant(&new_contestant).await;
+	}
+
+	pub async fn select_contestant(&self, id: i32) -> Result<Contestant, ApiError>
+	{
+		return self.repo.select_contestant(id).await;
+	}
+
+	pub async fn select_contestant_by_name(&self, name: &str, disambiguator: Option<&str>) -> Result<Contestant, ApiError>
+	{
+		return find_contestant_by_name(self.repo.as_ref(), name, disambiguator).await;
+	}
+
+	// Takes their photo with them. Only while every season they are cast on is still a draft,
+	// since deleting them drops their events, stats and the league picks naming them.
+	pub async fn delete_contestant(&self, photos: &PhotoStore, id: i32) -> Result<(), ApiError>
+	{
+		let contestant: Contestant = self.repo.select_contestant(id).await?;
+		if let Some(game_show) = self.repo.collect_game_shows_for_contestant(id).await?.into_iter().find(|game_show: &GameShow| game_show.status != SeasonStatus::Draft)
+		{
+			return Err(ApiError::Conflict(format!("Contestant [{}] is cast on showseason [{}], which is {}, so they can't be deleted", id, game_show.name, game_show.status.as_str())));
+		}
+
+		self.repo.delete_contestant(id).await?;
+
+		if let Some(photo) = contestant.photo
+		{
+			photos.remove(&photo).await;
+		}
+
+		return Ok(());
+	}
+
+	pub async fn update_contestant_profile(&self, id: i32, profile: &ContestantProfile) -> Result<Contestant, ApiError>
+	{
+		return self.repo.update_contestant_profile(id, &profile.normalized()?).await;
+	}
+
+	// Saves the new photo before pointing the contestant at it, then drops the one it replaces.
+	pub async fn upload_contestant_photo(&self, photos: &PhotoStore, id: i32, content_type: &ContentType, bytes: &[u8]) -> Result<Contestant, ApiError>
+	{
+		let contestant: Contestant = self.repo.select_contestant(id).await?;
+		let file_name: String = photos.save(id, content_type, bytes).await?;
+
+		if let Err(e) = self.repo.set_contestant_photo(id, Some(&file_name)).await
+		{
+			photos.remove(&file_name).await;
+			return Err(e);
+		}
+
+		if let Some(old_photo) = contestant.photo
+		{
+			photos.remove(&old_photo).await;
+		}
+
+		return Ok(Contestant { photo: Some(file_name), ..contestant });
+	}
+
+	pub async fn delete_contestant_photo(&self, photos: &PhotoStore, id: i32) -> Result<Contestant, ApiError>
+	{
+		let contestant: Contestant = self.repo.select_contestant(id).await?;
+		let Some(photo) = contestant.photo.clone() else
+		{
+			return Err(ApiError::NotFound(format!("Contestant [{}] has no photo", id)));
+		};
+
+		self.repo.set_contestant_photo(id, None).await?;
+		photos.remove(&photo).await;
+
+		return Ok(Contestant { photo: None, ..contestant });
+	}
+
+	pub async fn collect_all_contestants(&self) -> Result<Vec<Contestant>, ApiError>
+	{
+		return self.repo.collect_all_contestants().await;
+	}
+
+	// Storage narrows the candidates down to the season and to names holding a piece of the query,
+	// ranking is done here so both storages order names the same way.
+	pub async fn search_contestants(&self, query: &str, season: Option<i32>, limit: Option<i64>, offset: Option<i64>) -> Result<ContestantPage, ApiError>
+	{
+		let (limit, offset): (usize, usize) = contestant_search::page_bounds(limit, offset)?;
+		if let Some(game_show_id) = season
+		{
+			self.repo.select_game_show(game_show_id).await?;
+		}
+
+		let fragments: Vec<String> = contestant_search::prefilter_fragments(query);
+		let candidates: Vec<Contestant> = self.repo.search_contestants(season, &fragments).await?;
+
+		let matches: Vec<Contestant> = contestant_search::rank_contestants(candidates, query);
+		let total: usize = matches.len();
+		let contestants: Vec<Contestant> = matches.into_iter().skip(offset).take(limit).collect();
+
+		return Ok(ContestantPage { total, limit, offset, contestants });
+	}
+
+	// Every season the contestant played, where they finished and how the leagues picked them.
+	pub async fn collect_contestant_history(&self, contestant_id: i32) -> Result<ContestantHistory, ApiError>
+	{
+		let contestant: Contestant = self.repo.select_contestant(contestant_id).await?;
+
+		let mut seasons: Vec<SeasonAppearance> = Vec::new();
+		for game_show in self.repo.collect_game_shows_for_contestant(contestant_id).await?
+		{
+			let game_show_id: i32 = required(game_show.id, "game_show_id")?;
+			let cast: Vec<Contestant> = self.repo.fetch_contestants_on_show(game_show_id).await?;
+
+			let mut picks: Vec<LeaguePick> = Vec::new();
+			for league in self.league_repository.collect_leagues(game_show_id).await?
+			{
+				picks.extend(self.league_repository.collect_league_picks(required(league.id, "league_id")?).await?);
+			}
+
+			seasons.extend(contestant_history::season_appearance(&game_show, &cast, &picks, contestant_id));
+		}
+
+		return Ok(contestant_history::summarize(contestant, seasons));
+	}
+
+	pub async fn enter_contestant_onto_show(&self, request: &EnrollRequest) -> Result<(), ApiError>
+	{
+		let contestant_id: i32 = required(request.contestant_id, "contestant_id")?;
+		let game_show_id: i32 = required(request.game_show_id, "game_show_id")?;
+		let nickname: String = request.nickname.clone().unwrap_or_default();
+		self.require_season_status(game_show_id, SeasonStatus::Draft, "the cast can only be changed").await?;
+
+		return self.storage.transaction(async
+		{
+			self.repo.enter_contestant_onto_show(contestant_id, game_show_id, nickname).await?;
+
+			if let Some(tribe_name) = non_empty(&request.tribe)
+			{
+				assign_starting_tribe(self.repo.as_ref(), game_show_id, contestant_id, &tribe_name).await?;
+			}
+
+			return Ok(());
+		}).await;
+	}
+
+	// Contestants are matched to existing ones by name and disambiguator, see find_contestant_by_name,
+	// and created when there is no match.
+	// Every row is imported on its own, so one bad row doesn't stop the rest of the cast.
+	pub async fn import_cast(&self, game_show_id: i32, cast: &[CastMember]) -> Result<Vec<CastImportResult>, ApiError>
+	{
+		if cast.is_empty()
+		{
+			return Err(ApiError::Validation("The cast list is empty".to_string()));
+		}
+
+		self.require_season_status(game_show_id, SeasonStatus::Draft, "the cast can only be changed").await?;
+		let mut enrolled_ids: Vec<i32> = self.repo.fetch_contestants_on_show(game_show_id).await?
+			.iter()
+			.filter_map(|contestant: &Contestant| contestant.id)
+			.collect();
+
+		let mut results: Vec<CastImportResult> = Vec::new();
+		for (index, cast_member) in cast.iter().enumerate()
+		{
+			let name: String = cast_member.name.clone().unwrap_or_default().trim().to_string();
+			let import_result: Result<(Contestant, CastImportOutcome), ApiError> = self.import_cast_member(game_show_id, &name, cast_member, &enrolled_ids).await;
+
+			let result: CastImportResult = match import_result
+			{
+				Ok((contestant, outcome)) =>
+				{
+					enrolled_ids.extend(contestant.id);
+					CastImportResult { row: index + 1, name, contestant_id: contestant.id, outcome, message: None }
+				}
+
+				Err(e) => CastImportResult { row: index + 1, name, contestant_id: None, outcome: CastImportOutcome::Failed, message: Some(e.message().to_string()) },
+			};
+
+			results.push(result);
+		}
+
+		return Ok(results);
+	}
+
+	async fn import_cast_member(&self, game_show_id: i32, name: &str, cast_member: &CastMember, enrolled_ids: &[i32]) -> Result<(Contestant, CastImportOutcome), ApiError>
+	{
+		if name.is_empty()
+		{
+			return Err(ApiError::Validation("Missing required field [name]".to_string()));
+		}
+
+		return self.storage.transaction(async
+		{
+			let disambiguator: Option<String> = non_empty(&cast_member.disambiguator);
+			let (contestant, outcome): (Contestant, CastImportOutcome) = match find_contestant_by_name(self.repo.as_ref(), name, disambiguator.as_deref()).await
+			{
+				Ok(existing) if existing.id.is_some_and(|id: i32| enrolled_ids.contains(&id)) => return Ok((existing, CastImportOutcome::AlreadyEnrolled)),
+				Ok(existing) => (existing, CastImportOutcome::Enrolled),
+				Err(ApiError::NotFound(_)) =>
+				{
+					let new_contestant: Contestant = Contestant
+					{
+						id: None,
+						name: name.to_string(),
+						disambiguator: disambiguator.unwrap_or_default(),
+						id_showseason: None,
+						nickname: None,
+						round_number: scoring_engine::NOT_ELIMINATED,
+						was_medically_evacuated: false,
+						status: ContestantStatus::Active,
+						tribe: None,
+						tribe_color: None,
+						profile: ContestantProfile { bio: non_empty(&cast_member.bio), ..ContestantProfile::default() },
+						photo: None
+					};
+
+					(self.repo.create_contestant(&new_contestant).await?, CastImportOutcome::Created)
+				}
+				Err(e) => return Err(e),
+			};
+
+			let contestant_id: i32 = required(contestant.id, "contestant_id")?;
+			let nickname: String = non_empty(&cast_member.nickname).unwrap_or_default();
+			self.repo.enter_contestant_onto_show(contestant_id, game_show_id, nickname).await?;
+
+			if let Some(tribe_name) = non_empty(&cast_member.tribe)
+			{
+				assign_starting_tribe(self.repo.as_ref(), game_show_id, contestant_id, &tribe_name).await?;
+			}
+
+			return Ok((contestant, outcome));
+		}).await;
+	}
+
+	pub async fn eliminiate_contestant_from_show(&self, request: &EliminationRequest) -> Result<(), ApiError>
+	{
+		let contestant_id: i32 = required(request.contestant_id, "contestant_id")?;
+		let game_show_id: i32 = required(request.game_show_id, "game_show_id")?;
+		let round_number: i32 = required(request.round_number, "round_number")?;
+
+		let event: ContestantEvent = ContestantEvent { id: None, contestant_id, round_number, event_type: ContestantEventType::VotedOut, recorded_at: None, reverses_event_id: None };
+		self.record_contestant_event(game_show_id, &event).await?;
+		return Ok(());
+	}
+
+	pub async fn medically_evacuate_contestant_from_show(&self, request: &MedevacRequest) -> Result<(), ApiError>
+	{
+		let contestant_id: i32 = required(request.contestant_id, "contestant_id")?;
+		let game_show_id: i32 = required(request.game_show_id, "game_show_id")?;
+		let round_number: i32 = required(request.round_number, "round_number")?;
+
+		let event: ContestantEvent = ContestantEvent { id: None, contestant_id, round_number, event_type: ContestantEventType::Medevac, recorded_at: None, reverses_event_id: None };
+		self.record_contestant_event(game_show_id, &event).await?;
+		return Ok(());
+	}
+
+	// Undoes an elimination or medevac recorded by mistake, then rescores the season's leagues.
+	pub async fn reinstate_contestant(&self, request: &ReinstateRequest) -> Result<Reinstatement, ApiError>
+	{
+		let contestant_id: i32 = required(request.contestant_id, "contestant_id")?;
+		let game_show_id: i32 = required(request.game_show_id, "game_show_id")?;
+		let round_number: i32 = required(request.round_number, "round_number")?;
+
+		self.require_season_status(game_show_id, SeasonStatus::Live, "results can only be corrected").await?;
+		self.storage.transaction(async
+		{
+			let events: Vec<ContestantEvent> = self.repo.collect_contestant_events(game_show_id).await?;
+			let own_events: Vec<&ContestantEvent> = ContestantEvent::still_standing(&events).into_iter()
+				.filter(|event: &&ContestantEvent| event.contestant_id == contestant_id)
+				.collect();
+
+			let exit: &ContestantEvent = own_events.iter().copied()
+				.find(|event: &&ContestantEvent| event.round_number == round_number && event.event_type.is_exit())
+				.ok_or_else(|| gameshows_accessor::not_eliminated(game_show_id, contestant_id, round_number))?;
+
+			// A jury seat from that round on only followed from the exit, so it goes too.
+			let jury_seats = own_events.iter().copied()
+				.filter(|event: &&ContestantEvent| event.round_number >= round_number && event.event_type == ContestantEventType::JoinedJury);
+
+			for reversed_event in std::iter::once(exit).chain(jury_seats)
+			{
+				let reversal: ContestantEvent = ContestantEvent { id: None, contestant_id, round_number: reversed_event.round_number, event_type: ContestantEventType::Reversed, recorded_at: None, reverses_event_id: reversed_event.id };
+				self.repo.record_contestant_event(game_show_id, &reversal).await?;
+			}
+
+			return Ok(());
+		}).await?;
+
+		let contestant: Contestant = self.repo.fetch_contestants_on_show(game_show_id).await?
+			.into_iter()
+			.find(|contestant: &Contestant| contestant.id == Some(contestant_id))
+			.ok_or(ApiError::NotFound(format!("Contestant [{}] is not on showseason [{}]", contestant_id, game_show_id)))?;
+
+		return Ok(Reinstatement { contestant, rescored_leagues: self.rescore_leagues(game_show_id).await? });
+	}
+
+	async fn rescore_leagues(&self, game_show_id: i32) -> Result<Vec<LeagueStandings>, ApiError>
+	{
+		let mut rescored_leagues: Vec<LeagueStandings> = Vec::new();
+		for league in self.league_repository.collect_leagues(game_show_id).await?
+		{
+			let league_id: i32 = required(league.id, "league.id")?;
+			rescored_leagues.push(LeagueStandings { league_id, name: league.name, standings: self.calculate_league_standings(league_id).await? });
+		}
+
+		return Ok(rescored_leagues);
+	}
+
+	pub async fn collect_contestant_events(&self, game_show_id: i32) -> Result<Vec<ContestantEvent>, ApiError>
+	{
+		return self.repo.collect_contestant_events(game_show_id).await;
+	}
+
+	// Events have to happen in one of the season's rounds and fit where the contestant stands,
+	// e.g. only someone who is out can return or join the jury. Reversals only come from
+	// reinstate_contestant.
+	pub async fn record_contestant_event(&self, game_show_id: i32, event: &ContestantEvent) -> Result<Vec<ContestantEvent>, ApiError>
+	{
+		if event.event_type == ContestantEventType::Reversed
+		{
+			return Err(ApiError::Validation("Events are reversed by reinstating the contestant, not recorded directly".to_string()));
+		}
+
+		let event: &ContestantEvent = &ContestantEvent { reverses_event_id: None, ..event.clone() };
+		self.require_season_status(game_show_id, SeasonStatus::Live, "events can only be recorded").await?;
+		self.repo.select_round(game_show_id, event.round_number).await?;
+
+		return self.storage.transaction(async
+		{
+			let contestant: Contestant = self.repo.fetch_contestants_on_show(game_show_id).await?
+				.into_iter()
+				.find(|contestant: &Contestant| contestant.id == Some(event.contestant_id))
+				.ok_or(ApiError::NotFound(format!("Contestant [{}] is not on showseason [{}]", event.contestant_id, game_show_id)))?;
+
+			contestant.check_event_allowed(event.event_type)?;
+			self.repo.record_contestant_event(game_show_id, event).await?;
+
+			return self.repo.collect_contestant_events(game_show_id).await;
+		}).await;
+	}
+
+	pub async fn collect_round_stats(&self, game_show_id: i32) -> Result<Vec<ContestantRoundStats>, ApiError>
+	{
+		return self.repo.collect_round_stats(game_show_id).await;
+	}
+
+	// Stats belong to one of the season's rounds. Recording them again for the same contestant
+	// and round replaces what was there.
+	pub async fn record_round_stats(&self, game_show_id: i32, stats: &ContestantRoundStats) -> Result<Vec<ContestantRoundStats>, ApiError>
+	{
+		stats.validate()?;
+		self.require_season_status(game_show_id, SeasonStatus::Live, "round stats can only be recorded").await?;
+		self.repo.select_round(game_show_id, stats.round_number).await?;
+		self.repo.record_round_stats(game_show_id, stats).await?;
+
+		return self.repo.collect_round_stats(game_show_id).await;
+	}
+
+	pub async fn delete_round_stats(&self, game_show_id: i32, contestant_id: i32, round_number: i32) -> Result<Vec<ContestantRoundStats>, ApiError>
+	{
+		self.require_season_status(game_show_id, SeasonStatus::Live, "round stats can only be changed").await?;
+		self.repo.delete_round_stats(game_show_id, contestant_id, round_number).await?;
+		return self.repo.collect_round_stats(game_show_id).await;
+	}
+
+	pub async fn collect_rounds(&self, game_show_id: i32) -> Result<Vec<Round>, ApiError>
+	{
+		return self.repo.collect_rounds(game_show_id).await;
+	}
+
+	pub async fn create_round(&self, game_show_id: i32, round: &Round) -> Result<Vec<Round>, ApiError>
+	{
+		Self::validate_round(round)?;
+		self.require_season_status_in(game_show_id, &UNTIL_FINISHED, "rounds can only be changed").await?;
+		self.repo.create_round(game_show_id, round).await?;
+		return self.repo.collect_rounds(game_show_id).await;
+	}
+
+	pub async fn update_round(&self, game_show_id: i32, round_number: i32, round: &Round) -> Result<Vec<Round>, ApiError>
+	{
+		Self::validate_round(round)?;
+		self.require_season_status_in(game_show_id, &UNTIL_FINISHED, "rounds can only be changed").await?;
+		self.repo.update_round(game_show_id, round_number, round).await?;
+		return self.repo.collect_rounds(game_show_id).await;
+	}
+
+	pub async fn delete_round(&self, game_show_id: i32, round_number: i32) -> Result<Vec<Round>, ApiError>
+	{
+		self.require_season_status_in(game_show_id, &UNTIL_LIVE, "rounds can only be deleted").await?;
+		self.repo.delete_round(game_show_id, round_number).await?;
+		return self.repo.collect_rounds(game_show_id).await;
+	}
+
+	fn validate_round(round: &Round) -> Result<(), ApiError>
+	{
+		if round.round_number < 1
+		{
+			return Err(ApiError::Validation("Round numbers start at 1".to_string()));
+		}
+
+		if let (Some(air_date), Some(lock_time)) = (round.air_date, round.lock_time) && lock_time > air_date
+		{
+			return Err(ApiError::Validation(format!("Round [{}] has to lock before its episode airs", round.round_number)));
+		}
+
+		return Ok(());
+	}
+
+	pub async fn collect_tribes(&self, game_show_id: i32) -> Result<Vec<Tribe>, ApiError>
+	{
+		return self.repo.collect_tribes(game_show_id).await;
+	}
+
+	pub async fn create_tribe(&self, game_show_id: i32, tribe: &Tribe) -> Result<Vec<Tribe>, ApiError>
+	{
+		tribe.validate()?;
+		self.require_season_status_in(game_show_id, &UNTIL_FINISHED, "tribes can only be changed").await?;
+		self.repo.create_tribe(game_show_id, tribe).await?;
+		return self.repo.collect_tribes(game_show_id).await;
+	}
+
+	pub async fn update_tribe(&self, game_show_id: i32, tribe_id: i32, tribe: &Tribe) -> Result<Vec<Tribe>, ApiError>
+	{
+		tribe.validate()?;
+		self.require_season_status_in(game_show_id, &UNTIL_FINISHED, "tribes can only be changed").await?;
+		self.repo.update_tribe(game_show_id, tribe_id, tribe).await?;
+		return self.repo.collect_tribes(game_show_id).await;
+	}
+
+	pub async fn delete_tribe(&self, game_show_id: i32, tribe_id: i32) -> Result<Vec<Tribe>, ApiError>
+	{
+		self.require_season_status_in(game_show_id, &UNTIL_LIVE, "tribes can only be deleted").await?;
+		self.repo.delete_tribe(game_show_id, tribe_id).await?;
+		return self.repo.collect_tribes(game_show_id).await;
+	}
+
+	pub async fn collect_tribe_assignments(&self, game_show_id: i32) -> Result<Vec<TribeAssignment>, ApiError>
+	{
+		return self.repo.collect_tribe_assignments(game_show_id).await;
+	}
+
+	pub async fn assign_contestant_to_tribe(&self, game_show_id: i32, assignment: &TribeAssignment) -> Result<Vec<TribeAssignment>, ApiError>
+	{
+		Self::validate_assignment_round(assignment.from_round)?;
+		self.require_season_status_in(game_show_id, &UNTIL_FINISHED, "tribe assignments can only be changed").await?;
+		self.repo.select_tribe(game_show_id, assignment.tribe_id).await?;
+		self.repo.assign_contestant_to_tribe(game_show_id, assignment).await?;
+		return self.repo.collect_tribe_assignments(game_show_id).await;
+	}
+
+	pub async fn delete_tribe_assignment(&self, game_show_id: i32, contestant_id: i32, from_round: i32) -> Result<Vec<TribeAssignment>, ApiError>
+	{
+		self.require_season_status_in(game_show_id, &UNTIL_FINISHED, "tribe assignments can only be changed").await?;
+		self.repo.delete_tribe_assignment(game_show_id, contestant_id, from_round).await?;
+		return self.repo.collect_tribe_assignments(game_show_id).await;
+	}
+
+	// Moves everyone still in the game onto `tribe_id` from `from_round`. Contestants voted out
+	// before that round keep the tribe they left on.
+	pub async fn merge_tribes(&self, game_show_id: i32, tribe_id: i32, from_round: i32) -> Result<Vec<TribeAssignment>, ApiError>
+	{
+		Self::validate_assignment_round(from_round)?;
+		self.require_season_status_in(game_show_id, &UNTIL_FINISHED, "tribe assignments can only be changed").await?;
+		self.repo.select_tribe(game_show_id, tribe_id).await?;
+
+		return self.storage.transaction(async
+		{
+			for contestant in self.repo.fetch_contestants_on_show(game_show_id).await?
+			{
+				if contestant.round_number != scoring_engine::NOT_ELIMINATED && contestant.round_number < from_round
+				{
+					continue;
+				}
+
+				let contestant_id: i32 = required(contestant.id, "contestant_id")?;
+				self.repo.assign_contestant_to_tribe(game_show_id, &TribeAssignment { contestant_id, tribe_id, from_round }).await?;
+			}
+
+			return self.repo.collect_tribe_assignments(game_show_id).await;
+		}).await;
+	}
+
+	fn validate_assignment_round(from_round: i32) -> Result<(), ApiError>
+	{
+		if from_round < 1
+		{
+			return Err(ApiError::Validation("Tribe assignments start at round 1".to_string()));
+		}
+
+		return Ok(());
+	}
+
+	pub async fn create_league(&self, league: &League) -> Result<(), ApiError>
+	{
+		println!("G.S.M. create_league[{}], [{}]", league.name, league.id_showseason.unwrap_or(-1));
+		self.league_repository.create_league(league).await?;
+		return Ok(());
+	}
+
+	pub async fn collect_leagues(&self, id_showseason : i32) -> Result<Vec<League>, ApiError>
+	{
+		return self.league_repository.collect_leagues(id_showseason).await;
+	}
+
+	pub async fn collect_leagues_for_user(&self, user_id: i32) -> Result<Vec<League>, ApiError>
+	{
+		return self.league_repository.collect_leagues_for_user(user_id).await;
+	}
+
+	// Clears out everything hanging off the league first so nothing is left behind
+	// even on databases whose foreign keys don't cascade.
+	pub async fn delete_league(&self, id: i32) -> Result<(), ApiError>
+	{
+		return self.storage.transaction(async
+		{
+			self.league_repository.delete_league_picks(id).await?;
+			self.league_repository.delete_league_members(id).await?;
+			self.league_repository.delete_scoring_rules(id).await?;
+			return self.league_repository.delete_league(id).await;
+		}).await;
+	}
+
+	pub async fn add_user_to_league(&self, user_id: i32, league_id: i32) -> Result<(), ApiError>
+	{
+		return self.league_repository.add_user_to_league(user_id, league_id).await;
+	}
+
+	pub async fn remove_user_from_league(&self, user_id: i32, league_id: i32) -> Result<(), ApiError>
+	{
+		return self.league_repository.remove_user_from_league(user_id, league_id).await;
+	}
+
+	pub async fn fetch_contestants_on_show(&self, game_show_id: i32) -> Result<Vec<Contestant>, ApiError>
+	{
+		return self.repo.fetch_contestants_on_show(game_show_id).await;
+	}
+
+	pub async fn set_league_pick(&self, league_id: i32, user_id: i32, round_number: i32, contestant_id: i32, rank_pick: i32) -> Result<(), ApiError>
+	{
+		let league: League = self.league_repository.select_league(league_id).await?;
+		let game_show_id: i32 = league.id_showseason.ok_or(ApiError::Validation(format!("League [{}] is not attached to a show season", league_id)))?;
+		self.require_season_status(game_show_id, SeasonStatus::Live, "picks can only be made").await?;
+
+		if !self.league_repository.collect_league_member_ids(league_id).await?.contains(&user_id)
+		{
+			return Err(ApiError::Forbidden(format!("User [{}] is not in league [{}]", user_id, league_id)));
+		}
+
+		let cast: Vec<Contestant> = self.repo.fetch_contestants_on_show(game_show_id).await?;
+		if !cast.iter().any(|contestant: &Contestant| contestant.id == Some(contestant_id))
+		{
+			return Err(ApiError::Validation(format!("Contestant [{}] is not on league [{}]'s season", contestant_id, league_id)));
+		}
+
+		return self.league_repository.set_league_pick(league_id, user_id, round_number, contestant_id, rank_pick).await;
+	}
+
+	pub async fn calculate_league_scores(&self, league_id: i32, through_round: Option<i32>) -> Result<Vec<UserScore>, ApiError>
+	{
+		let league: League = self.league_repository.select_league(league_id).await?;
+		let game_show_id: i32 = league.id_showseason.ok_or(ApiError::Validation(format!("League [{}] is not attached to a show season", league_id)))?;
+
+		let contestants: Vec<Contestant> = self.repo.fetch_contestants_on_show(game_show_id).await?;
+		let picks: Vec<LeaguePick> = self.league_repository.collect_league_picks(league_id).await?;
+		let member_ids: Vec<i32> = self.league_repository.collect_league_member_ids(league_id).await?;
+		let rules: ScoringRules = self.fetch_scoring_rules(league_id).await?;
+		let round_stats: Vec<ContestantRoundStats> = self.repo.collect_round_stats(game_show_id).await?;
+		let exits: Vec<GameExit> = scoring_engine::game_exits(&self.repo.collect_contestant_events(game_show_id).await?);
+		let rounds: Vec<Round> = self.repo.collect_rounds(game_show_id).await?;
+
+		let mut last_round: i32 = scoring_engine::latest_played_round(&rounds, &exits, &round_stats);
+		if let Some(round_number) = through_round
+		{
+			last_round = last_round.min(round_number);
+		}
+
+		let season: SeasonRecord = SeasonRecord { contestants: &contestants, exits: &exits, round_stats: &round_stats };
+		return Ok(scoring_engine::score_league(&rules, &member_ids, &picks, &season, last_round));
+	}
+
+	pub async fn fetch_scoring_rules(&self, league_id: i32) -> Result<ScoringRules, ApiError>
+	{
+		let saved_rules: Option<ScoringRules> = self.league_repository.select_scoring_rules(league_id).await?;
+		return Ok(saved_rules.unwrap_or(ScoringRules::default_for_league(league_id)));
+	}
+
+	pub async fn create_scoring_rules(&self, league_id: i32, rules: &ScoringRules) -> Result<ScoringRules, ApiError>
+	{
+		self.require_league_season_status_in(league_id, &UNTIL_FINISHED, "scoring rules can only be changed").await?;
+		self.league_repository.create_scoring_rules(league_id, rules).await?;
+		return self.fetch_scoring_rules(league_id).await;
+	}
+
+	pub async fn update_scoring_rules(&self, league_id: i32, rules: &ScoringRules) -> Result<ScoringRules, ApiError>
+	{
+		self.require_league_season_status_in(league_id, &UNTIL_FINISHED, "scoring rules can only be changed").await?;
+		self.league_repository.update_scoring_rules(league_id, rules).await?;
+		return self.fetch_scoring_rules(league_id).await;
+	}
+
+	pub async fn delete_scoring_rules(&self, league_id: i32) -> Result<(), ApiError>
+	{
+		self.require_league_season_status_in(league_id, &UNTIL_FINISHED, "scoring rules can only be changed").await?;
+		return self.league_repository.delete_scoring_rules(league_id).await;
+	}
+
+	pub async fn calculate_league_standings(&self, league_id: i32) -> Result<Vec<Standing>, ApiError>
+	{
+		let scores: Vec<UserScore> = self.calculate_league_scores(league_id, None).await?;
+		let members: Vec<User> = self.league_repository.collect_league_members(league_id).await?;
+
+		return Ok(scoring_engine::build_standings(&scores, &members));
+	}
+
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+	use crate::in_memory_accessor::{ InMemoryGameShowRepository, InMemoryLeagueRepository, InMemoryStore, InMemoryUserRepository };
+	use crate::memberships_accessor::UserRepository;
+	use crate::scoring_engine::{ NOT_ELIMINATED, RoundScore };
+
+	struct Fixture
+	{
+		manager: GameShowManager,
+		users: InMemoryUserRepository,
+	}
+
+	fn fixture() -> Fixture
+	{
+		let store: InMemoryStore = InMemoryStore::new();
+		let manager: GameShowManager = GameShowManager::create(
+			Arc::new(InMemoryGameShowRepository::new(store.clone())),
+			Arc::new(InMemoryLeagueRepository::new(store.clone())),
+			Arc::new(StorageConnector::in_memory(store.clone()))
+		);
+
+		return Fixture { manager, users: InMemoryUserRepository::new(store) };
+	}
+
+	fn round(round_number: i32) -> Round
+	{
+		return Round { id: None, game_show_id: None, round_number, title: format!("Episode {}", round_number), air_date: None, lock_time: None, status: RoundStatus::Upcoming };
+	}
+
+	fn season(name: &str) -> GameShow
+	{
+		return GameShow { id: None, name: name.to_string(), status: SeasonStatus::Draft, start_date: None, end_date: None };
+	}
+
+	fn contestant(name: &str) -> Contestant
+	{
+		return Contestant { id: None, name: name.to_string(), disambiguator: String::new(), id_showseason: None, nickname: None, round_number: -1, was_medically_evacuated: false, status: ContestantStatus::Active, tribe: None, tribe_color: None, profile: ContestantProfile::default(), photo: None };
+	}
+
+	// One live show with Rob and Parvati on it, and a first round. Returns the contestant ids.
+	async fn seed_show(manager: &GameShowManager) -> (i32, i32)
+	{
+		manager.add_gameshow_and_refresh(&season("Heroes vs Villains")).await.unwrap();
+		manager.create_round(1, &round(1)).await.unwrap();
+
+		let rob: i32 = manager.create_contestant(&contestant("Rob")).await.unwrap().id.unwrap();
+		let parvati: i32 = manager.create_contestant(&contestant("Parvati")).await.unwrap().id.unwrap();
+
+		for contestant_id in [rob, parvati]
+		{
+			manager.enter_contestant_onto_show(&EnrollRequest { contestant_id: Some(contestant_id), game_show_id: Some(1), nickname: None, tribe: None }).await.unwrap();
+		}
+
+		for status in [SeasonStatus::Enrollment, SeasonStatus::Live]
+		{
+			manager.update_gameshow(1, &GameShow { status, ..season("Heroes vs Villains") }).await.unwrap();
+		}
+
+		return (rob, parvati);
+	}
+
+	fn player(name: &str) -> User
+	{
+		return User { id: None, name: name.to_string(), email: format!("{}@example.com", name.to_lowercase()), account_type: "Player".to_string(), password: None };
+	}
+
+	// Adds the players, who get ids 1, 2, ... in order, and signs them all up to league 1,
+	// "Office pool", on show 1.
+	async fn seed_league(manager: &GameShowManager, users: &InMemoryUserRepository, players: &[&str])
+	{
+		for name in players
+		{
+			users.add_user(&player(name), None).await.unwrap();
+		}
+
+		manager.create_league(&League { id: None, name: "Office pool".to_string(), id_showseason: Some(1) }).await.unwrap();
+		for user_id in 1..=players.len() as i32
+		{
+			manager.add_user_to_league(user_id, 1).await.unwrap();
+		}
+	}
+
+	#[tokio::test]
+	async fn rounds_are_validated_and_kept_in_order()
+	{
+		let Fixture { manager, .. } = fixture();
+		manager.add_gameshow_and_refresh(&season("Survivor")).await.unwrap();
+
+		manager.create_round(1, &round(2)).await.unwrap();
+		let rounds: Vec<Round> = manager.create_round(1, &round(1)).await.unwrap();
+		assert_eq!(rounds.iter().map(|round: &Round| round.round_number).collect::<Vec<i32>>(), vec![1, 2]);
+
+		assert!(matches!(manager.create_round(1, &round(0)).await, Err(ApiError::Validation(_))));
+		assert!(matches!(manager.create_round(1, &round(1)).await, Err(ApiError::Conflict(_))));
+
+		let mut late_lock: Round = round(3);
+		late_lock.air_date = Some(Utc::now());
+		late_lock.lock_time = Some(Utc::now() + chrono::Duration::hours(1));
+		assert!(matches!(manager.create_round(1, &late_lock).await, Err(ApiError::Validation(_))));
+
+		assert!(matches!(manager.delete_round(1, 7).await, Err(ApiError::NotFound(_))));
+	}
+
+	#[tokio::test]
+	async fn eliminating_on_a_missing_round_is_not_found()
+	{
+		let Fixture { manager, .. } = fixture();
+		let (rob, _) = seed_show(&manager).await;
+
+		let request: EliminationRequest = EliminationRequest { contestant_id: Some(rob), game_show_id: Some(1), round_number: Some(5) };
+		assert!(matches!(manager.eliminiate_contestant_from_show(&request).await, Err(ApiError::NotFound(_))));
+
+		let missing_field: EliminationRequest = EliminationRequest { contestant_id: Some(rob), game_show_id: Some(1), round_number: None };
+		assert!(matches!(manager.eliminiate_contestant_from_show(&missing_field).await, Err(ApiError::Validation(_))));
+	}
+
+	#[tokio::test]
+	async fn medevac_eliminates_and_flags_the_contestant()
+	{
+		let Fixture { manager, .. } = fixture();
+		let (rob, parvati) = seed_show(&manager).await;
+
+		manager.medically_evacuate_contestant_from_show(&MedevacRequest { contestant_id: Some(rob), game_show_id: Some(1), round_number: Some(1) }).await.unwrap();
+
+		let cast: Vec<Contestant> = manager.fetch_contestants_on_show(1).await.unwrap();
+		let evacuated: &Contestant = cast.iter().find(|contestant: &&Contestant| contestant.id == Some(rob)).unwrap();
+		let remaining: &Contestant = cast.iter().find(|contestant: &&Contestant| contestant.id == Some(parvati)).unwrap();
+
+		assert_eq!(evacuated.round_number, 1);
+		assert!(evacuated.was_medically_evacuated);
+		assert_eq!(remaining.round_number, -1);
+		assert!(!remaining.was_medically_evacuated);
+	}
+
+	#[tokio::test]
+	async fn status_follows_the_event_log()
+	{
+		let Fixture { manager, .. } = fixture();
+		let (rob, parvati) = seed_show(&manager).await;
+		for round_number in [2, 3]
+		{
+			manager.create_round(1, &round(round_number)).await.unwrap();
+		}
+
+		let event = |contestant_id: i32, round_number: i32, event_type: ContestantEventType| ContestantEvent { id: None, contestant_id, round_number, event_type, recorded_at: None, reverses_event_id: None };
+		let status_of = |cast: &[Contestant], contestant_id: i32| -> (ContestantStatus, i32)
+		{
+			let contestant: &Contestant = cast.iter().find(|contestant: &&Contestant| contestant.id == Some(contestant_id)).unwrap();
+			return (contestant.status, contestant.round_number);
+		};
+
+		manager.eliminiate_contestant_from_show(&EliminationRequest { contestant_id: Some(rob), game_show_id: Some(1), round_number: Some(1) }).await.unwrap();
+		manager.record_contestant_event(1, &event(rob, 2, ContestantEventType::Returned)).await.unwrap();
+		manager.record_contestant_event(1, &event(parvati, 2, ContestantEventType::Quit)).await.unwrap();
+		manager.record_contestant_event(1, &event(parvati, 3, ContestantEventType::JoinedJury)).await.unwrap();
+
+		let cast: Vec<Contestant> = manager.fetch_contestants_on_show(1).await.unwrap();
+		assert_eq!(status_of(&cast, rob), (ContestantStatus::Active, NOT_ELIMINATED));
+		assert_eq!(status_of(&cast, parvati), (ContestantStatus::Jury, 2));
+		assert_eq!(manager.collect_contestant_events(1).await.unwrap().len(), 4);
+
+		assert!(matches!(manager.record_contestant_event(1, &event(parvati, 3, ContestantEventType::WonImmunity)).await, Err(ApiError::Conflict(_))));
+		assert!(matches!(manager.record_contestant_event(1, &event(rob, 3, ContestantEventType::Returned)).await, Err(ApiError::Conflict(_))));
+		assert!(matches!(manager.record_contestant_event(1, &event(rob, 4, ContestantEventType::FoundIdol)).await, Err(ApiError::NotFound(_))));
+	}
+
+	#[tokio::test]
+	async fn importing_a_cast_reports_every_row()
+	{
+		let Fixture { manager, .. } = fixture();
+		let (rob, _) = seed_show(&manager).await;
+		manager.add_gameshow_and_refresh(&season("Winners at War")).await.unwrap();
+
+		let cast: Vec<CastMember> = CastMember::parse_csv("name,nickname,tribe,bio\nRob,Boston Rob,Sele,\nSandra, ,Dakal,Queen of the game\n,,,\nSandra,,,\n").unwrap();
+		let results: Vec<CastImportResult> = manager.import_cast(2, &cast).await.unwrap();
+
+		let outcomes: Vec<(usize, CastImportOutcome)> = results.iter().map(|result: &CastImportResult| (result.row, result.outcome)).collect();
+		assert_eq!(outcomes, vec![(1, CastImportOutcome::Enrolled), (2, CastImportOutcome::Created), (3, CastImportOutcome::Failed), (4, CastImportOutcome::AlreadyEnrolled)]);
+		assert_eq!(results[0].contestant_id, Some(rob));
+
+		let cast: Vec<Contestant> = manager.fetch_contestants_on_show(2).await.unwrap();
+		let sandra: &Contestant = cast.iter().find(|contestant: &&Contestant| contestant.name == "Sandra").unwrap();
+		assert_eq!(cast.len(), 2);
+		assert_eq!((sandra.nickname.as_deref(), sandra.tribe.as_deref(), sandra.profile.bio.as_deref()), (Some(""), Some("Dakal"), Some("Queen of the game")));
+
+		// A namesake comes in with a disambiguator, after which "Rob" alone no longer says who is meant.
+		let namesake: CastMember = CastMember { name: Some("Rob".to_string()), disambiguator: Some("Cesternino".to_string()), ..CastMember::default() };
+		assert_eq!(manager.import_cast(2, &[namesake]).await.unwrap()[0].outcome, CastImportOutcome::Created);
+		let ambiguous: Vec<CastImportResult> = manager.import_cast(2, &[CastMember { name: Some("Rob".to_string()), ..CastMember::default() }]).await.unwrap();
+		assert_eq!((ambiguous[0].outcome, ambiguous[0].contestant_id), (CastImportOutcome::Failed, None));
+		assert!(matches!(manager.import_cast(1, &[CastMember { name: Some("Tony".to_string()), ..CastMember::default() }]).await, Err(ApiError::Conflict(_))));
+
+		assert!(matches!(manager.import_cast(9, &[CastMember { name: Some("Tony".to_string()), ..CastMember::default() }]).await, Err(ApiError::NotFound(_))));
+		assert!(matches!(manager.import_cast(2, &[]).await, Err(ApiError::Validation(_))));
+		assert!(matches!(CastMember::parse_csv("nickname,tribe\nBoston Rob,Sele\n"), Err(ApiError::Validation(_))));
+	}
+
+	#[tokio::test]
+	async fn the_merge_skips_contestants_already_voted_out()
+	{
+		let Fixture { manager, .. } = fixture();
+		let (rob, parvati) = seed_show(&manager).await;
+		manager.create_round(1, &round(2)).await.unwrap();
+		manager.eliminiate_contestant_from_show(&EliminationRequest { contestant_id: Some(rob), game_show_id: Some(1), round_number: Some(1) }).await.unwrap();
+
+		let merged: Tribe = Tribe { id: None, game_show_id: None, name: "Yin Yang".to_string(), color: "#FFD700".to_string() };
+		manager.create_tribe(1, &merged).await.unwrap();
+		let assignments: Vec<TribeAssignment> = manager.merge_tribes(1, 1, 2).await.unwrap();
+
+		assert_eq!(assignments.iter().map(|assignment: &TribeAssignment| assignment.contestant_id).collect::<Vec<i32>>(), vec![parvati]);
+		assert!(matches!(manager.merge_tribes(1, 1, 0).await, Err(ApiError::Validation(_))));
+		assert!(matches!(manager.merge_tribes(1, 4, 2).await, Err(ApiError::NotFound(_))));
+		assert!(matches!(manager.create_tribe(1, &Tribe { color: "#FFD70".to_string(), ..merged }).await, Err(ApiError::Validation(_))));
+	}
+
+	#[tokio::test]
+	async fn failed_transactions_leave_no_changes_behind()
+	{
+		let Fixture { manager, .. } = fixture();
+
+		let result: Result<(), ApiError> = manager.storage.transaction(async
+		{
+			manager.repo.add_gameshow(&season("Survivor")).await?;
+			return Err(ApiError::Conflict("abandoned".to_string()));
+		}).await;
+
+		assert!(result.is_err());
+		assert!(manager.collect_gameshows().await.unwrap().is_empty());
+	}
+
+	#[tokio::test]
+	async fn rolling_back_keeps_changes_made_alongside_the_transaction()
+	{
+		let Fixture { manager, .. } = fixture();
+
+		// The second add is started while the transaction is still running, so it has to wait
+		// for the rollback instead of being swept away by it.
+		let australia: GameShow = season("Australia");
+		let (result, added) = tokio::join!(
+			manager.storage.transaction(async
+			{
+				manager.repo.add_gameshow(&season("Survivor")).await?;
+				tokio::task::yield_now().await;
+				return Err::<(), ApiError>(ApiError::Conflict("abandoned".to_string()));
+			}),
+			manager.repo.add_gameshow(&australia)
+		);
+
+		assert!(result.is_err());
+		assert_eq!(added.unwrap(), 1);
+		let game_shows: Vec<GameShow> = manager.collect_gameshows().await.unwrap();
+		assert_eq!(game_shows.iter().map(|game_show: &GameShow| game_show.name.as_str()).collect::<Vec<&str>>(), vec!["Australia"]);
+	}
+
+	#[tokio::test]
+	async fn deleting_a_league_removes_members_picks_and_rules()
+	{
+		let Fixture { manager, users } = fixture();
+		let (rob, _) = seed_show(&manager).await;
+		seed_league(&manager, &users, &["Jeff"]).await;
+
+		manager.set_league_pick(1, 1, 1, rob, 1).await.unwrap();
+		manager.create_scoring_rules(1, &ScoringRules::default_for_league(1)).await.unwrap();
+
+		manager.delete_league(1).await.unwrap();
+
+		assert!(manager.collect_leagues(1).await.unwrap().is_empty());
+		assert!(manager.collect_leagues_for_user(1).await.unwrap().is_empty());
+		assert!(manager.league_repository.collect_league_picks(1).await.unwrap().is_empty());
+		assert!(manager.league_repository.select_scoring_rules(1).await.unwrap().is_none());
+		assert!(matches!(manager.delete_league(1).await, Err(ApiError::NotFound(_))));
+	}
+
+	#[tokio::test]
+	async fn picks_close_with_the_round()
+	{
+		let Fixture { manager, users } = fixture();
+		let (rob, _) = seed_show(&manager).await;
+		seed_league(&manager, &users, &[]).await;
+		users.add_user(&player("Jeff"), None).await.unwrap();
+		assert!(matches!(manager.set_league_pick(1, 1, 1, rob, 1).await, Err(ApiError::Forbidden(_))));
+		manager.add_user_to_league(1, 1).await.unwrap();
+
+		assert!(matches!(manager.set_league_pick(1, 1, 4, rob, 1).await, Err(ApiError::Validation(_))));
+
+		let mut locked: Round = round(1);
+		locked.status = RoundStatus::Locked;
+		manager.update_round(1, 1, &locked).await.unwrap();
+		assert!(matches!(manager.set_league_pick(1, 1, 1, rob, 1).await, Err(ApiError::Conflict(_))));
+
+		let mut past_lock: Round = round(1);
+		past_lock.lock_time = Some(Utc::now() - chrono::Duration::minutes(1));
+		manager.update_round(1, 1, &past_lock).await.unwrap();
+		assert!(matches!(manager.set_league_pick(1, 1, 1, rob, 1).await, Err(ApiError::Conflict(_))));
+	}
+
+	#[tokio::test]
+	async fn standings_rank_players_by_points()
+	{
+		let Fixture { manager, users } = fixture();
+		let (rob, parvati) = seed_show(&manager).await;
+		seed_league(&manager, &users, &["Jeff", "Probst"]).await;
+
+		// Jeff calls Rob's boot, Probst expects Parvati to go.
+		manager.set_league_pick(1, 1, 1, parvati, 1).await.unwrap();
+		manager.set_league_pick(1, 1, 1, rob, 2).await.unwrap();
+		manager.set_league_pick(1, 2, 1, rob, 1).await.unwrap();
+		manager.set_league_pick(1, 2, 1, parvati, 2).await.unwrap();
+
+		manager.eliminiate_contestant_from_show(&EliminationRequest { contestant_id: Some(rob), game_show_id: Some(1), round_number: Some(1) }).await.unwrap();
+
+		let standings: Vec<Standing> = manager.calculate_league_standings(1).await.unwrap();
+		assert_eq!(standings.len(), 2);
+		assert_eq!((standings[0].user_name.as_str(), standings[0].rank, standings[0].total_points), ("Jeff", 1, 4));
+		assert_eq!((standings[1].user_name.as_str(), standings[1].rank, standings[1].total_points), ("Probst", 2, 1));
+	}
+
+	#[tokio::test]
+	async fn a_returnees_first_boot_still_scores()
+	{
+		let Fixture { manager, users } = fixture();
+		let (rob, parvati) = seed_show(&manager).await;
+		for round_number in [2, 3]
+		{
+			manager.create_round(1, &round(round_number)).await.unwrap();
+		}
+
+		seed_league(&manager, &users, &["Jeff"]).await;
+		manager.set_league_pick(1, 1, 1, parvati, 1).await.unwrap();
+		manager.set_league_pick(1, 1, 1, rob, 2).await.unwrap();
+		manager.set_league_pick(1, 1, 2, parvati, 1).await.unwrap();
+		manager.set_league_pick(1, 1, 2, rob, 2).await.unwrap();
+
+		// Rob is voted out first and comes back from the edge for round 3.
+		let event = |contestant_id: i32, round_number: i32, event_type: ContestantEventType| ContestantEvent { id: None, contestant_id, round_number, event_type, recorded_at: None, reverses_event_id: None };
+		manager.record_contestant_event(1, &event(rob, 1, ContestantEventType::VotedOut)).await.unwrap();
+		manager.record_contestant_event(1, &event(parvati, 2, ContestantEventType::VotedOut)).await.unwrap();
+		manager.record_contestant_event(1, &event(rob, 3, ContestantEventType::Returned)).await.unwrap();
+
+		// Round 1 is Parvati's survival and the boot call on Rob, round 2 nothing at all:
+		// Rob was still out and Parvati went home.
+		let scores: Vec<UserScore> = manager.calculate_league_scores(1, None).await.unwrap();
+		assert_eq!(scores[0].rounds.iter().map(|round: &RoundScore| round.points).collect::<Vec<i32>>(), vec![4, 0]);
+	}
+
+	#[tokio::test]
+	async fn reinstating_undoes_the_exit_and_rescores_the_leagues()
+	{
+		let Fixture { manager, users } = fixture();
+		let (rob, parvati) = seed_show(&manager).await;
+		seed_league(&manager, &users, &["Jeff"]).await;
+		manager.set_league_pick(1, 1, 1, parvati, 1).await.unwrap();
+		manager.set_league_pick(1, 1, 1, rob, 2).await.unwrap();
+		let points_before: i32 = manager.calculate_league_standings(1).await.unwrap()[0].total_points;
+
+		manager.eliminiate_contestant_from_show(&EliminationRequest { contestant_id: Some(rob), game_show_id: Some(1), round_number: Some(1) }).await.unwrap();
+		manager.record_contestant_event(1, &ContestantEvent { id: None, contestant_id: rob, round_number: 1, event_type: ContestantEventType::JoinedJury, recorded_at: None, reverses_event_id: None }).await.unwrap();
+		assert_ne!(manager.calculate_league_standings(1).await.unwrap()[0].total_points, points_before);
+
+		let request: ReinstateRequest = ReinstateRequest { contestant_id: Some(rob), game_show_id: Some(1), round_number: Some(1) };
+		let reinstatement: Reinstatement = manager.reinstate_contestant(&request).await.unwrap();
+		assert_eq!((reinstatement.contestant.status, reinstatement.contestant.round_number), (ContestantStatus::Active, NOT_ELIMINATED));
+		assert_eq!(reinstatement.rescored_leagues.len(), 1);
+		assert_eq!(reinstatement.rescored_leagues[0].standings[0].total_points, points_before);
+
+		// The log keeps the mistake and records its correction.
+		let events: Vec<ContestantEvent> = manager.collect_contestant_events(1).await.unwrap();
+		let reversed_ids: Vec<Option<i32>> = events.iter()
+			.filter(|event: &&ContestantEvent| event.event_type == ContestantEventType::Reversed)
+			.map(|event: &ContestantEvent| event.reverses_event_id)
+			.collect();
+		assert_eq!(events.len(), 4);
+		assert_eq!(reversed_ids, vec![events[0].id, events[1].id]);
+
+		assert!(matches!(manager.reinstate_contestant(&request).await, Err(ApiError::NotFound(_))));
+	}
+
+	#[tokio::test]
+	async fn round_stats_earn_the_points_their_league_gives_them()
+	{
+		let Fixture { manager, users } = fixture();
+		let (rob, parvati) = seed_show(&manager).await;
+		seed_league(&manager, &users, &["Jeff"]).await;
+		manager.set_league_pick(1, 1, 1, parvati, 1).await.unwrap();
+
+		manager.record_round_stats(1, &ContestantRoundStats { contestant_id: parvati, round_number: 1, won_individual_immunity: true, idols_found: 1, votes_received: 2, ..ContestantRoundStats::default() }).await.unwrap();
+		manager.eliminiate_contestant_from_show(&EliminationRequest { contestant_id: Some(rob), game_show_id: Some(1), round_number: Some(1) }).await.unwrap();
+
+		// Stats are worth nothing under the default rules.
+		let survival_only: i32 = manager.calculate_league_standings(1).await.unwrap()[0].total_points;
+		manager.create_scoring_rules(1, &ScoringRules { immunity_win_points: 5, idol_found_points: 2, vote_received_points: -1, ..ScoringRules::default_for_league(1) }).await.unwrap();
+		assert_eq!(manager.calculate_league_standings(1).await.unwrap()[0].total_points, survival_only + 5 + 2 - 2);
+
+		// Recording the round again replaces the earlier stats.
+		let round_stats: Vec<ContestantRoundStats> = manager.record_round_stats(1, &ContestantRoundStats { contestant_id: parvati, round_number: 1, ..ContestantRoundStats::default() }).await.unwrap();
+		assert_eq!(round_stats.len(), 1);
+		assert_eq!(manager.calculate_league_standings(1).await.unwrap()[0].total_points, survival_only);
+
+		assert!(matches!(manager.record_round_stats(1, &ContestantRoundStats { contestant_id: parvati, round_number: 1, votes_received: -1, ..ContestantRoundStats::default() }).await, Err(ApiError::Validation(_))));
+		assert!(matches!(manager.record_round_stats(1, &ContestantRoundStats { contestant_id: parvati, round_number: 9, ..ContestantRoundStats::default() }).await, Err(ApiError::NotFound(_))));
+		assert!(manager.delete_round_stats(1, parvati, 1).await.unwrap().is_empty());
+		assert!(matches!(manager.delete_round_stats(1, parvati, 1).await, Err(ApiError::NotFound(_))));
+	}
+
+	#[tokio::test]
+	async fn rounds_without_a_boot_still_score()
+	{
+		let Fixture { manager, users } = fixture();
+		let (_, parvati) = seed_show(&manager).await;
+		manager.create_round(1, &round(2)).await.unwrap();
+		seed_league(&manager, &users, &["Jeff"]).await;
+		manager.set_league_pick(1, 1, 1, parvati, 1).await.unwrap();
+		manager.create_scoring_rules(1, &ScoringRules { immunity_win_points: 5, ..ScoringRules::default_for_league(1) }).await.unwrap();
+		assert!(manager.calculate_league_scores(1, None).await.unwrap()[0].rounds.is_empty());
+
+		// Nobody goes home in round 1, Parvati wins immunity.
+		manager.record_round_stats(1, &ContestantRoundStats { contestant_id: parvati, round_number: 1, won_individual_immunity: true, ..ContestantRoundStats::default() }).await.unwrap();
+		let with_immunity: Vec<RoundScore> = manager.calculate_league_scores(1, None).await.unwrap().remove(0).rounds;
+		manager.update_scoring_rules(1, &ScoringRules::default_for_league(1)).await.unwrap();
+		let without_immunity: Vec<RoundScore> = manager.calculate_league_scores(1, None).await.unwrap().remove(0).rounds;
+		assert_eq!(with_immunity.len(), 1);
+		assert_eq!(with_immunity[0].points, without_immunity[0].points + 5);
+
+		// Marking a round scored counts it as played even with nothing recorded on it.
+		manager.update_round(1, 2, &Round { status: RoundStatus::Scored, ..round(2) }).await.unwrap();
+		assert_eq!(manager.calculate_league_scores(1, None).await.unwrap()[0].rounds.len(), 2);
+	}
+
+	#[tokio::test]
+	async fn search_prefilter_keeps_typos_anywhere_in_the_name()
+	{
+		let Fixture { manager, .. } = fixture();
+		seed_show(&manager).await;
+		manager.create_contestant(&contestant("Sandra")).await.unwrap();
+
+		let names = |page: ContestantPage| page.contestants.into_iter().map(|contestant: Contestant| contestant.name).collect::<Vec<String>>();
+
+		assert_eq!(names(manager.search_contestants("bandra", None, None, None).await.unwrap()), vec!["Sandra"]);
+		assert_eq!(names(manager.search_contestants("parvait", Some(1), None, None).await.unwrap()), vec!["Parvati"]);
+		assert_eq!(names(manager.search_contestants("ob", None, None, None).await.unwrap()), vec!["Rob"]);
+		assert!(names(manager.search_contestants("sandra", Some(1), None, None).await.unwrap()).is_empty());
+		assert_eq!(manager.search_contestants("", None, None, None).await.unwrap().total, 3);
+	}
+}
@@ -1,251 +1,769 @@
 
+use crate::utilities::errors::ApiError;
 use crate::utilities::storage::StorageConnector;
+use crate::scoring_engine::NOT_ELIMINATED;
 
-use crate::gameshow_manager::GameShow;
-use crate::gameshow_manager::Contestant;
+use crate::gameshow_manager::{ GameShow, SeasonStatus };
+use crate::gameshow_manager::{ Contestant, ContestantProfile };
+use crate::gameshow_manager::{ Round, RoundStatus };
+use crate::gameshow_manager::{ Tribe, TribeAssignment };
+use crate::gameshow_manager::{ ContestantEvent, ContestantEventType, ContestantStatus };
+use crate::gameshow_manager::ContestantRoundStats;
 
 use std::sync::Arc;
 
-pub struct GameShowRepository
+#[rocket::async_trait]
+pub trait GameShowRepository: Send + Sync
+{
+	async fn collect_game_shows(&self) -> Result<Vec<GameShow>, ApiError>;
+
+	async fn select_game_show(&self, id: i32) -> Result<GameShow, ApiError>;
+
+	// Every season the contestant was cast on, oldest first.
+	async fn collect_game_shows_for_contestant(&self, contestant_id: i32) -> Result<Vec<GameShow>, ApiError>;
+
+	// Returns the id of the new season.
+	async fn add_gameshow(&self, game_show: &GameShow) -> Result<i32, ApiError>;
+
+	// Saves the name, status and dates as given, the caller checks the status change is allowed.
+	async fn update_game_show(&self, id: i32, game_show: &GameShow) -> Result<(), ApiError>;
+
+	async fn delete_game_show(&self, id: i32) -> Result<(), ApiError>;
+
+	// Returns the row as it was inserted, new id included. Fails with Conflict if the name and
+	// disambiguator are already taken.
+	async fn create_contestant(&self, contestant: &Contestant) -> Result<Contestant, ApiError>;
+
+	async fn select_contestant(&self, id: i32) -> Result<Contestant, ApiError>;
+
+	// Everyone with exactly that name, whatever their disambiguator, by id.
+	async fn select_contestants_by_name(&self, name: &str) -> Result<Vec<Contestant>, ApiError>;
+
+	async fn delete_contestant(&self, id: i32) -> Result<(), ApiError>;
+
+	// Replaces the whole profile and returns the contestant as updated.
+	async fn update_contestant_profile(&self, id: i32, profile: &ContestantProfile) -> Result<Contestant, ApiError>;
+
+	// The photo's file name, or None once it has been removed.
+	async fn set_contestant_photo(&self, id: i32, photo: Option<&str>) -> Result<(), ApiError>;
+
+	async fn collect_all_contestants(&self) -> Result<Vec<Contestant>, ApiError>;
+
+	// Candidates for the contestant search: everyone, or the season's cast as fetch_contestants_on_show
+	// gives it, whose lowercased display name contains at least one of the fragments. No fragments,
+	// no filtering. Ranking is left to the caller.
+	async fn search_contestants(&self, game_show_id: Option<i32>, fragments: &[String]) -> Result<Vec<Contestant>, ApiError>;
+
+	async fn enter_contestant_onto_show(&self, contestant_id: i32, game_show_id: i32, nickname: String) -> Result<(), ApiError>;
+
+	// Appends to the season's event log, stamped now unless the event already has a time (restores).
+	// Returns the new event's id. Fails with NotFound if the contestant isn't on the show.
+	async fn record_contestant_event(&self, game_show_id: i32, event: &ContestantEvent) -> Result<i32, ApiError>;
+
+	// Ordered by round, then by when they were recorded.
+	async fn collect_contestant_events(&self, game_show_id: i32) -> Result<Vec<ContestantEvent>, ApiError>;
+
+	// Ordered by round, then contestant.
+	async fn collect_round_stats(&self, game_show_id: i32) -> Result<Vec<ContestantRoundStats>, ApiError>;
+
+	// Replaces whatever was recorded for the contestant in that round.
+	// Fails with NotFound if the contestant isn't on the show.
+	async fn record_round_stats(&self, game_show_id: i32, stats: &ContestantRoundStats) -> Result<(), ApiError>;
+
+	async fn delete_round_stats(&self, game_show_id: i32, contestant_id: i32, round_number: i32) -> Result<(), ApiError>;
+
+	// Each contestant's status, elimination round and medevac flag come from their events.
+	async fn fetch_contestants_on_show(&self, game_show_id: i32) -> Result<Vec<Contestant>, ApiError>;
+
+	async fn collect_rounds(&self, game_show_id: i32) -> Result<Vec<Round>, ApiError>;
+
+	async fn select_round(&self, game_show_id: i32, round_number: i32) -> Result<Round, ApiError>;
+
+	async fn create_round(&self, game_show_id: i32, round: &Round) -> Result<(), ApiError>;
+
+	async fn update_round(&self, game_show_id: i32, round_number: i32, round: &Round) -> Result<(), ApiError>;
+
+	async fn delete_round(&self, game_show_id: i32, round_number: i32) -> Result<(), ApiError>;
+
+	async fn collect_tribes(&self, game_show_id: i32) -> Result<Vec<Tribe>, ApiError>;
+
+	async fn select_tribe(&self, game_show_id: i32, tribe_id: i32) -> Result<Tribe, ApiError>;
+
+	// Returns the id of the new tribe.
+	async fn create_tribe(&self, game_show_id: i32, tribe: &Tribe) -> Result<i32, ApiError>;
+
+	async fn update_tribe(&self, game_show_id: i32, tribe_id: i32, tribe: &Tribe) -> Result<(), ApiError>;
+
+	// Takes the tribe's assignments with it.
+	async fn delete_tribe(&self, game_show_id: i32, tribe_id: i32) -> Result<(), ApiError>;
+
+	// Ordered by round, then contestant.
+	async fn collect_tribe_assignments(&self, game_show_id: i32) -> Result<Vec<TribeAssignment>, ApiError>;
+
+	// Replaces any assignment the contestant already has from the same round.
+	async fn assign_contestant_to_tribe(&self, game_show_id: i32, assignment: &TribeAssignment) -> Result<(), ApiError>;
+
+	async fn delete_tribe_assignment(&self, game_show_id: i32, contestant_id: i32, from_round: i32) -> Result<(), ApiError>;
+}
+
+pub fn tribe_not_found(game_show_id: i32, tribe_id: i32) -> ApiError
+{
+	return ApiError::NotFound(format!("Tribe [{}] does not exist for showseason [{}]", tribe_id, game_show_id));
+}
+
+pub fn contestant_not_found(contestant_id: i32) -> ApiError
+{
+	return ApiError::NotFound(format!("Contestant [{}] not found", contestant_id));
+}
+
+pub fn round_stats_not_found(game_show_id: i32, contestant_id: i32, round_number: i32) -> ApiError
+{
+	return ApiError::NotFound(format!("No stats for contestant [{}] in round [{}] of showseason [{}]", contestant_id, round_number, game_show_id));
+}
+
+pub fn not_eliminated(game_show_id: i32, contestant_id: i32, round_number: i32) -> ApiError
+{
+	return ApiError::NotFound(format!("Contestant [{}] did not leave showseason [{}] in round [{}]", contestant_id, game_show_id, round_number));
+}
+
+pub fn assignment_not_found(game_show_id: i32, contestant_id: i32, from_round: i32) -> ApiError
+{
+	return ApiError::NotFound(format!("Contestant [{}] has no tribe assignment from round [{}] on showseason [{}]", contestant_id, from_round, game_show_id));
+}
+
+// The game_shows columns read_game_show expects, in order, for a table aliased `gs`.
+const GAME_SHOW_COLUMNS : &str = "gs.game_show_id, gs.name, gs.status, gs.start_date, gs.end_date";
+
+// The contestants columns read_contestant expects, in order, for a table aliased `c`.
+const CONTESTANT_COLUMNS : &str = "c.contestant_id, c.name, c.bio, c.disambiguator, c.age, c.hometown, c.occupation, c.prior_seasons, c.photo";
+
+// What Contestant::display_name gives, lowercased, for matching search fragments against.
+const CONTESTANT_DISPLAY_NAME : &str = "lower(CASE WHEN c.disambiguator = '' THEN c.name ELSE c.name || ' (' || c.disambiguator || ')' END)";
+
+pub struct PostgresGameShowRepository
 {
 	connector: Arc<StorageConnector>,
 }
 
-impl GameShowRepository
+impl PostgresGameShowRepository
 {
-	pub async fn new(storage_connection: Arc<StorageConnector>) -> Self
+	pub fn new(storage_connection: Arc<StorageConnector>) -> Self
 	{	
-		let repository: GameShowRepository = GameShowRepository
+		let repository: PostgresGameShowRepository = PostgresGameShowRepository
 		{
 			connector: Arc::clone(&storage_connection),
 		};
 
-		repository.initialize_storage().await;
-
 		return repository;
 	}
 
-	async fn initialize_storage(&self) -> ()
+	// The season's cast, or only those whose display name contains one of the fragments.
+	async fn fetch_cast(&self, game_show_id: i32, fragments: &[String]) -> Result<Vec<Contestant>, ApiError>
 	{
-		self.initialize_gameshows().await;
+		let mut contestants: Vec<Contestant> = self.connector.client().await?
+			.query(
+				&format!(
+				"SELECT {}, gsc.nickname, current_tribe.name, current_tribe.color
+				FROM contestants c
+				JOIN game_show_contestants gsc ON c.contestant_id = gsc.contestant_id
+				LEFT JOIN LATERAL (
+					SELECT t.name, t.color FROM tribe_assignments ta
+					JOIN tribes t ON t.tribe_id = ta.tribe_id
+					WHERE ta.game_show_id = gsc.game_show_id AND ta.contestant_id = gsc.contestant_id
+					ORDER BY ta.from_round DESC LIMIT 1
+				) current_tribe ON TRUE
+				WHERE gsc.game_show_id = $1 AND (cardinality($2::TEXT[]) = 0 OR {} LIKE ANY($2))", CONTESTANT_COLUMNS, CONTESTANT_DISPLAY_NAME),
+				&[&game_show_id, &Self::like_patterns(fragments)]
+			).await?
+			.iter()
+			.map(|row: &tokio_postgres::Row| Contestant {
+				id_showseason: Some(game_show_id),
+				nickname: row.get(9),
+				tribe: row.get(10),
+				tribe_color: row.get(11),
+				..Self::read_contestant(row)
+			})
+			.collect::<Vec<Contestant>>();
 
-		self.initialize_contestants().await;
+		let events: Vec<ContestantEvent> = self.collect_contestant_events(game_show_id).await?;
+		for contestant in contestants.iter_mut()
+		{
+			contestant.apply_events(&events);
+		}
 
-		self.initialize_gameshow_contestants().await;
+		return Ok(contestants);
 	}
 
-	async fn initialize_gameshows(&self)
+	// `%fragment%` with LIKE's own wildcards escaped.
+	fn like_patterns(fragments: &[String]) -> Vec<String>
 	{
-		self.connector.storage
-			.execute(
-				"CREATE TABLE IF NOT EXISTS game_shows (
-				game_show_id SERIAL PRIMARY KEY,
-				name TEXT DEFAULT 'Jeffs Jamboree'
-				)",
-			&[]
-			).await
-		.expect("Failed to create table");
+		return fragments.iter()
+			.map(|fragment: &String| format!("%{}%", fragment.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")))
+			.collect();
 	}
 
-	async fn initialize_contestants(&self)
+	fn not_on_show(contestant_id: i32, game_show_id: i32) -> ApiError
 	{
-		self.connector.storage
-			.execute(
-				"CREATE TABLE IF NOT EXISTS contestants (
-				contestant_id SERIAL PRIMARY KEY,
-				name TEXT NOT NULL
-				)",
-				&[]
-			).await
-		.expect("Failed to create table");
+		return ApiError::NotFound(format!("Contestant [{}] is not on showseason [{}]", contestant_id, game_show_id));
 	}
 
-	async fn initialize_gameshow_contestants(&self)
+	// Reads the first columns of a row selected with CONTESTANT_COLUMNS.
+	fn read_contestant(row: &tokio_postgres::Row) -> Contestant
 	{
-		self.connector.storage
-			.execute(
-				"CREATE TABLE IF NOT EXISTS game_show_contestants (
-				contestant_id INTEGER,
-				game_show_id INTEGER,
-				nickname TEXT,
-				PRIMARY KEY (contestant_id, game_show_id),
-				FOREIGN KEY (contestant_id) REFERENCES contestants(contestant_id)
-					ON DELETE CASCADE,
-				FOREIGN KEY (game_show_id) REFERENCES game_shows(game_show_id)
-					ON DELETE CASCADE,
-				was_medically_evacuated BOOLEAN DEFAULT FALSE,
-				eliminated_on_round INTEGER DEFAULT -1
-				)",
-				&[]
-			).await
-		.expect("Failed to create table");
-	}
-
-	pub async fn collect_game_shows(&self) -> Result<Vec<GameShow>, String>
-	{
-		let users: Vec<GameShow> = self.connector.storage
-			.query("SELECT game_show_id, name FROM game_shows", &[]).await
-			.map_err(|e: tokio_postgres::Error| e.to_string()) ?
+		let profile: ContestantProfile = ContestantProfile
+		{
+			bio: row.get(2),
+			age: row.get(4),
+			hometown: row.get(5),
+			occupation: row.get(6),
+			prior_seasons: row.get(7)
+		};
+
+		return Contestant { id: Some(row.get(0)), name: row.get(1), disambiguator: row.get(3), id_showseason: None, nickname: None, round_number: NOT_ELIMINATED, was_medically_evacuated: false, status: ContestantStatus::Active, tribe: None, tribe_color: None, profile, photo: row.get(8) };
+	}
+
+	fn read_round(row: &tokio_postgres::Row) -> Result<Round, ApiError>
+	{
+		let status: String = row.get(6);
+
+		return Ok(Round {
+			id: Some(row.get(0)),
+			game_show_id: Some(row.get(1)),
+			round_number: row.get(2),
+			title: row.get(3),
+			air_date: row.get(4),
+			lock_time: row.get(5),
+			status: RoundStatus::parse(&status)?
+		});
+	}
+
+	fn read_contestant_event(row: &tokio_postgres::Row) -> Result<ContestantEvent, ApiError>
+	{
+		let event_type: String = row.get(3);
+
+		return Ok(ContestantEvent {
+			id: Some(row.get(0)),
+			contestant_id: row.get(1),
+			round_number: row.get(2),
+			event_type: ContestantEventType::parse(&event_type)?,
+			recorded_at: Some(row.get(4)),
+			reverses_event_id: row.get(5)
+		});
+	}
+
+	fn read_round_stats(row: &tokio_postgres::Row) -> ContestantRoundStats
+	{
+		return ContestantRoundStats {
+			contestant_id: row.get(0),
+			round_number: row.get(1),
+			won_individual_immunity: row.get(2),
+			won_reward: row.get(3),
+			idols_found: row.get(4),
+			idols_played: row.get(5),
+			votes_received: row.get(6),
+			cast_correct_vote: row.get(7)
+		};
+	}
+
+	fn read_game_show(row: &tokio_postgres::Row) -> Result<GameShow, ApiError>
+	{
+		let status: String = row.get(2);
+
+		return Ok(GameShow {
+			id: Some(row.get(0)),
+			name: row.get(1),
+			status: SeasonStatus::parse(&status)?,
+			start_date: row.get(3),
+			end_date: row.get(4)
+		});
+	}
+
+	fn read_tribe(row: &tokio_postgres::Row) -> Tribe
+	{
+		return Tribe { id: Some(row.get(0)), game_show_id: Some(row.get(1)), name: row.get(2), color: row.get(3) };
+	}
+}
+
+#[rocket::async_trait]
+impl GameShowRepository for PostgresGameShowRepository
+{
+	async fn collect_game_shows(&self) -> Result<Vec<GameShow>, ApiError>
+	{
+		let users: Vec<GameShow> = self.connector.client().await?
+			.query(&format!("SELECT {} FROM game_shows gs ORDER BY gs.game_show_id", GAME_SHOW_COLUMNS), &[]).await?
 			.iter()
-			.map(|row: &tokio_postgres::Row| GameShow { id: Some(row.get(0)), name: row.get(1) })
-			.collect::<Vec<GameShow>>();
+			.map(Self::read_game_show)
+			.collect::<Result<Vec<GameShow>, ApiError>>()?;
 
 		return Ok(users);
 	}
 
-	pub async fn add_gameshow(&self, game_show: &GameShow) -> Result<(), String>
+	async fn select_game_show(&self, id: i32) -> Result<GameShow, ApiError>
+	{
+		let row_option: Option<tokio_postgres::Row> = self.connector.client().await?
+			.query_opt(&format!("SELECT {} FROM game_shows gs WHERE gs.game_show_id = $1", GAME_SHOW_COLUMNS), &[&id]).await?;
+
+		match row_option
+		{
+			Some(row) => return Self::read_game_show(&row),
+			None => return Err(ApiError::NotFound(format!("Showseason [{}] not found", id))),
+		}
+	}
+
+	async fn collect_game_shows_for_contestant(&self, contestant_id: i32) -> Result<Vec<GameShow>, ApiError>
+	{
+		let game_shows: Vec<GameShow> = self.connector.client().await?
+			.query(
+				&format!(
+					"SELECT {} FROM game_shows gs
+					JOIN game_show_contestants gsc ON gsc.game_show_id = gs.game_show_id
+					WHERE gsc.contestant_id = $1
+					ORDER BY gs.game_show_id", GAME_SHOW_COLUMNS),
+				&[&contestant_id]
+			).await?
+			.iter()
+			.map(Self::read_game_show)
+			.collect::<Result<Vec<GameShow>, ApiError>>()?;
+
+		return Ok(game_shows);
+	}
+
+	async fn add_gameshow(&self, game_show: &GameShow) -> Result<i32, ApiError>
 	{
-		self.connector.storage
+		let row: tokio_postgres::Row = self.connector.client().await?
+			.query_one(
+				"INSERT INTO game_shows (name, status, start_date, end_date) VALUES ($1, $2, $3, $4) RETURNING game_show_id",
+				&[&game_show.name, &game_show.status.as_str(), &game_show.start_date, &game_show.end_date]
+			).await?;
+
+		return Ok(row.get(0));
+	}
+
+	async fn update_game_show(&self, id: i32, game_show: &GameShow) -> Result<(), ApiError>
+	{
+		let updated_rows: u64 = self.connector.client().await?
 			.execute(
-				"INSERT INTO game_shows (name) VALUES ($1)",
-				&[&game_show.name]
-			).await
-			.map_err(|e: tokio_postgres::Error| e.to_string())?;
+				"UPDATE game_shows SET name = $2, status = $3, start_date = $4, end_date = $5 WHERE game_show_id = $1",
+				&[&id, &game_show.name, &game_show.status.as_str(), &game_show.start_date, &game_show.end_date]
+			).await?;
+
+		if updated_rows == 0
+		{
+			return Err(ApiError::NotFound(format!("Showseason [{}] not found", id)));
+		}
 
 		return Ok(());
 	}
 
-	pub async fn delete_game_show(&self, id: i32) -> Result<(), String>
+	async fn delete_game_show(&self, id: i32) -> Result<(), ApiError>
 	{
-		self.connector.storage
+		let deleted_rows: u64 = self.connector.client().await?
 			.execute("DELETE FROM game_shows WHERE game_show_id = $1", &[&id]).await
-			.map_err(|e| {
-			print!("delete_game_show error[{}]", e.to_string());
-			e.to_string()
+			.map_err(|e: tokio_postgres::Error| {
+			print!("delete_game_show error[{}]", e);
+			ApiError::from(e)
 	})?;
-		
+
+		if deleted_rows == 0
+		{
+			return Err(ApiError::NotFound(format!("Showseason [{}] not found", id)));
+		}
 
 		return Ok(());
 	}
 
-	pub async fn create_contestant(&self, contestant: &Contestant) -> Result<(), String>
+	async fn create_contestant(&self, contestant: &Contestant) -> Result<Contestant, ApiError>
 	{
-		self.connector.storage
-			.execute(
-				"INSERT INTO contestants (name) VALUES ($1)",
-				&[&contestant.name]
-			).await
-			.map_err(|e: tokio_postgres::Error| e.to_string())?;
-
-		return Ok(());
+		let row: tokio_postgres::Row = self.connector.client().await?
+			.query_one(
+				&format!(
+					"INSERT INTO contestants AS c (name, disambiguator, bio, age, hometown, occupation, prior_seasons) VALUES ($1, $2, $3, $4, $5, $6, $7)
+					RETURNING {}", CONTESTANT_COLUMNS),
+				&[&contestant.name, &contestant.disambiguator, &contestant.profile.bio, &contestant.profile.age,
+					&contestant.profile.hometown, &contestant.profile.occupation, &contestant.profile.prior_seasons]
+			).await?;
+
+		return Ok(Self::read_contestant(&row));
 	}
 
-	pub async fn select_contestant_by_name(&self, name: String) -> Result<Contestant, String>
+	async fn select_contestant(&self, id: i32) -> Result<Contestant, ApiError>
 	{
-		let row_option: Option<tokio_postgres::Row> = self.connector.storage
+		let row_option: Option<tokio_postgres::Row> = self.connector.client().await?
 			.query_opt(
-				"SELECT contestant_id, name FROM contestants WHERE name = $1",
+				&format!("SELECT {} FROM contestants c WHERE c.contestant_id = $1", CONTESTANT_COLUMNS),
+				&[&id]
+			).await?;
+
+		return row_option.map(|row: tokio_postgres::Row| Self::read_contestant(&row)).ok_or(contestant_not_found(id));
+	}
+
+	async fn select_contestants_by_name(&self, name: &str) -> Result<Vec<Contestant>, ApiError>
+	{
+		let contestants: Vec<Contestant> = self.connector.client().await?
+			.query(
+				&format!("SELECT {} FROM contestants c WHERE c.name = $1 ORDER BY c.contestant_id", CONTESTANT_COLUMNS),
 				&[&name]
-			).await
-			.map_err(|e: tokio_postgres::Error| e.to_string())?;
+			).await?
+			.iter()
+			.map(Self::read_contestant)
+			.collect::<Vec<Contestant>>();
 
-		match row_option
-		{
-			Some(row) =>
-			{
-				let contestant: Contestant = Contestant
-				{
-					id: Some(row.get(0)),
-					name: row.get(1),
-					id_showseason: None,
-					nickname: None,
-					round_number: -1,
-					was_medically_evacuated: false
-				};
+		return Ok(contestants);
+	}
 
-				return Ok(contestant);
-			}
+	async fn delete_contestant(&self, id: i32) -> Result<(), ApiError>
+	{
+		let deleted_rows: u64 = self.connector.client().await?
+			.execute(
+				"DELETE FROM contestants WHERE contestant_id = $1",
+				&[&id]
+			).await?;
 
-			None =>
-			{
-				return Err("Contestant not found".to_string());
-			}
+		if deleted_rows == 0
+		{
+			return Err(contestant_not_found(id));
 		}
+
+		return Ok(());
+	}
+
+	async fn update_contestant_profile(&self, id: i32, profile: &ContestantProfile) -> Result<Contestant, ApiError>
+	{
+		let row_option: Option<tokio_postgres::Row> = self.connector.client().await?
+			.query_opt(
+				&format!(
+					"UPDATE contestants c SET bio = $2, age = $3, hometown = $4, occupation = $5, prior_seasons = $6
+					WHERE c.contestant_id = $1
+					RETURNING {}", CONTESTANT_COLUMNS),
+				&[&id, &profile.bio, &profile.age, &profile.hometown, &profile.occupation, &profile.prior_seasons]
+			).await?;
+
+		return row_option.map(|row: tokio_postgres::Row| Self::read_contestant(&row)).ok_or(contestant_not_found(id));
 	}
 
-	pub async fn delete_contestant(&self, name: &str) -> Result<(), String>
+	async fn set_contestant_photo(&self, id: i32, photo: Option<&str>) -> Result<(), ApiError>
 	{
-		self.connector.storage
+		let updated_rows: u64 = self.connector.client().await?
 			.execute(
-				"DELETE FROM contestants WHERE name = $1",
-				&[&name]
-			).await
-			.map_err(|e: tokio_postgres::Error| e.to_string())?;
+				"UPDATE contestants SET photo = $2 WHERE contestant_id = $1",
+				&[&id, &photo]
+			).await?;
+
+		if updated_rows == 0
+		{
+			return Err(contestant_not_found(id));
+		}
 
 		return Ok(());
 	}
 
-	pub async fn collect_all_contestants(&self) -> Result<Vec<Contestant>, String>
+	async fn collect_all_contestants(&self) -> Result<Vec<Contestant>, ApiError>
 	{
-		let users: Vec<Contestant> = self.connector.storage
-			.query("SELECT contestant_id, name FROM contestants", &[]).await
-			.map_err(|e: tokio_postgres::Error| e.to_string()) ?
+		let users: Vec<Contestant> = self.connector.client().await?
+			.query(&format!("SELECT {} FROM contestants c", CONTESTANT_COLUMNS), &[]).await?
 			.iter()
-			.map(|row: &tokio_postgres::Row| Contestant { id: Some(row.get(0)), name: row.get(1), id_showseason: None, nickname: None, round_number: -1, was_medically_evacuated: false })
+			.map(Self::read_contestant)
 			.collect::<Vec<Contestant>>();
 
 		return Ok(users);
 	}
 
-	pub async fn enter_contestant_onto_show(&self, contestant_id: i32, game_show_id: i32, nickname: String) -> Result<(), String>
+	async fn search_contestants(&self, game_show_id: Option<i32>, fragments: &[String]) -> Result<Vec<Contestant>, ApiError>
+	{
+		if let Some(game_show_id) = game_show_id
+		{
+			return self.fetch_cast(game_show_id, fragments).await;
+		}
+
+		let contestants: Vec<Contestant> = self.connector.client().await?
+			.query(
+				&format!("SELECT {} FROM contestants c WHERE cardinality($1::TEXT[]) = 0 OR {} LIKE ANY($1)", CONTESTANT_COLUMNS, CONTESTANT_DISPLAY_NAME),
+				&[&Self::like_patterns(fragments)]
+			).await?
+			.iter()
+			.map(Self::read_contestant)
+			.collect::<Vec<Contestant>>();
+
+		return Ok(contestants);
+	}
+
+	async fn enter_contestant_onto_show(&self, contestant_id: i32, game_show_id: i32, nickname: String) -> Result<(), ApiError>
 	{
-		self.connector.storage
+		self.connector.client().await?
 			.execute(
 				"INSERT INTO game_show_contestants (contestant_id, game_show_id, nickname) VALUES ($1, $2, $3)",
 				&[&contestant_id, &game_show_id, &nickname]
-			).await
-			.map_err(|e: tokio_postgres::Error| e.to_string())?;
+			).await?;
 
 		return Ok(());
 	}
 
-	pub async fn eliminate_contestant_from_show(&self, contestant_id: i32, game_show_id: i32, round_number: i32) -> Result<(), String>
+	async fn record_contestant_event(&self, game_show_id: i32, event: &ContestantEvent) -> Result<i32, ApiError>
 	{
-		self.connector.storage
+		let inserted_row: Option<tokio_postgres::Row> = self.connector.client().await?
+			.query_opt(
+				"INSERT INTO contestant_events (game_show_id, contestant_id, round_number, event_type, recorded_at, reverses_event_id)
+				SELECT game_show_id, contestant_id, $3, $4, COALESCE($5, now()), $6 FROM game_show_contestants WHERE game_show_id = $1 AND contestant_id = $2
+				RETURNING event_id",
+				&[&game_show_id, &event.contestant_id, &event.round_number, &event.event_type.as_str(), &event.recorded_at, &event.reverses_event_id]
+			).await?;
+
+		return inserted_row
+			.map(|row: tokio_postgres::Row| row.get(0))
+			.ok_or(Self::not_on_show(event.contestant_id, game_show_id));
+	}
+
+	async fn collect_contestant_events(&self, game_show_id: i32) -> Result<Vec<ContestantEvent>, ApiError>
+	{
+		let rows: Vec<tokio_postgres::Row> = self.connector.client().await?
+			.query(
+				"SELECT event_id, contestant_id, round_number, event_type, recorded_at, reverses_event_id FROM contestant_events
+				WHERE game_show_id = $1 ORDER BY round_number, event_id",
+				&[&game_show_id]
+			).await?;
+
+		return rows.iter().map(Self::read_contestant_event).collect::<Result<Vec<ContestantEvent>, ApiError>>();
+	}
+
+	async fn collect_round_stats(&self, game_show_id: i32) -> Result<Vec<ContestantRoundStats>, ApiError>
+	{
+		let rows: Vec<tokio_postgres::Row> = self.connector.client().await?
+			.query(
+				"SELECT contestant_id, round_number, won_individual_immunity, won_reward, idols_found, idols_played, votes_received, cast_correct_vote
+				FROM contestant_round_stats WHERE game_show_id = $1 ORDER BY round_number, contestant_id",
+				&[&game_show_id]
+			).await?;
+
+		return Ok(rows.iter().map(Self::read_round_stats).collect());
+	}
+
+	async fn record_round_stats(&self, game_show_id: i32, stats: &ContestantRoundStats) -> Result<(), ApiError>
+	{
+		let written_rows: u64 = self.connector.client().await?
 			.execute(
-				"UPDATE game_show_contestants SET eliminated_on_round = $1 WHERE contestant_id = $2 AND game_show_id = $3",
-				&[&round_number, &contestant_id, &game_show_id]
-			).await
-			.map_err(|e: tokio_postgres::Error| e.to_string())?;
+				"INSERT INTO contestant_round_stats (game_show_id, contestant_id, round_number, won_individual_immunity, won_reward, idols_found, idols_played, votes_received, cast_correct_vote)
+				SELECT game_show_id, contestant_id, $3, $4, $5, $6, $7, $8, $9 FROM game_show_contestants WHERE game_show_id = $1 AND contestant_id = $2
+				ON CONFLICT (game_show_id, contestant_id, round_number) DO UPDATE SET
+					won_individual_immunity = EXCLUDED.won_individual_immunity, won_reward = EXCLUDED.won_reward,
+					idols_found = EXCLUDED.idols_found, idols_played = EXCLUDED.idols_played,
+					votes_received = EXCLUDED.votes_received, cast_correct_vote = EXCLUDED.cast_correct_vote",
+				&[&game_show_id, &stats.contestant_id, &stats.round_number, &stats.won_individual_immunity, &stats.won_reward,
+					&stats.idols_found, &stats.idols_played, &stats.votes_received, &stats.cast_correct_vote]
+			).await?;
+
+		if written_rows == 0
+		{
+			return Err(Self::not_on_show(stats.contestant_id, game_show_id));
+		}
 
 		return Ok(());
 	}
 
-	pub async fn medically_evacuate_contestant_from_show(&self, contestant_id: i32, game_show_id: i32) -> Result<(), String>
+	async fn delete_round_stats(&self, game_show_id: i32, contestant_id: i32, round_number: i32) -> Result<(), ApiError>
 	{
-		self.connector.storage
+		let deleted_rows: u64 = self.connector.client().await?
 			.execute(
-				"UPDATE game_show_contestants SET was_medically_evacuated = TRUE WHERE contestant_id = $1 AND game_show_id = $2",
-				&[&contestant_id, &game_show_id]
-			).await
-			.map_err(|e: tokio_postgres::Error| e.to_string())?;
+				"DELETE FROM contestant_round_stats WHERE game_show_id = $1 AND contestant_id = $2 AND round_number = $3",
+				&[&game_show_id, &contestant_id, &round_number]
+			).await?;
+
+		if deleted_rows == 0
+		{
+			return Err(round_stats_not_found(game_show_id, contestant_id, round_number));
+		}
 
 		return Ok(());
 	}
 
-	pub async fn fetch_contestants_on_show(&self, game_show_id: i32) -> Result<Vec<Contestant>, String>
+	async fn fetch_contestants_on_show(&self, game_show_id: i32) -> Result<Vec<Contestant>, ApiError>
 	{
-		let contestants: Vec<Contestant> = self.connector.storage
+		return self.fetch_cast(game_show_id, &[]).await;
+	}
+
+	async fn collect_rounds(&self, game_show_id: i32) -> Result<Vec<Round>, ApiError>
+	{
+		let rows: Vec<tokio_postgres::Row> = self.connector.client().await?
 			.query(
-				"SELECT c.contestant_id, c.name, gsc.nickname, gsc.eliminated_on_round, gsc.was_medically_evacuated
-				FROM contestants c
-				JOIN game_show_contestants gsc ON c.contestant_id = gsc.contestant_id
-				WHERE gsc.game_show_id = $1",
+				"SELECT round_id, game_show_id, round_number, title, air_date, lock_time, status FROM rounds
+				WHERE game_show_id = $1 ORDER BY round_number",
+				&[&game_show_id]
+			).await?;
+
+		return rows.iter().map(Self::read_round).collect::<Result<Vec<Round>, ApiError>>();
+	}
+
+	async fn select_round(&self, game_show_id: i32, round_number: i32) -> Result<Round, ApiError>
+	{
+		let row_option: Option<tokio_postgres::Row> = self.connector.client().await?
+			.query_opt(
+				"SELECT round_id, game_show_id, round_number, title, air_date, lock_time, status FROM rounds
+				WHERE game_show_id = $1 AND round_number = $2",
+				&[&game_show_id, &round_number]
+			).await?;
+
+		match row_option
+		{
+			Some(row) => return Self::read_round(&row),
+			None => return Err(ApiError::NotFound(format!("Round [{}] does not exist for showseason [{}]", round_number, game_show_id))),
+		}
+	}
+
+	async fn create_round(&self, game_show_id: i32, round: &Round) -> Result<(), ApiError>
+	{
+		self.connector.client().await?
+			.execute(
+				"INSERT INTO rounds (game_show_id, round_number, title, air_date, lock_time, status) VALUES ($1, $2, $3, $4, $5, $6)",
+				&[&game_show_id, &round.round_number, &round.title, &round.air_date, &round.lock_time, &round.status.as_str()]
+			).await?;
+
+		return Ok(());
+	}
+
+	async fn update_round(&self, game_show_id: i32, round_number: i32, round: &Round) -> Result<(), ApiError>
+	{
+		let updated_rows: u64 = self.connector.client().await?
+			.execute(
+				"UPDATE rounds SET round_number = $1, title = $2, air_date = $3, lock_time = $4, status = $5
+				WHERE game_show_id = $6 AND round_number = $7",
+				&[&round.round_number, &round.title, &round.air_date, &round.lock_time, &round.status.as_str(), &game_show_id, &round_number]
+			).await?;
+
+		if updated_rows == 0
+		{
+			return Err(ApiError::NotFound(format!("Round [{}] does not exist for showseason [{}]", round_number, game_show_id)));
+		}
+
+		return Ok(());
+	}
+
+	async fn delete_round(&self, game_show_id: i32, round_number: i32) -> Result<(), ApiError>
+	{
+		let deleted_rows: u64 = self.connector.client().await?
+			.execute(
+				"DELETE FROM rounds WHERE game_show_id = $1 AND round_number = $2",
+				&[&game_show_id, &round_number]
+			).await?;
+
+		if deleted_rows == 0
+		{
+			return Err(ApiError::NotFound(format!("Round [{}] does not exist for showseason [{}]", round_number, game_show_id)));
+		}
+
+		return Ok(());
+	}
+
+	async fn collect_tribes(&self, game_show_id: i32) -> Result<Vec<Tribe>, ApiError>
+	{
+		let tribes: Vec<Tribe> = self.connector.client().await?
+			.query(
+				"SELECT tribe_id, game_show_id, name, color FROM tribes WHERE game_show_id = $1 ORDER BY tribe_id",
 				&[&game_show_id]
-			).await
-			.map_err(|e: tokio_postgres::Error| e.to_string()) ?
+			).await?
 			.iter()
-			.map(|row: &tokio_postgres::Row| Contestant {
-				id: Some(row.get(0)),
-				name: row.get(1),
-				nickname: row.get(2),
-				round_number: row.get(3),
-				was_medically_evacuated: row.get(4),
-				id_showseason: Some(game_show_id)
-			})
-			.collect::<Vec<Contestant>>();
+			.map(Self::read_tribe)
+			.collect::<Vec<Tribe>>();
 
-		return Ok(contestants);
+		return Ok(tribes);
+	}
+
+	async fn select_tribe(&self, game_show_id: i32, tribe_id: i32) -> Result<Tribe, ApiError>
+	{
+		let row_option: Option<tokio_postgres::Row> = self.connector.client().await?
+			.query_opt(
+				"SELECT tribe_id, game_show_id, name, color FROM tribes WHERE game_show_id = $1 AND tribe_id = $2",
+				&[&game_show_id, &tribe_id]
+			).await?;
+
+		match row_option
+		{
+			Some(row) => return Ok(Self::read_tribe(&row)),
+			None => return Err(tribe_not_found(game_show_id, tribe_id)),
+		}
+	}
+
+	async fn create_tribe(&self, game_show_id: i32, tribe: &Tribe) -> Result<i32, ApiError>
+	{
+		let row: tokio_postgres::Row = self.connector.client().await?
+			.query_one(
+				"INSERT INTO tribes (game_show_id, name, color) VALUES ($1, $2, $3) RETURNING tribe_id",
+				&[&game_show_id, &tribe.name, &tribe.color]
+			).await?;
+
+		return Ok(row.get(0));
+	}
+
+	async fn update_tribe(&self, game_show_id: i32, tribe_id: i32, tribe: &Tribe) -> Result<(), ApiError>
+	{
+		let updated_rows: u64 = self.connector.client().await?
+			.execute(
+				"UPDATE tribes SET name = $1, color = $2 WHERE game_show_id = $3 AND tribe_id = $4",
+				&[&tribe.name, &tribe.color, &game_show_id, &tribe_id]
+			).await?;
+
+		if updated_rows == 0
+		{
+			return Err(tribe_not_found(game_show_id, tribe_id));
+		}
+
+		return Ok(());
+	}
+
+	async fn delete_tribe(&self, game_show_id: i32, tribe_id: i32) -> Result<(), ApiError>
+	{
+		let deleted_rows: u64 = self.connector.client().await?
+			.execute(
+				"DELETE FROM tribes WHERE game_show_id = $1 AND tribe_id = $2",
+				&[&game_show_id, &tribe_id]
+			).await?;
+
+		if deleted_rows == 0
+		{
+			return Err(tribe_not_found(game_show_id, tribe_id));
+		}
+
+		return Ok(());
+	}
+
+	async fn collect_tribe_assignments(&self, game_show_id: i32) -> Result<Vec<TribeAssignment>, ApiError>
+	{
+		let assignments: Vec<TribeAssignment> = self.connector.client().await?
+			.query(
+				"SELECT contestant_id, tribe_id, from_round FROM tribe_assignments
+				WHERE game_show_id = $1 ORDER BY from_round, contestant_id",
+				&[&game_show_id]
+			).await?
+			.iter()
+			.map(|row: &tokio_postgres::Row| TribeAssignment { contestant_id: row.get(0), tribe_id: row.get(1), from_round: row.get(2) })
+			.collect::<Vec<TribeAssignment>>();
+
+		return Ok(assignments);
+	}
+
+	async fn assign_contestant_to_tribe(&self, game_show_id: i32, assignment: &TribeAssignment) -> Result<(), ApiError>
+	{
+		self.connector.client().await?
+			.execute(
+				"INSERT INTO tribe_assignments (game_show_id, contestant_id, tribe_id, from_round) VALUES ($1, $2, $3, $4)
+				ON CONFLICT (game_show_id, contestant_id, from_round) DO UPDATE SET tribe_id = EXCLUDED.tribe_id",
+				&[&game_show_id, &assignment.contestant_id, &assignment.tribe_id, &assignment.from_round]
+			).await?;
+
+		return Ok(());
 	}
 
+	async fn delete_tribe_assignment(&self, game_show_id: i32, contestant_id: i32, from_round: i32) -> Result<(), ApiError>
+	{
+		let deleted_rows: u64 = self.connector.client().await?
+			.execute(
+				"DELETE FROM tribe_assignments WHERE game_show_id = $1 AND contestant_id = $2 AND from_round = $3",
+				&[&game_show_id, &contestant_id, &from_round]
+			).await?;
+
+		if deleted_rows == 0
+		{
+			return Err(assignment_not_found(game_show_id, contestant_id, from_round));
+		}
+
+		return Ok(());
+	}
 }
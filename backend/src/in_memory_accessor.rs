@@ -0,0 +1,1123 @@
+use chrono::Utc;
+
+use crate::gameshow_manager::{ Contestant, ContestantEvent, ContestantProfile, ContestantRoundStats, ContestantStatus, GameShow, League, LeaguePick, Round, ScoringRules, Tribe, TribeAssignment };
+use crate::gameshows_accessor::{ self, GameShowRepository };
+use crate::league_accessor::{ self, LeagueRepository };
+use crate::memberships_accessor::UserRepository;
+use crate::scoring_engine::NOT_ELIMINATED;
+use crate::user_manager::User;
+use crate::utilities::errors::ApiError;
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::ops::{ Deref, DerefMut };
+use std::sync::{ Arc, Mutex, MutexGuard };
+
+// Repositories that keep everything in process memory. They follow the same rules as the
+// Postgres schema (unique keys, foreign keys, cascading deletes) so managers behave the same
+// against either, which is what lets the managers and the whole app run in `cargo test`.
+
+#[derive(Clone)]
+struct StoredUser
+{
+	user: User,
+	password_hash: Option<String>,
+}
+
+#[derive(Clone)]
+struct ShowContestant
+{
+	contestant_id: i32,
+	game_show_id: i32,
+	nickname: String,
+}
+
+#[derive(Clone)]
+struct StoredContestantEvent
+{
+	game_show_id: i32,
+	event: ContestantEvent,
+}
+
+#[derive(Clone)]
+struct StoredRoundStats
+{
+	game_show_id: i32,
+	stats: ContestantRoundStats,
+}
+
+#[derive(Clone)]
+struct StoredTribeAssignment
+{
+	game_show_id: i32,
+	assignment: TribeAssignment,
+}
+
+#[derive(Clone, Default)]
+pub struct InMemoryData
+{
+	users: Vec<StoredUser>,
+	game_shows: Vec<GameShow>,
+	contestants: Vec<Contestant>,
+	show_contestants: Vec<ShowContestant>,
+	rounds: Vec<Round>,
+	tribes: Vec<Tribe>,
+	tribe_assignments: Vec<StoredTribeAssignment>,
+	contestant_events: Vec<StoredContestantEvent>,
+	round_stats: Vec<StoredRoundStats>,
+	leagues: Vec<League>,
+	league_members: Vec<(i32, i32)>,
+	league_picks: Vec<LeaguePick>,
+	scoring_rules: Vec<ScoringRules>,
+	sequences: HashMap<&'static str, i32>,
+}
+
+impl InMemoryData
+{
+	// Each table counts its own ids from 1, like a SERIAL column.
+	fn next_id(&mut self, table: &'static str) -> i32
+	{
+		let sequence: &mut i32 = self.sequences.entry(table).or_insert(0);
+		*sequence += 1;
+		return *sequence;
+	}
+
+	fn user_exists(&self, user_id: i32) -> bool
+	{
+		return self.users.iter().any(|stored: &StoredUser| stored.user.id == Some(user_id));
+	}
+
+	fn league_exists(&self, league_id: i32) -> bool
+	{
+		return self.leagues.iter().any(|league: &League| league.id == Some(league_id));
+	}
+
+	fn contestant_exists(&self, contestant_id: i32) -> bool
+	{
+		return self.contestants.iter().any(|contestant: &Contestant| contestant.id == Some(contestant_id));
+	}
+
+	fn game_show_exists(&self, game_show_id: i32) -> bool
+	{
+		return self.game_shows.iter().any(|game_show: &GameShow| game_show.id == Some(game_show_id));
+	}
+
+	fn tribe_exists(&self, game_show_id: i32, tribe_id: i32) -> bool
+	{
+		return self.tribes.iter().any(|tribe: &Tribe| tribe.game_show_id == Some(game_show_id) && tribe.id == Some(tribe_id));
+	}
+
+	fn tribe_name_taken(&self, game_show_id: i32, name: &str, other_than: Option<i32>) -> bool
+	{
+		return self.tribes.iter().any(|tribe: &Tribe| tribe.game_show_id == Some(game_show_id) && tribe.name == name && tribe.id != other_than);
+	}
+
+	// The tribe from the contestant's latest assignment.
+	fn current_tribe(&self, game_show_id: i32, contestant_id: i32) -> Option<&Tribe>
+	{
+		let latest: &StoredTribeAssignment = self.tribe_assignments.iter()
+			.filter(|stored: &&StoredTribeAssignment| stored.game_show_id == game_show_id && stored.assignment.contestant_id == contestant_id)
+			.max_by_key(|stored: &&StoredTribeAssignment| stored.assignment.from_round)?;
+
+		return self.tribes.iter().find(|tribe: &&Tribe| tribe.id == Some(latest.assignment.tribe_id));
+	}
+}
+
+fn missing_reference(table: &str, column: &str, id: i32) -> ApiError
+{
+	return ApiError::Validation(format!("{} references {} [{}], which does not exist", table, column, id));
+}
+
+tokio::task_local!
+{
+	// Set while the task runs a transaction, which already holds the store's writer gate.
+	static IN_TRANSACTION: ();
+}
+
+// The store locked for one repository call. Outside a transaction it also holds the writer
+// gate, so the call waits for any running transaction to finish first.
+struct StoreGuard<'a>
+{
+	data: MutexGuard<'a, InMemoryData>,
+	_gate: Option<tokio::sync::MutexGuard<'a, ()>>,
+}
+
+impl Deref for StoreGuard<'_>
+{
+	type Target = InMemoryData;
+
+	fn deref(&self) -> &InMemoryData
+	{
+		return &self.data;
+	}
+}
+
+impl DerefMut for StoreGuard<'_>
+{
+	fn deref_mut(&mut self) -> &mut InMemoryData
+	{
+		return &mut self.data;
+	}
+}
+
+// One set of tables shared by the three in-memory repositories, the same way the Postgres
+// repositories share a database.
+#[derive(Clone, Default)]
+pub struct InMemoryStore
+{
+	data: Arc<Mutex<InMemoryData>>,
+	// Held by a transaction for as long as it runs. Nothing else touches the data meanwhile,
+	// so rolling back to the snapshot taken at its start can't undo anyone else's changes.
+	gate: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl InMemoryStore
+{
+	pub fn new() -> Self
+	{
+		return InMemoryStore::default();
+	}
+
+	async fn lock(&self) -> StoreGuard<'_>
+	{
+		let gate: Option<tokio::sync::MutexGuard<'_, ()>> = match IN_TRANSACTION.try_with(|_| ())
+		{
+			Ok(()) => None,
+			Err(_) => Some(self.gate.lock().await),
+		};
+
+		return StoreGuard { data: self.data.lock().unwrap_or_else(|poisoned| poisoned.into_inner()), _gate: gate };
+	}
+
+	// Stands in for a database transaction: if `work` fails, every change it made is undone.
+	// Transactions run one at a time and hold off every other repository call while they run.
+	// Nested calls join the outer transaction.
+	pub async fn transaction<T, F>(&self, work: F) -> Result<T, ApiError>
+		where F: Future<Output = Result<T, ApiError>>
+	{
+		if IN_TRANSACTION.try_with(|_| ()).is_ok()
+		{
+			return work.await;
+		}
+
+		let _gate: tokio::sync::MutexGuard<'_, ()> = self.gate.lock().await;
+		let snapshot: InMemoryData = self.data.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone();
+		let work_result: Result<T, ApiError> = IN_TRANSACTION.scope((), work).await;
+
+		if work_result.is_err()
+		{
+			*self.data.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = snapshot;
+		}
+
+		return work_result;
+	}
+}
+
+pub struct InMemoryGameShowRepository
+{
+	store: InMemoryStore,
+}
+
+impl InMemoryGameShowRepository
+{
+	pub fn new(store: InMemoryStore) -> Self
+	{
+		return InMemoryGameShowRepository { store };
+	}
+
+	fn not_on_show(contestant_id: i32, game_show_id: i32) -> ApiError
+	{
+		return ApiError::NotFound(format!("Contestant [{}] is not on showseason [{}]", contestant_id, game_show_id));
+	}
+
+	fn round_not_found(game_show_id: i32, round_number: i32) -> ApiError
+	{
+		return ApiError::NotFound(format!("Round [{}] does not exist for showseason [{}]", round_number, game_show_id));
+	}
+}
+
+#[rocket::async_trait]
+impl GameShowRepository for InMemoryGameShowRepository
+{
+	async fn collect_game_shows(&self) -> Result<Vec<GameShow>, ApiError>
+	{
+		return Ok(self.store.lock().await.game_shows.clone());
+	}
+
+	async fn select_game_show(&self, id: i32) -> Result<GameShow, ApiError>
+	{
+		return self.store.lock().await.game_shows.iter()
+			.find(|game_show: &&GameShow| game_show.id == Some(id))
+			.cloned()
+			.ok_or(ApiError::NotFound(format!("Showseason [{}] not found", id)));
+	}
+
+	async fn collect_game_shows_for_contestant(&self, contestant_id: i32) -> Result<Vec<GameShow>, ApiError>
+	{
+		let data: StoreGuard<'_> = self.store.lock().await;
+		let mut game_shows: Vec<GameShow> = data.game_shows.iter()
+			.filter(|game_show: &&GameShow| data.show_contestants.iter()
+				.any(|entry: &ShowContestant| entry.contestant_id == contestant_id && Some(entry.game_show_id) == game_show.id))
+			.cloned()
+			.collect();
+		game_shows.sort_by_key(|game_show: &GameShow| game_show.id);
+
+		return Ok(game_shows);
+	}
+
+	async fn add_gameshow(&self, game_show: &GameShow) -> Result<i32, ApiError>
+	{
+		let mut data: StoreGuard<'_> = self.store.lock().await;
+		let id: i32 = data.next_id("game_shows");
+		data.game_shows.push(GameShow { id: Some(id), ..game_show.clone() });
+
+		return Ok(id);
+	}
+
+	async fn update_game_show(&self, id: i32, game_show: &GameShow) -> Result<(), ApiError>
+	{
+		let mut data: StoreGuard<'_> = self.store.lock().await;
+		let stored: &mut GameShow = data.game_shows.iter_mut()
+			.find(|stored: &&mut GameShow| stored.id == Some(id))
+			.ok_or(ApiError::NotFound(format!("Showseason [{}] not found", id)))?;
+
+		*stored = GameShow { id: Some(id), ..game_show.clone() };
+		return Ok(());
+	}
+
+	async fn delete_game_show(&self, id: i32) -> Result<(), ApiError>
+	{
+		let mut data: StoreGuard<'_> = self.store.lock().await;
+		if !data.game_show_exists(id)
+		{
+			return Err(ApiError::NotFound(format!("Showseason [{}] not found", id)));
+		}
+
+		data.game_shows.retain(|game_show: &GameShow| game_show.id != Some(id));
+		data.show_contestants.retain(|entry: &ShowContestant| entry.game_show_id != id);
+		data.rounds.retain(|round: &Round| round.game_show_id != Some(id));
+		data.tribes.retain(|tribe: &Tribe| tribe.game_show_id != Some(id));
+		data.tribe_assignments.retain(|stored: &StoredTribeAssignment| stored.game_show_id != id);
+		data.contestant_events.retain(|stored: &StoredContestantEvent| stored.game_show_id != id);
+		data.round_stats.retain(|stored: &StoredRoundStats| stored.game_show_id != id);
+
+		return Ok(());
+	}
+
+	async fn create_contestant(&self, contestant: &Contestant) -> Result<Contestant, ApiError>
+	{
+		let mut data: StoreGuard<'_> = self.store.lock().await;
+		if data.contestants.iter().any(|existing: &Contestant| existing.name == contestant.name && existing.disambiguator == contestant.disambiguator)
+		{
+			return Err(ApiError::Conflict(format!("There is already a contestant called [{}]", contestant.display_name())));
+		}
+
+		let id: i32 = data.next_id("contestants");
+		let created_contestant: Contestant = Contestant
+		{
+			id: Some(id),
+			name: contestant.name.clone(),
+			disambiguator: contestant.disambiguator.clone(),
+			id_showseason: None,
+			nickname: None,
+			round_number: NOT_ELIMINATED,
+			was_medically_evacuated: false,
+			status: ContestantStatus::Active,
+			tribe: None,
+			tribe_color: None,
+			profile: contestant.profile.clone(),
+			photo: None
+		};
+
+		data.contestants.push(created_contestant.clone());
+		return Ok(created_contestant);
+	}
+
+	async fn select_contestant(&self, id: i32) -> Result<Contestant, ApiError>
+	{
+		return self.store.lock().await.contestants.iter()
+			.find(|contestant: &&Contestant| contestant.id == Some(id))
+			.cloned()
+			.ok_or(gameshows_accessor::contestant_not_found(id));
+	}
+
+	async fn select_contestants_by_name(&self, name: &str) -> Result<Vec<Contestant>, ApiError>
+	{
+		return Ok(self.store.lock().await.contestants.iter()
+			.filter(|contestant: &&Contestant| contestant.name == name)
+			.cloned()
+			.collect());
+	}
+
+	async fn delete_contestant(&self, id: i32) -> Result<(), ApiError>
+	{
+		let mut data: StoreGuard<'_> = self.store.lock().await;
+		if !data.contestant_exists(id)
+		{
+			return Err(gameshows_accessor::contestant_not_found(id));
+		}
+
+		data.contestants.retain(|contestant: &Contestant| contestant.id != Some(id));
+		data.show_contestants.retain(|entry: &ShowContestant| entry.contestant_id != id);
+		data.tribe_assignments.retain(|stored: &StoredTribeAssignment| stored.assignment.contestant_id != id);
+		data.contestant_events.retain(|stored: &StoredContestantEvent| stored.event.contestant_id != id);
+		data.round_stats.retain(|stored: &StoredRoundStats| stored.stats.contestant_id != id);
+		data.league_picks.retain(|pick: &LeaguePick| pick.contestant_id != id);
+
+		return Ok(());
+	}
+
+	async fn update_contestant_profile(&self, id: i32, profile: &ContestantProfile) -> Result<Contestant, ApiError>
+	{
+		let mut data: StoreGuard<'_> = self.store.lock().await;
+		let contestant: &mut Contestant = data.contestants.iter_mut()
+			.find(|contestant: &&mut Contestant| contestant.id == Some(id))
+			.ok_or(gameshows_accessor::contestant_not_found(id))?;
+
+		contestant.profile = profile.clone();
+		return Ok(contestant.clone());
+	}
+
+	async fn set_contestant_photo(&self, id: i32, photo: Option<&str>) -> Result<(), ApiError>
+	{
+		let mut data: StoreGuard<'_> = self.store.lock().await;
+		let contestant: &mut Contestant = data.contestants.iter_mut()
+			.find(|contestant: &&mut Contestant| contestant.id == Some(id))
+			.ok_or(gameshows_accessor::contestant_not_found(id))?;
+
+		contestant.photo = photo.map(str::to_string);
+		return Ok(());
+	}
+
+	async fn collect_all_contestants(&self) -> Result<Vec<Contestant>, ApiError>
+	{
+		return Ok(self.store.lock().await.contestants.clone());
+	}
+
+	async fn search_contestants(&self, game_show_id: Option<i32>, fragments: &[String]) -> Result<Vec<Contestant>, ApiError>
+	{
+		let candidates: Vec<Contestant> = match game_show_id
+		{
+			Some(game_show_id) => self.fetch_contestants_on_show(game_show_id).await?,
+			None => self.collect_all_contestants().await?,
+		};
+
+		return Ok(candidates.into_iter()
+			.filter(|contestant: &Contestant|
+			{
+				let display_name: String = contestant.display_name().to_lowercase();
+				return fragments.is_empty() || fragments.iter().any(|fragment: &String| display_name.contains(fragment.as_str()));
+			})
+			.collect());
+	}
+
+	async fn enter_contestant_onto_show(&self, contestant_id: i32, game_show_id: i32, nickname: String) -> Result<(), ApiError>
+	{
+		let mut data: StoreGuard<'_> = self.store.lock().await;
+		if !data.contestant_exists(contestant_id)
+		{
+			return Err(missing_reference("game_show_contestants", "contestant", contestant_id));
+		}
+
+		if !data.game_show_exists(game_show_id)
+		{
+			return Err(missing_reference("game_show_contestants", "game show", game_show_id));
+		}
+
+		if data.show_contestants.iter().any(|entry: &ShowContestant| entry.contestant_id == contestant_id && entry.game_show_id == game_show_id)
+		{
+			return Err(ApiError::Conflict(format!("Contestant [{}] is already on showseason [{}]", contestant_id, game_show_id)));
+		}
+
+		data.show_contestants.push(ShowContestant
+		{
+			contestant_id,
+			game_show_id,
+			nickname
+		});
+
+		return Ok(());
+	}
+
+	async fn record_contestant_event(&self, game_show_id: i32, event: &ContestantEvent) -> Result<i32, ApiError>
+	{
+		let mut data: StoreGuard<'_> = self.store.lock().await;
+		if !data.show_contestants.iter().any(|entry: &ShowContestant| entry.contestant_id == event.contestant_id && entry.game_show_id == game_show_id)
+		{
+			return Err(Self::not_on_show(event.contestant_id, game_show_id));
+		}
+
+		if event.round_number < 1
+		{
+			return Err(ApiError::Validation(format!("Contestant events start at round 1, not [{}]", event.round_number)));
+		}
+
+		if let Some(reversed_id) = event.reverses_event_id && !data.contestant_events.iter().any(|stored: &StoredContestantEvent| stored.event.id == Some(reversed_id))
+		{
+			return Err(missing_reference("contestant_events", "event", reversed_id));
+		}
+
+		let id: i32 = data.next_id("contestant_events");
+		data.contestant_events.push(StoredContestantEvent
+		{
+			game_show_id,
+			event: ContestantEvent { id: Some(id), recorded_at: event.recorded_at.or(Some(Utc::now())), ..event.clone() }
+		});
+
+		return Ok(id);
+	}
+
+	async fn collect_contestant_events(&self, game_show_id: i32) -> Result<Vec<ContestantEvent>, ApiError>
+	{
+		let mut events: Vec<ContestantEvent> = self.store.lock().await.contestant_events.iter()
+			.filter(|stored: &&StoredContestantEvent| stored.game_show_id == game_show_id)
+			.map(|stored: &StoredContestantEvent| stored.event.clone())
+			.collect();
+
+		events.sort_by_key(|event: &ContestantEvent| (event.round_number, event.id));
+
+		return Ok(events);
+	}
+
+	async fn collect_round_stats(&self, game_show_id: i32) -> Result<Vec<ContestantRoundStats>, ApiError>
+	{
+		let mut round_stats: Vec<ContestantRoundStats> = self.store.lock().await.round_stats.iter()
+			.filter(|stored: &&StoredRoundStats| stored.game_show_id == game_show_id)
+			.map(|stored: &StoredRoundStats| stored.stats.clone())
+			.collect();
+
+		round_stats.sort_by_key(|stats: &ContestantRoundStats| (stats.round_number, stats.contestant_id));
+		return Ok(round_stats);
+	}
+
+	async fn record_round_stats(&self, game_show_id: i32, stats: &ContestantRoundStats) -> Result<(), ApiError>
+	{
+		let mut data: StoreGuard<'_> = self.store.lock().await;
+		if !data.show_contestants.iter().any(|entry: &ShowContestant| entry.contestant_id == stats.contestant_id && entry.game_show_id == game_show_id)
+		{
+			return Err(Self::not_on_show(stats.contestant_id, game_show_id));
+		}
+
+		if stats.round_number < 1
+		{
+			return Err(ApiError::Validation(format!("Round stats start at round 1, not [{}]", stats.round_number)));
+		}
+
+		stats.validate()?;
+		data.round_stats.retain(|stored: &StoredRoundStats|
+			!(stored.game_show_id == game_show_id && stored.stats.contestant_id == stats.contestant_id && stored.stats.round_number == stats.round_number));
+		data.round_stats.push(StoredRoundStats { game_show_id, stats: stats.clone() });
+
+		return Ok(());
+	}
+
+	async fn delete_round_stats(&self, game_show_id: i32, contestant_id: i32, round_number: i32) -> Result<(), ApiError>
+	{
+		let mut data: StoreGuard<'_> = self.store.lock().await;
+		let stats_count: usize = data.round_stats.len();
+		data.round_stats.retain(|stored: &StoredRoundStats|
+			!(stored.game_show_id == game_show_id && stored.stats.contestant_id == contestant_id && stored.stats.round_number == round_number));
+
+		if data.round_stats.len() == stats_count
+		{
+			return Err(gameshows_accessor::round_stats_not_found(game_show_id, contestant_id, round_number));
+		}
+
+		return Ok(());
+	}
+
+	async fn fetch_contestants_on_show(&self, game_show_id: i32) -> Result<Vec<Contestant>, ApiError>
+	{
+		let events: Vec<ContestantEvent> = self.collect_contestant_events(game_show_id).await?;
+
+		let data: StoreGuard<'_> = self.store.lock().await;
+		let contestants: Vec<Contestant> = data.show_contestants.iter()
+			.filter(|entry: &&ShowContestant| entry.game_show_id == game_show_id)
+			.filter_map(|entry: &ShowContestant|
+			{
+				data.contestants.iter()
+					.find(|contestant: &&Contestant| contestant.id == Some(entry.contestant_id))
+					.map(|contestant: &Contestant|
+					{
+						let current_tribe: Option<&Tribe> = data.current_tribe(game_show_id, entry.contestant_id);
+						let mut contestant_on_show: Contestant = Contestant
+						{
+							id: contestant.id,
+							name: contestant.name.clone(),
+							disambiguator: contestant.disambiguator.clone(),
+							nickname: Some(entry.nickname.clone()),
+							round_number: NOT_ELIMINATED,
+							was_medically_evacuated: false,
+							status: ContestantStatus::Active,
+							id_showseason: Some(game_show_id),
+							tribe: current_tribe.map(|tribe: &Tribe| tribe.name.clone()),
+							tribe_color: current_tribe.map(|tribe: &Tribe| tribe.color.clone()),
+							profile: contestant.profile.clone(),
+							photo: contestant.photo.clone()
+						};
+
+						contestant_on_show.apply_events(&events);
+						return contestant_on_show;
+					})
+			})
+			.collect();
+
+		return Ok(contestants);
+	}
+
+	async fn collect_rounds(&self, game_show_id: i32) -> Result<Vec<Round>, ApiError>
+	{
+		let mut rounds: Vec<Round> = self.store.lock().await.rounds.iter()
+			.filter(|round: &&Round| round.game_show_id == Some(game_show_id))
+			.cloned()
+			.collect();
+
+		rounds.sort_by_key(|round: &Round| round.round_number);
+
+		return Ok(rounds);
+	}
+
+	async fn select_round(&self, game_show_id: i32, round_number: i32) -> Result<Round, ApiError>
+	{
+		return self.store.lock().await.rounds.iter()
+			.find(|round: &&Round| round.game_show_id == Some(game_show_id) && round.round_number == round_number)
+			.cloned()
+			.ok_or(Self::round_not_found(game_show_id, round_number));
+	}
+
+	async fn create_round(&self, game_show_id: i32, round: &Round) -> Result<(), ApiError>
+	{
+		let mut data: StoreGuard<'_> = self.store.lock().await;
+		if !data.game_show_exists(game_show_id)
+		{
+			return Err(missing_reference("rounds", "game show", game_show_id));
+		}
+
+		if data.rounds.iter().any(|existing: &Round| existing.game_show_id == Some(game_show_id) && existing.round_number == round.round_number)
+		{
+			return Err(ApiError::Conflict(format!("Round [{}] already exists for showseason [{}]", round.round_number, game_show_id)));
+		}
+
+		let id: i32 = data.next_id("rounds");
+		data.rounds.push(Round { id: Some(id), game_show_id: Some(game_show_id), ..round.clone() });
+
+		return Ok(());
+	}
+
+	async fn update_round(&self, game_show_id: i32, round_number: i32, round: &Round) -> Result<(), ApiError>
+	{
+		let mut data: StoreGuard<'_> = self.store.lock().await;
+		if round.round_number != round_number
+			&& data.rounds.iter().any(|existing: &Round| existing.game_show_id == Some(game_show_id) && existing.round_number == round.round_number)
+		{
+			return Err(ApiError::Conflict(format!("Round [{}] already exists for showseason [{}]", round.round_number, game_show_id)));
+		}
+
+		let existing: &mut Round = data.rounds.iter_mut()
+			.find(|existing: &&mut Round| existing.game_show_id == Some(game_show_id) && existing.round_number == round_number)
+			.ok_or(Self::round_not_found(game_show_id, round_number))?;
+
+		existing.round_number = round.round_number;
+		existing.title = round.title.clone();
+		existing.air_date = round.air_date;
+		existing.lock_time = round.lock_time;
+		existing.status = round.status;
+
+		return Ok(());
+	}
+
+	async fn delete_round(&self, game_show_id: i32, round_number: i32) -> Result<(), ApiError>
+	{
+		let mut data: StoreGuard<'_> = self.store.lock().await;
+		let round_count: usize = data.rounds.len();
+		data.rounds.retain(|round: &Round| !(round.game_show_id == Some(game_show_id) && round.round_number == round_number));
+
+		if data.rounds.len() == round_count
+		{
+			return Err(Self::round_not_found(game_show_id, round_number));
+		}
+
+		return Ok(());
+	}
+
+	async fn collect_tribes(&self, game_show_id: i32) -> Result<Vec<Tribe>, ApiError>
+	{
+		let tribes: Vec<Tribe> = self.store.lock().await.tribes.iter()
+			.filter(|tribe: &&Tribe| tribe.game_show_id == Some(game_show_id))
+			.cloned()
+			.collect();
+
+		return Ok(tribes);
+	}
+
+	async fn select_tribe(&self, game_show_id: i32, tribe_id: i32) -> Result<Tribe, ApiError>
+	{
+		return self.store.lock().await.tribes.iter()
+			.find(|tribe: &&Tribe| tribe.game_show_id == Some(game_show_id) && tribe.id == Some(tribe_id))
+			.cloned()
+			.ok_or(gameshows_accessor::tribe_not_found(game_show_id, tribe_id));
+	}
+
+	async fn create_tribe(&self, game_show_id: i32, tribe: &Tribe) -> Result<i32, ApiError>
+	{
+		let mut data: StoreGuard<'_> = self.store.lock().await;
+		if !data.game_show_exists(game_show_id)
+		{
+			return Err(missing_reference("tribes", "game show", game_show_id));
+		}
+
+		if data.tribe_name_taken(game_show_id, &tribe.name, None)
+		{
+			return Err(ApiError::Conflict(format!("Showseason [{}] already has a tribe called [{}]", game_show_id, tribe.name)));
+		}
+
+		let id: i32 = data.next_id("tribes");
+		data.tribes.push(Tribe { id: Some(id), game_show_id: Some(game_show_id), name: tribe.name.clone(), color: tribe.color.clone() });
+
+		return Ok(id);
+	}
+
+	async fn update_tribe(&self, game_show_id: i32, tribe_id: i32, tribe: &Tribe) -> Result<(), ApiError>
+	{
+		let mut data: StoreGuard<'_> = self.store.lock().await;
+		if data.tribe_name_taken(game_show_id, &tribe.name, Some(tribe_id))
+		{
+			return Err(ApiError::Conflict(format!("Showseason [{}] already has a tribe called [{}]", game_show_id, tribe.name)));
+		}
+
+		let existing: &mut Tribe = data.tribes.iter_mut()
+			.find(|existing: &&mut Tribe| existing.game_show_id == Some(game_show_id) && existing.id == Some(tribe_id))
+			.ok_or(gameshows_accessor::tribe_not_found(game_show_id, tribe_id))?;
+
+		existing.name = tribe.name.clone();
+		existing.color = tribe.color.clone();
+
+		return Ok(());
+	}
+
+	async fn delete_tribe(&self, game_show_id: i32, tribe_id: i32) -> Result<(), ApiError>
+	{
+		let mut data: StoreGuard<'_> = self.store.lock().await;
+		if !data.tribe_exists(game_show_id, tribe_id)
+		{
+			return Err(gameshows_accessor::tribe_not_found(game_show_id, tribe_id));
+		}
+
+		data.tribes.retain(|tribe: &Tribe| tribe.id != Some(tribe_id));
+		data.tribe_assignments.retain(|stored: &StoredTribeAssignment| stored.assignment.tribe_id != tribe_id);
+
+		return Ok(());
+	}
+
+	async fn collect_tribe_assignments(&self, game_show_id: i32) -> Result<Vec<TribeAssignment>, ApiError>
+	{
+		let mut assignments: Vec<TribeAssignment> = self.store.lock().await.tribe_assignments.iter()
+			.filter(|stored: &&StoredTribeAssignment| stored.game_show_id == game_show_id)
+			.map(|stored: &StoredTribeAssignment| stored.assignment.clone())
+			.collect();
+
+		assignments.sort_by_key(|assignment: &TribeAssignment| (assignment.from_round, assignment.contestant_id));
+
+		return Ok(assignments);
+	}
+
+	async fn assign_contestant_to_tribe(&self, game_show_id: i32, assignment: &TribeAssignment) -> Result<(), ApiError>
+	{
+		let mut data: StoreGuard<'_> = self.store.lock().await;
+		if assignment.from_round < 1
+		{
+			return Err(ApiError::Validation(format!("Tribe assignments start at round 1, not [{}]", assignment.from_round)));
+		}
+
+		if !data.show_contestants.iter().any(|entry: &ShowContestant| entry.contestant_id == assignment.contestant_id && entry.game_show_id == game_show_id)
+		{
+			return Err(missing_reference("tribe_assignments", "contestant", assignment.contestant_id));
+		}
+
+		if !data.tribe_exists(game_show_id, assignment.tribe_id)
+		{
+			return Err(missing_reference("tribe_assignments", "tribe", assignment.tribe_id));
+		}
+
+		match data.tribe_assignments.iter_mut().find(|stored: &&mut StoredTribeAssignment|
+			stored.game_show_id == game_show_id && stored.assignment.contestant_id == assignment.contestant_id && stored.assignment.from_round == assignment.from_round)
+		{
+			Some(existing) => existing.assignment.tribe_id = assignment.tribe_id,
+			None => data.tribe_assignments.push(StoredTribeAssignment { game_show_id, assignment: assignment.clone() }),
+		}
+
+		return Ok(());
+	}
+
+	async fn delete_tribe_assignment(&self, game_show_id: i32, contestant_id: i32, from_round: i32) -> Result<(), ApiError>
+	{
+		let mut data: StoreGuard<'_> = self.store.lock().await;
+		let assignment_count: usize = data.tribe_assignments.len();
+		data.tribe_assignments.retain(|stored: &StoredTribeAssignment|
+			!(stored.game_show_id == game_show_id && stored.assignment.contestant_id == contestant_id && stored.assignment.from_round == from_round));
+
+		if data.tribe_assignments.len() == assignment_count
+		{
+			return Err(gameshows_accessor::assignment_not_found(game_show_id, contestant_id, from_round));
+		}
+
+		return Ok(());
+	}
+}
+
+pub struct InMemoryLeagueRepository
+{
+	store: InMemoryStore,
+}
+
+impl InMemoryLeagueRepository
+{
+	pub fn new(store: InMemoryStore) -> Self
+	{
+		return InMemoryLeagueRepository { store };
+	}
+}
+
+#[rocket::async_trait]
+impl LeagueRepository for InMemoryLeagueRepository
+{
+	async fn collect_leagues_for_user(&self, user_id: i32) -> Result<Vec<League>, ApiError>
+	{
+		let data: StoreGuard<'_> = self.store.lock().await;
+		let leagues: Vec<League> = data.leagues.iter()
+			.filter(|league: &&League| data.league_members.iter().any(|(league_id, member_id)| Some(*league_id) == league.id && *member_id == user_id))
+			.cloned()
+			.collect();
+
+		return Ok(leagues);
+	}
+
+	async fn collect_leagues(&self, id_show_season : i32) -> Result<Vec<League>, ApiError>
+	{
+		let leagues: Vec<League> = self.store.lock().await.leagues.iter()
+			.filter(|league: &&League| league.id_showseason == Some(id_show_season))
+			.cloned()
+			.collect();
+
+		return Ok(leagues);
+	}
+
+	async fn create_league(&self, league: &League) -> Result<i32, ApiError>
+	{
+		let mut data: StoreGuard<'_> = self.store.lock().await;
+		let id: i32 = data.next_id("leagues");
+		data.leagues.push(League { id: Some(id), name: league.name.clone(), id_showseason: league.id_showseason });
+
+		return Ok(id);
+	}
+
+	async fn delete_league(&self, id: i32) -> Result<(), ApiError>
+	{
+		let mut data: StoreGuard<'_> = self.store.lock().await;
+		if !data.league_exists(id)
+		{
+			return Err(ApiError::NotFound(format!("League [{}] not found", id)));
+		}
+
+		data.leagues.retain(|league: &League| league.id != Some(id));
+		data.league_members.retain(|(league_id, _)| *league_id != id);
+		data.league_picks.retain(|pick: &LeaguePick| pick.league_id != id);
+		data.scoring_rules.retain(|rules: &ScoringRules| rules.league_id != Some(id));
+
+		return Ok(());
+	}
+
+	async fn delete_league_members(&self, league_id: i32) -> Result<(), ApiError>
+	{
+		self.store.lock().await.league_members.retain(|(member_league_id, _)| *member_league_id != league_id);
+		return Ok(());
+	}
+
+	async fn delete_league_picks(&self, league_id: i32) -> Result<(), ApiError>
+	{
+		self.store.lock().await.league_picks.retain(|pick: &LeaguePick| pick.league_id != league_id);
+		return Ok(());
+	}
+
+	async fn add_user_to_league(&self, user_id: i32, league_id: i32) -> Result<(), ApiError>
+	{
+		let mut data: StoreGuard<'_> = self.store.lock().await;
+		if !data.league_exists(league_id)
+		{
+			return Err(missing_reference("league_members", "league", league_id));
+		}
+
+		if !data.user_exists(user_id)
+		{
+			return Err(missing_reference("league_members", "user", user_id));
+		}
+
+		if data.league_members.contains(&(league_id, user_id))
+		{
+			return Err(ApiError::Conflict(format!("User [{}] is already in league [{}]", user_id, league_id)));
+		}
+
+		data.league_members.push((league_id, user_id));
+
+		return Ok(());
+	}
+
+	async fn remove_user_from_league(&self, user_id: i32, league_id: i32) -> Result<(), ApiError>
+	{
+		let mut data: StoreGuard<'_> = self.store.lock().await;
+		let member_count: usize = data.league_members.len();
+		data.league_members.retain(|membership: &(i32, i32)| *membership != (league_id, user_id));
+
+		if data.league_members.len() == member_count
+		{
+			return Err(ApiError::NotFound(format!("User [{}] is not in league [{}]", user_id, league_id)));
+		}
+
+		return Ok(());
+	}
+
+	async fn set_league_pick(&self, league_id: i32, user_id: i32, round_number: i32, contestant_id: i32, rank_pick: i32) -> Result<(), ApiError>
+	{
+		let mut data: StoreGuard<'_> = self.store.lock().await;
+		let game_show_id: Option<i32> = data.leagues.iter()
+			.find(|league: &&League| league.id == Some(league_id))
+			.and_then(|league: &League| league.id_showseason);
+
+		let Some(round) = data.rounds.iter().find(|round: &&Round| game_show_id.is_some() && round.game_show_id == game_show_id && round.round_number == round_number) else
+		{
+			return Err(ApiError::Validation(format!("Round {} does not exist for this league's season", round_number)));
+		};
+
+		let lock_time = round.lock_time.or(round.air_date);
+		league_accessor::ensure_round_takes_picks(round_number, round.status, lock_time, lock_time.is_some_and(|lock_time| lock_time <= Utc::now()))?;
+
+		if !data.user_exists(user_id)
+		{
+			return Err(missing_reference("league_picks", "user", user_id));
+		}
+
+		if !data.contestant_exists(contestant_id)
+		{
+			return Err(missing_reference("league_picks", "contestant", contestant_id));
+		}
+
+		match data.league_picks.iter_mut().find(|pick: &&mut LeaguePick|
+			pick.league_id == league_id && pick.user_id == user_id && pick.round_number == round_number && pick.contestant_id == contestant_id)
+		{
+			Some(existing_pick) => existing_pick.rank_pick = rank_pick,
+			None => data.league_picks.push(LeaguePick { league_id, user_id, round_number, contestant_id, rank_pick }),
+		}
+
+		return Ok(());
+	}
+
+	async fn restore_league_pick(&self, pick: &LeaguePick) -> Result<(), ApiError>
+	{
+		let mut data: StoreGuard<'_> = self.store.lock().await;
+		if !data.league_exists(pick.league_id)
+		{
+			return Err(missing_reference("league_picks", "league", pick.league_id));
+		}
+
+		if !data.user_exists(pick.user_id)
+		{
+			return Err(missing_reference("league_picks", "user", pick.user_id));
+		}
+
+		if !data.contestant_exists(pick.contestant_id)
+		{
+			return Err(missing_reference("league_picks", "contestant", pick.contestant_id));
+		}
+
+		if data.league_picks.iter().any(|existing: &LeaguePick|
+			existing.league_id == pick.league_id && existing.user_id == pick.user_id && existing.round_number == pick.round_number && existing.contestant_id == pick.contestant_id)
+		{
+			return Err(ApiError::Conflict(format!("League [{}] already has that pick for user [{}]", pick.league_id, pick.user_id)));
+		}
+
+		data.league_picks.push(pick.clone());
+
+		return Ok(());
+	}
+
+	async fn select_league(&self, id: i32) -> Result<League, ApiError>
+	{
+		return self.store.lock().await.leagues.iter()
+			.find(|league: &&League| league.id == Some(id))
+			.cloned()
+			.ok_or(ApiError::NotFound(format!("League [{}] not found", id)));
+	}
+
+	async fn collect_league_member_ids(&self, league_id: i32) -> Result<Vec<i32>, ApiError>
+	{
+		let member_ids: Vec<i32> = self.store.lock().await.league_members.iter()
+			.filter(|(member_league_id, _)| *member_league_id == league_id)
+			.map(|(_, user_id)| *user_id)
+			.collect();
+
+		return Ok(member_ids);
+	}
+
+	async fn collect_league_members(&self, league_id: i32) -> Result<Vec<User>, ApiError>
+	{
+		let data: StoreGuard<'_> = self.store.lock().await;
+		let members: Vec<User> = data.users.iter()
+			.filter(|stored: &&StoredUser| stored.user.id.is_some_and(|user_id: i32| data.league_members.contains(&(league_id, user_id))))
+			.map(|stored: &StoredUser| stored.user.clone())
+			.collect();
+
+		return Ok(members);
+	}
+
+	async fn collect_league_picks(&self, league_id: i32) -> Result<Vec<LeaguePick>, ApiError>
+	{
+		let picks: Vec<LeaguePick> = self.store.lock().await.league_picks.iter()
+			.filter(|pick: &&LeaguePick| pick.league_id == league_id)
+			.cloned()
+			.collect();
+
+		return Ok(picks);
+	}
+
+	async fn select_scoring_rules(&self, league_id: i32) -> Result<Option<ScoringRules>, ApiError>
+	{
+		let rules: Option<ScoringRules> = self.store.lock().await.scoring_rules.iter()
+			.find(|rules: &&ScoringRules| rules.league_id == Some(league_id))
+			.cloned();
+
+		return Ok(rules);
+	}
+
+	async fn create_scoring_rules(&self, league_id: i32, rules: &ScoringRules) -> Result<(), ApiError>
+	{
+		let mut data: StoreGuard<'_> = self.store.lock().await;
+		if !data.league_exists(league_id)
+		{
+			return Err(missing_reference("scoring_rules", "league", league_id));
+		}
+
+		if data.scoring_rules.iter().any(|existing: &ScoringRules| existing.league_id == Some(league_id))
+		{
+			return Err(ApiError::Conflict(format!("League [{}] already has scoring rules", league_id)));
+		}
+
+		let id: i32 = data.next_id("scoring_rules");
+		data.scoring_rules.push(ScoringRules { id: Some(id), league_id: Some(league_id), ..rules.clone() });
+
+		return Ok(());
+	}
+
+	async fn update_scoring_rules(&self, league_id: i32, rules: &ScoringRules) -> Result<(), ApiError>
+	{
+		let mut data: StoreGuard<'_> = self.store.lock().await;
+		let existing: &mut ScoringRules = data.scoring_rules.iter_mut()
+			.find(|existing: &&mut ScoringRules| existing.league_id == Some(league_id))
+			.ok_or(ApiError::NotFound(format!("League [{}] has no scoring rules to update", league_id)))?;
+
+		*existing = ScoringRules { id: existing.id, league_id: Some(league_id), ..rules.clone() };
+
+		return Ok(());
+	}
+
+	async fn delete_scoring_rules(&self, league_id: i32) -> Result<(), ApiError>
+	{
+		self.store.lock().await.scoring_rules.retain(|rules: &ScoringRules| rules.league_id != Some(league_id));
+		return Ok(());
+	}
+}
+
+pub struct InMemoryUserRepository
+{
+	store: InMemoryStore,
+}
+
+impl InMemoryUserRepository
+{
+	pub fn new(store: InMemoryStore) -> Self
+	{
+		return InMemoryUserRepository { store };
+	}
+
+	fn user_not_found(id: i32) -> ApiError
+	{
+		return ApiError::NotFound(format!("User [{}] not found", id));
+	}
+}
+
+#[rocket::async_trait]
+impl UserRepository for InMemoryUserRepository
+{
+	async fn collect_users(&self) -> Result<Vec<User>, ApiError>
+	{
+		return Ok(self.store.lock().await.users.iter().map(|stored: &StoredUser| stored.user.clone()).collect());
+	}
+
+	async fn add_user(&self, user: &User, password_hash: Option<String>) -> Result<(), ApiError>
+	{
+		let mut data: StoreGuard<'_> = self.store.lock().await;
+		let id: i32 = data.next_id("users");
+		data.users.push(StoredUser
+		{
+			user: User { id: Some(id), password: None, ..user.clone() },
+			password_hash
+		});
+
+		return Ok(());
+	}
+
+	async fn edit_user(&self, id: i32, user: &User) -> Result<(), ApiError>
+	{
+		let mut data: StoreGuard<'_> = self.store.lock().await;
+		let stored: &mut StoredUser = data.users.iter_mut()
+			.find(|stored: &&mut StoredUser| stored.user.id == Some(id))
+			.ok_or(Self::user_not_found(id))?;
+
+		stored.user.name = user.name.clone();
+		stored.user.email = user.email.clone();
+
+		return Ok(());
+	}
+
+	async fn delet_user(&self, id: i32) -> Result<(), ApiError>
+	{
+		let mut data: StoreGuard<'_> = self.store.lock().await;
+		if !data.user_exists(id)
+		{
+			return Err(Self::user_not_found(id));
+		}
+
+		data.users.retain(|stored: &StoredUser| stored.user.id != Some(id));
+		data.league_members.retain(|(_, user_id)| *user_id != id);
+		data.league_picks.retain(|pick: &LeaguePick| pick.user_id != id);
+
+		return Ok(());
+	}
+
+	async fn set_password_hash(&self, id: i32, password_hash: &str) -> Result<(), ApiError>
+	{
+		let mut data: StoreGuard<'_> = self.store.lock().await;
+		if let Some(stored) = data.users.iter_mut().find(|stored: &&mut StoredUser| stored.user.id == Some(id))
+		{
+			stored.password_hash = Some(password_hash.to_string());
+		}
+
+		return Ok(());
+	}
+
+	async fn select_user(&self, id: i32) -> Result<User, ApiError>
+	{
+		return self.store.lock().await.users.iter()
+			.find(|stored: &&StoredUser| stored.user.id == Some(id))
+			.map(|stored: &StoredUser| stored.user.clone())
+			.ok_or(Self::user_not_found(id));
+	}
+
+	async fn select_user_credentials(&self, email: &str) -> Result<Option<(User, Option<String>)>, ApiError>
+	{
+		let credentials: Option<(User, Option<String>)> = self.store.lock().await.users.iter()
+			.find(|stored: &&StoredUser| stored.user.email == email)
+			.map(|stored: &StoredUser| (stored.user.clone(), stored.password_hash.clone()));
+
+		return Ok(credentials);
+	}
+}
@@ -1,86 +1,114 @@
-use crate::gameshow_manager::League;
+use chrono::{ DateTime, Utc };
+
+use crate::gameshow_manager::{ League, LeaguePick, RoundStatus, ScoringRules };
+use crate::user_manager::User;
+use crate::utilities::errors::ApiError;
 use crate::utilities::storage::StorageConnector;
 
 use std::sync::Arc;
 
-pub struct LeagueRepository
+// Shared by every LeagueRepository so a closed round refuses picks the same way everywhere.
+pub fn ensure_round_takes_picks(round_number: i32, status: RoundStatus, lock_time: Option<DateTime<Utc>>, lock_time_passed: bool) -> Result<(), ApiError>
+{
+	if !status.accepts_picks()
+	{
+		return Err(ApiError::Conflict(format!("Picks for round {} are closed, the round is {}", round_number, status.as_str())));
+	}
+
+	if let Some(lock_time) = lock_time && lock_time_passed
+	{
+		return Err(ApiError::Conflict(format!("Picks for round {} locked at {}", round_number, lock_time.format("%Y-%m-%d %H:%M UTC"))));
+	}
+
+	return Ok(());
+}
+
+#[rocket::async_trait]
+pub trait LeagueRepository: Send + Sync
+{
+	async fn collect_leagues_for_user(&self, user_id: i32) -> Result<Vec<League>, ApiError>;
+
+	async fn collect_leagues(&self, id_show_season : i32) -> Result<Vec<League>, ApiError>;
+
+	// Returns the id of the new league.
+	async fn create_league(&self, league: &League) -> Result<i32, ApiError>;
+
+	async fn delete_league(&self, id: i32) -> Result<(), ApiError>;
+
+	async fn delete_league_members(&self, league_id: i32) -> Result<(), ApiError>;
+
+	async fn delete_league_picks(&self, league_id: i32) -> Result<(), ApiError>;
+
+	async fn add_user_to_league(&self, user_id: i32, league_id: i32) -> Result<(), ApiError>;
+
+	async fn remove_user_from_league(&self, user_id: i32, league_id: i32) -> Result<(), ApiError>;
+
+	// Picks have to name a round of the league's season that is still taking picks.
+	// A round without its own lock time locks when the episode airs.
+	async fn set_league_pick(&self, league_id: i32, user_id: i32, round_number: i32, contestant_id: i32, rank_pick: i32) -> Result<(), ApiError>;
+
+	// Writes a pick as it was, whatever state its round is in. Only for restoring archived seasons.
+	async fn restore_league_pick(&self, pick: &LeaguePick) -> Result<(), ApiError>;
+
+	async fn select_league(&self, id: i32) -> Result<League, ApiError>;
+
+	async fn collect_league_member_ids(&self, league_id: i32) -> Result<Vec<i32>, ApiError>;
+
+	async fn collect_league_members(&self, league_id: i32) -> Result<Vec<User>, ApiError>;
+
+	async fn collect_league_picks(&self, league_id: i32) -> Result<Vec<LeaguePick>, ApiError>;
+
+	async fn select_scoring_rules(&self, league_id: i32) -> Result<Option<ScoringRules>, ApiError>;
+
+	async fn create_scoring_rules(&self, league_id: i32, rules: &ScoringRules) -> Result<(), ApiError>;
+
+	async fn update_scoring_rules(&self, league_id: i32, rules: &ScoringRules) -> Result<(), ApiError>;
+
+	async fn delete_scoring_rules(&self, league_id: i32) -> Result<(), ApiError>;
+}
+
+pub struct PostgresLeagueRepository
 {
 	connector: Arc<StorageConnector>,
 }
 
-impl LeagueRepository
+impl PostgresLeagueRepository
 {
-	pub async fn new(storage_connection: Arc<StorageConnector>) -> Self
+	pub fn new(storage_connection: Arc<StorageConnector>) -> Self
 	{
-		let league_repository: LeagueRepository = LeagueRepository
+		let league_repository: PostgresLeagueRepository = PostgresLeagueRepository
 		{
 			connector: Arc::clone(&storage_connection),
 		};
 
-		league_repository.initialize_storage_leagues().await;
-		league_repository.initialize_storage_league_memberships().await;
-		league_repository.initialize_storage_league_picks().await;
-
 		return league_repository;
 	}
+}
 
-	async fn initialize_storage_leagues(&self) -> ()
+#[rocket::async_trait]
+impl LeagueRepository for PostgresLeagueRepository
+{
+	async fn collect_leagues_for_user(&self, user_id: i32) -> Result<Vec<League>, ApiError>
 	{
-		self.connector.storage
-			.execute(
-				"CREATE TABLE IF NOT EXISTS leagues (
-					id SERIAL PRIMARY KEY,
-					name TEXT NOT NULL,
-					id_showseason INTEGER
-				)",
-				&[]
-			).await
-			.expect("Failed to create table");
+		let leagues: Vec<League> = self.connector.client().await?
+			.query(
+				"SELECT l.id, l.name, l.id_showseason
+				FROM leagues l
+				JOIN league_members lm ON l.id = lm.league_id
+				WHERE lm.user_id = $1",
+				&[&user_id]
+			).await?
+			.iter()
+			.map(|row: &tokio_postgres::Row| League { id: Some(row.get(0)), name: row.get(1), id_showseason: row.get(2) })
+			.collect::<Vec<League>>();
+
+		return Ok(leagues);
 	}
 
-	async fn initialize_storage_league_memberships(&self) -> ()
+	async fn collect_leagues(&self, id_show_season : i32) -> Result<Vec<League>, ApiError>
 	{
-		self.connector.storage
-			.execute(
-				"CREATE TABLE IF NOT EXISTS league_members (
-						id SERIAL PRIMARY KEY,
-						league_id INTEGER NOT NULL REFERENCES leagues(id)
-							ON DELETE CASCADE,
-						user_id INTEGER NOT NULL REFERENCES users(id)
-							ON DELETE CASCADE,
-						UNIQUE(league_id, user_id)
-				)",
-				&[]
-			).await
-			.expect("Failed to create league_members table");
-	}
-
-	async fn initialize_storage_league_picks(&self) -> ()
-	{
-		self.connector.storage
-			.execute(
-				"CREATE TABLE IF NOT EXISTS league_picks (
-						id SERIAL PRIMARY KEY,
-						league_id INTEGER NOT NULL REFERENCES leagues(id)
-							ON DELETE CASCADE,
-						user_id INTEGER NOT NULL REFERENCES users(id)
-							ON DELETE CASCADE,
-						round_number INTEGER NOT NULL,
-						contestant_id INTEGER NOT NULL REFERENCES contestants(contestant_id)
-							ON DELETE CASCADE,
-						rank_pick INTEGER NOT NULL,
-						UNIQUE(league_id, user_id, round_number, contestant_id)
-				)",
-				&[]
-			).await
-			.expect("Failed to create league_picks table");
-	}
-
-	pub async fn collect_leagues(&self, id_show_season : i32) -> Result<Vec<League>, String>
-	{
-		let users: Vec<League> = self.connector.storage
-			.query("SELECT id, name, id_showseason FROM leagues WHERE id_showseason = $1", &[&id_show_season]).await
-			.map_err(|e: tokio_postgres::Error| e.to_string()) ?
+		let users: Vec<League> = self.connector.client().await?
+			.query("SELECT id, name, id_showseason FROM leagues WHERE id_showseason = $1", &[&id_show_season]).await?
 			.iter()
 			.map(|row: &tokio_postgres::Row| League { id: Some(row.get(0)), name: row.get(1), id_showseason: row.get(2) })
 			.collect::<Vec<League>>();
@@ -88,63 +116,243 @@ impl LeagueRepository
 		return Ok(users);
 	}
 
-	pub async fn create_league(&self, league: &League) -> Result<(), String>
+	async fn create_league(&self, league: &League) -> Result<i32, ApiError>
 	{
 		println!("create_league[{}], [{}]", league.name, league.id_showseason.unwrap_or(-1));
-		self.connector.storage
-			.execute(
-				"INSERT INTO leagues (name, id_showseason) VALUES ($1, $2)",
+		let row: tokio_postgres::Row = self.connector.client().await?
+			.query_one(
+				"INSERT INTO leagues (name, id_showseason) VALUES ($1, $2) RETURNING id",
 				&[&league.name, &league.id_showseason]
-			).await
-			.map_err(|e: tokio_postgres::Error| e.to_string())?;
+			).await?;
+
+		return Ok(row.get(0));
+	}
+
+	async fn delete_league(&self, id: i32) -> Result<(), ApiError>
+	{
+		let deleted_rows: u64 = self.connector.client().await?
+			.execute("DELETE FROM leagues WHERE id = $1", &[&id]).await?;
+
+		if deleted_rows == 0
+		{
+			return Err(ApiError::NotFound(format!("League [{}] not found", id)));
+		}
 
 		return Ok(());
 	}
 
-	pub async fn delete_league(&self, id: i32) -> Result<(), String>
+	async fn delete_league_members(&self, league_id: i32) -> Result<(), ApiError>
 	{
-		self.connector.storage
-			.execute("DELETE FROM leagues WHERE id = $1", &[&id]).await
-			.map_err(|e|  e.to_string())?;
+		self.connector.client().await?
+			.execute("DELETE FROM league_members WHERE league_id = $1", &[&league_id]).await?;
 
 		return Ok(());
 	}
 
-	pub async fn add_user_to_league(&self, user_id: i32, league_id: i32) -> Result<(), String>
+	async fn delete_league_picks(&self, league_id: i32) -> Result<(), ApiError>
 	{
-		self.connector.storage
+		self.connector.client().await?
+			.execute("DELETE FROM league_picks WHERE league_id = $1", &[&league_id]).await?;
+
+		return Ok(());
+	}
+
+	async fn add_user_to_league(&self, user_id: i32, league_id: i32) -> Result<(), ApiError>
+	{
+		self.connector.client().await?
 			.execute(
 					"INSERT INTO league_members (league_id, user_id) VALUES ($1, $2)",
 					&[&league_id, &user_id]
-			).await
-			.map_err(|e: tokio_postgres::Error| e.to_string())?;
+			).await?;
 		
 		Ok(())
 	}
 
-	pub async fn remove_user_from_league(&self, user_id: i32, league_id: i32) -> Result<(), String>
+	async fn remove_user_from_league(&self, user_id: i32, league_id: i32) -> Result<(), ApiError>
 	{
-		self.connector.storage
+		let deleted_rows: u64 = self.connector.client().await?
 			.execute(
 					"DELETE FROM league_members WHERE user_id = $1 AND league_id = $2",
 					&[&user_id, &league_id]
-			).await
-			.map_err(|e: tokio_postgres::Error| e.to_string())?;
+			).await?;
+
+		if deleted_rows == 0
+		{
+			return Err(ApiError::NotFound(format!("User [{}] is not in league [{}]", user_id, league_id)));
+		}
 		
 		Ok(())
 	}
 
-	pub async fn set_league_pick(&self, league_id: i32, user_id: i32, round_number: i32, contestant_id: i32, rank_pick: i32) -> Result<(), String>
+	async fn set_league_pick(&self, league_id: i32, user_id: i32, round_number: i32, contestant_id: i32, rank_pick: i32) -> Result<(), ApiError>
 	{
-		self.connector.storage
+		let round_row: Option<tokio_postgres::Row> = self.connector.client().await?
+			.query_opt(
+				"SELECT r.status, COALESCE(r.lock_time, r.air_date), COALESCE(r.lock_time, r.air_date) <= NOW()
+				FROM rounds r
+				JOIN leagues l ON l.id_showseason = r.game_show_id
+				WHERE l.id = $1 AND r.round_number = $2",
+				&[&league_id, &round_number]
+			).await?;
+
+		let Some(row) = round_row else
+		{
+			return Err(ApiError::Validation(format!("Round {} does not exist for this league's season", round_number)));
+		};
+
+		let status: RoundStatus = RoundStatus::parse(row.get(0))?;
+		let lock_time: Option<DateTime<Utc>> = row.get(1);
+		let lock_time_passed: Option<bool> = row.get(2);
+
+		ensure_round_takes_picks(round_number, status, lock_time, lock_time_passed.unwrap_or(false))?;
+
+		self.connector.client().await?
 			.execute(
 					"INSERT INTO league_picks (league_id, user_id, round_number, contestant_id, rank_pick) VALUES ($1, $2, $3, $4, $5)
 					ON CONFLICT (league_id, user_id, contestant_id, round_number) DO UPDATE SET rank_pick = EXCLUDED.rank_pick",
 					&[&league_id, &user_id, &round_number, &contestant_id, &rank_pick]
-			).await
-			.map_err(|e: tokio_postgres::Error| e.to_string())?;
+			).await?;
 		
 		Ok(())
 	}
 
+	async fn restore_league_pick(&self, pick: &LeaguePick) -> Result<(), ApiError>
+	{
+		self.connector.client().await?
+			.execute(
+				"INSERT INTO league_picks (league_id, user_id, round_number, contestant_id, rank_pick) VALUES ($1, $2, $3, $4, $5)",
+				&[&pick.league_id, &pick.user_id, &pick.round_number, &pick.contestant_id, &pick.rank_pick]
+			).await?;
+
+		return Ok(());
+	}
+
+	async fn select_league(&self, id: i32) -> Result<League, ApiError>
+	{
+		let row_option: Option<tokio_postgres::Row> = self.connector.client().await?
+			.query_opt("SELECT id, name, id_showseason FROM leagues WHERE id = $1", &[&id]).await?;
+
+		match row_option
+		{
+			Some(row) => return Ok(League { id: Some(row.get(0)), name: row.get(1), id_showseason: row.get(2) }),
+			None => return Err(ApiError::NotFound(format!("League [{}] not found", id))),
+		}
+	}
+
+	async fn collect_league_member_ids(&self, league_id: i32) -> Result<Vec<i32>, ApiError>
+	{
+		let member_ids: Vec<i32> = self.connector.client().await?
+			.query("SELECT user_id FROM league_members WHERE league_id = $1", &[&league_id]).await?
+			.iter()
+			.map(|row: &tokio_postgres::Row| row.get(0))
+			.collect::<Vec<i32>>();
+
+		return Ok(member_ids);
+	}
+
+	async fn collect_league_members(&self, league_id: i32) -> Result<Vec<User>, ApiError>
+	{
+		let members: Vec<User> = self.connector.client().await?
+			.query(
+				"SELECT u.id, u.name, u.email, u.atype
+				FROM users u
+				JOIN league_members lm ON u.id = lm.user_id
+				WHERE lm.league_id = $1",
+				&[&league_id]
+			).await?
+			.iter()
+			.map(|row: &tokio_postgres::Row| User { id: Some(row.get(0)), name: row.get(1), email: row.get(2), account_type: row.get(3), password: None })
+			.collect::<Vec<User>>();
+
+		return Ok(members);
+	}
+
+	async fn collect_league_picks(&self, league_id: i32) -> Result<Vec<LeaguePick>, ApiError>
+	{
+		let picks: Vec<LeaguePick> = self.connector.client().await?
+			.query(
+				"SELECT league_id, user_id, round_number, contestant_id, rank_pick FROM league_picks WHERE league_id = $1",
+				&[&league_id]
+			).await?
+			.iter()
+			.map(|row: &tokio_postgres::Row| LeaguePick {
+				league_id: row.get(0),
+				user_id: row.get(1),
+				round_number: row.get(2),
+				contestant_id: row.get(3),
+				rank_pick: row.get(4)
+			})
+			.collect::<Vec<LeaguePick>>();
+
+		return Ok(picks);
+	}
+
+	async fn select_scoring_rules(&self, league_id: i32) -> Result<Option<ScoringRules>, ApiError>
+	{
+		let row_option: Option<tokio_postgres::Row> = self.connector.client().await?
+			.query_opt(
+				"SELECT id, league_id, survival_points, boot_prediction_bonus, medevac_points, medevac_counts_as_boot,
+					immunity_win_points, reward_win_points, idol_found_points, idol_played_points, vote_received_points, correct_vote_points
+				FROM scoring_rules WHERE league_id = $1",
+				&[&league_id]
+			).await?;
+
+		let rules: Option<ScoringRules> = row_option.map(|row: tokio_postgres::Row| ScoringRules {
+			id: Some(row.get(0)),
+			league_id: Some(row.get(1)),
+			survival_points: row.get(2),
+			boot_prediction_bonus: row.get(3),
+			medevac_points: row.get(4),
+			medevac_counts_as_boot: row.get(5),
+			immunity_win_points: row.get(6),
+			reward_win_points: row.get(7),
+			idol_found_points: row.get(8),
+			idol_played_points: row.get(9),
+			vote_received_points: row.get(10),
+			correct_vote_points: row.get(11)
+		});
+
+		return Ok(rules);
+	}
+
+	async fn create_scoring_rules(&self, league_id: i32, rules: &ScoringRules) -> Result<(), ApiError>
+	{
+		self.connector.client().await?
+			.execute(
+				"INSERT INTO scoring_rules (league_id, survival_points, boot_prediction_bonus, medevac_points, medevac_counts_as_boot,
+					immunity_win_points, reward_win_points, idol_found_points, idol_played_points, vote_received_points, correct_vote_points)
+				VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)",
+				&[&league_id, &rules.survival_points, &rules.boot_prediction_bonus, &rules.medevac_points, &rules.medevac_counts_as_boot,
+					&rules.immunity_win_points, &rules.reward_win_points, &rules.idol_found_points, &rules.idol_played_points, &rules.vote_received_points, &rules.correct_vote_points]
+			).await?;
+
+		return Ok(());
+	}
+
+	async fn update_scoring_rules(&self, league_id: i32, rules: &ScoringRules) -> Result<(), ApiError>
+	{
+		let updated_rows: u64 = self.connector.client().await?
+			.execute(
+				"UPDATE scoring_rules SET survival_points = $1, boot_prediction_bonus = $2, medevac_points = $3, medevac_counts_as_boot = $4,
+					immunity_win_points = $5, reward_win_points = $6, idol_found_points = $7, idol_played_points = $8, vote_received_points = $9, correct_vote_points = $10
+				WHERE league_id = $11",
+				&[&rules.survival_points, &rules.boot_prediction_bonus, &rules.medevac_points, &rules.medevac_counts_as_boot,
+					&rules.immunity_win_points, &rules.reward_win_points, &rules.idol_found_points, &rules.idol_played_points, &rules.vote_received_points, &rules.correct_vote_points, &league_id]
+			).await?;
+
+		if updated_rows == 0
+		{
+			return Err(ApiError::NotFound(format!("League [{}] has no scoring rules to update", league_id)));
+		}
+
+		return Ok(());
+	}
+
+	async fn delete_scoring_rules(&self, league_id: i32) -> Result<(), ApiError>
+	{
+		self.connector.client().await?
+			.execute("DELETE FROM scoring_rules WHERE league_id = $1", &[&league_id]).await?;
+
+		return Ok(());
+	}
 }
@@ -0,0 +1,935 @@
+#![allow(clippy::needless_return)]
+
+#[macro_use]
+extern crate rocket;
+
+pub mod authentication;
+pub mod user_manager;
+pub mod gameshows_accessor;
+pub mod memberships_accessor;
+pub mod league_accessor;
+pub mod utilities;
+pub mod scoring_engine;
+pub mod contestant_search;
+pub mod contestant_history;
+pub mod in_memory_accessor;
+
+pub mod gameshow_manager;
+pub mod season_archive;
+
+use rocket::serde::json::{ Error as JsonError, Json };
+use rocket::data::{ Data, ToByteUnit };
+use rocket::fs::FileServer;
+use rocket::{ Build, Rocket, State, http::{ ContentType, CookieJar, Status } };
+use rocket_cors::{ CorsOptions, AllowedOrigins };
+
+use crate::authentication::{ GameMaster, LoginRequest, Player, Role, SessionUser };
+use crate::gameshows_accessor::{ GameShowRepository, PostgresGameShowRepository };
+use crate::in_memory_accessor::{ InMemoryGameShowRepository, InMemoryLeagueRepository, InMemoryUserRepository };
+use crate::league_accessor::{ LeagueRepository, PostgresLeagueRepository };
+use crate::memberships_accessor::{ PostgresUserRepository, UserRepository };
+use crate::utilities::errors::ApiError;
+use crate::utilities::photo_store::{ MAX_PHOTO_MEBIBYTES, PHOTO_ROUTE, PhotoStore };
+use crate::utilities::storage::StorageConnector;
+use crate::utilities::validation::parsed_body;
+use crate::gameshow_manager::{ CastImportResult, CastMember, Contestant, ContestantProfile, EliminationRequest, EnrollRequest, GameShow, ContestantEvent, ContestantRoundStats, GameShowManager, League, MedevacRequest, ReinstateRequest, Reinstatement, Round, ScoringRules, Tribe, TribeAssignment };
+use crate::contestant_search::ContestantPage;
+use crate::contestant_history::ContestantHistory;
+use crate::scoring_engine::{ Standing, UserScore };
+use crate::season_archive::{ RestoredSeason, SeasonArchive, SeasonArchiveManager };
+use crate::user_manager::User;
+use crate::user_manager::UserManager;
+
+use std::sync::Arc;
+
+const DEFAULT_FRONTEND_ORIGINS : [&str; 2] = ["http://127.0.0.1:8080", "http://localhost:8080"];
+
+// Everything the server needs, wired to `shared_storage`. main() passes a Postgres-backed
+// connector, tests pass an in-memory one.
+pub fn rocket(shared_storage: Arc<StorageConnector>) -> Rocket<Build>
+{
+	let (shared_memberships_repo, shared_gameshows_repo, shared_leagues_repo) : (Arc<dyn UserRepository>, Arc<dyn GameShowRepository>, Arc<dyn LeagueRepository>) =
+		match shared_storage.in_memory_store()
+		{
+			Some(store) =>
+			(
+				Arc::new(InMemoryUserRepository::new(store.clone())),
+				Arc::new(InMemoryGameShowRepository::new(store.clone())),
+				Arc::new(InMemoryLeagueRepository::new(store.clone()))
+			),
+			None =>
+			(
+				Arc::new(PostgresUserRepository::new(Arc::clone(&shared_storage))),
+				Arc::new(PostgresGameShowRepository::new(Arc::clone(&shared_storage))),
+				Arc::new(PostgresLeagueRepository::new(Arc::clone(&shared_storage)))
+			),
+		};
+
+	// Photos uploaded while running in memory go to a throwaway directory, like everything else.
+	let photo_store: PhotoStore = match shared_storage.in_memory_store()
+	{
+		Some(_) => PhotoStore::temporary(),
+		None => PhotoStore::from_environment(),
+	};
+	let photo_server: FileServer = FileServer::from(photo_store.directory());
+
+	let user_manager: UserManager = UserManager::create(Arc::clone(&shared_memberships_repo));
+	let season_archive_manager: SeasonArchiveManager = SeasonArchiveManager::create(	Arc::clone(&shared_gameshows_repo),
+																											Arc::clone(&shared_leagues_repo),
+																											Arc::clone(&shared_memberships_repo),
+																											Arc::clone(&shared_storage)
+																										);
+	let gameshow_manager : GameShowManager = GameShowManager::create(	Arc::clone(&shared_gameshows_repo),
+																							Arc::clone(&shared_leagues_repo),
+																							Arc::clone(&shared_storage)
+																						);
+
+	// Credentials have to be allowed for the browser to send the session cookie cross-origin,
+	// so only the frontend's own origins are let through.
+	let cors: rocket_cors::Cors = CorsOptions::default()
+		.allowed_origins(AllowedOrigins::some_exact(&frontend_origins()))
+		.allow_credentials(true)
+		.to_cors()
+		.expect("Error while building CORS");
+
+	rocket::build()
+		.manage(user_manager)
+		.manage(gameshow_manager)
+		.manage(season_archive_manager)
+		.manage(photo_store)
+		.mount(PHOTO_ROUTE, photo_server)
+		.mount("/", routes![	add_user, collect_users, update_user, delete_user,
+									login, logout, fetch_session, login_preflight, logout_preflight, collect_leagues_for_user,
+									collect_gameshows, add_gameshow, update_gameshow, delete_gameshow,
+									collect_rounds, create_round, update_round, delete_round, rounds_preflight, round_preflight,
+									collect_tribes, create_tribe, update_tribe, delete_tribe, merge_tribes, tribes_preflight, tribe_preflight, merge_tribes_preflight,
+									collect_tribe_assignments, assign_contestant_to_tribe, delete_tribe_assignment, tribe_assignments_preflight,
+									collect_contestant_events, record_contestant_event, contestant_events_preflight,
+									collect_round_stats, record_round_stats, delete_round_stats, round_stats_preflight,
+									import_cast_json, import_cast_csv, import_cast_unsupported, import_cast_preflight,
+									export_season, restore_season, export_season_preflight, restore_season_preflight,
+									create_contestant, select_contestant_by_name, select_contestant, search_contestants, delete_contestant, fetch_contestants_on_show, collect_contestant_history,
+									update_contestant_profile, upload_contestant_photo, delete_contestant_photo, contestant_profile_preflight, contestant_photo_preflight,
+									enroll_contestant, eliminate_contestant, medevac_contestant, reinstate_contestant,
+									gameshow_preflight, gameshow_preflight_for_delete, create_contestant_preflight, delete_contestant_preflight,
+									set_league_pick_preflight, enroll_contestant_preflight, add_user_to_league_preflight, create_league_preflight,
+									collect_leagues, create_league, delete_league, add_user_to_league, remove_user_from_league,
+									set_league_pick, collect_league_scores, collect_league_standings,
+									fetch_scoring_rules, create_scoring_rules, update_scoring_rules, delete_scoring_rules, scoring_rules_preflight])
+		.register("/", catchers![authentication::unauthorized, authentication::forbidden])
+		.attach(cors)
+}
+
+// Origins the browser may call the API from with the session cookie. `frontend_origins` in
+// Rocket.toml or ROCKET_FRONTEND_ORIGINS overrides the Trunk dev server's.
+fn frontend_origins() -> Vec<String>
+{
+	return rocket::Config::figment()
+		.extract_inner::<Vec<String>>("frontend_origins")
+		.unwrap_or(DEFAULT_FRONTEND_ORIGINS.iter().map(|origin: &&str| origin.to_string()).collect());
+}
+
+#[get("/api/users")]
+async fn collect_users(
+	manager : &State<UserManager>,
+	_game_master : GameMaster
+	) -> Result<Json<Vec<User>>, ApiError>
+{
+	return manager.collect_users().await.map(Json);
+}
+
+// Anyone can sign up as a player. Gamemaster accounts can only be made by another
+// gamemaster, except for the very first account so a fresh database can be set up.
+#[post("/api/users", data = "<user>")]
+async fn add_user(
+	manager : &State<UserManager>,
+	game_master : Option<GameMaster>,
+	user: Result<Json<User>, JsonError<'_>>
+	) -> Result<Json<Vec<User>>, ApiError>
+{
+	let user: User = parsed_body(user)?;
+	if Role::from_account_type(&user.account_type) == Role::GameMaster && game_master.is_none()
+	{
+		let existing_users: Vec<User> = manager.collect_users().await?;
+		if !existing_users.is_empty()
+		{
+			return Err(ApiError::Forbidden("Only gamemasters can create gamemaster accounts".to_string()));
+		}
+	}
+
+	return manager.add_user_and_refresh(&user).await.map(Json);
+}
+
+#[put("/api/users/<id>", data = "<user>")]
+async fn update_user(
+	manager : &State<UserManager>,
+	_game_master : GameMaster,
+	id: i32,
+	user: Result<Json<User>, JsonError<'_>>
+	) -> Result<Json<Vec<User>>, ApiError>
+{
+	let user: User = parsed_body(user)?;
+	return manager.edit_user_and_refresh(id, &user).await.map(Json)
+}
+
+#[delete("/api/users/<id>")]
+async fn delete_user(manager : &State<UserManager>, _game_master : GameMaster, id: i32) -> Result<Json<Vec<User>>, ApiError>
+{
+	return manager.delete_user_and_refresh(id).await.map(Json);
+}
+
+#[post("/api/auth/login", data = "<login_request>")]
+async fn login(
+	manager : &State<UserManager>,
+	cookies : &CookieJar<'_>,
+	login_request: Result<Json<LoginRequest>, JsonError<'_>>
+	) -> Result<Json<User>, ApiError>
+{
+	let login_request: LoginRequest = parsed_body(login_request)?;
+	let user: User = manager.login(&login_request).await?;
+	authentication::start_session(cookies, &user);
+
+	return Ok(Json(user));
+}
+
+#[post("/api/auth/logout")]
+async fn logout(cookies : &CookieJar<'_>) -> Status
+{
+	authentication::end_session(cookies);
+	return Status::NoContent;
+}
+
+#[get("/api/auth/session")]
+async fn fetch_session(session: SessionUser) -> Json<User>
+{
+	return Json(session.user);
+}
+
+// Players see their own leagues, gamemasters anyone's.
+#[get("/api/users/<user_id>/leagues")]
+async fn collect_leagues_for_user(
+	manager : &State<GameShowManager>,
+	session : SessionUser,
+	user_id: i32
+	) -> Result<Json<Vec<League>>, ApiError>
+{
+	if session.id() != user_id && session.role() != Role::GameMaster
+	{
+		return Err(ApiError::Forbidden(format!("User [{}] can only see their own leagues", session.id())));
+	}
+
+	return manager.collect_leagues_for_user(user_id).await.map(Json);
+}
+
+#[get("/api/gameshows")]
+async fn collect_gameshows(
+	manager : &State<GameShowManager>
+	) -> Result<Json<Vec<GameShow>>, ApiError>
+{
+	return manager.collect_gameshows().await.map(Json);
+}
+
+#[post("/api/gameshows", data = "<gameshow>")]
+async fn add_gameshow(
+	manager : &State<GameShowManager>,
+	_game_master : GameMaster,
+	gameshow: Result<Json<GameShow>, JsonError<'_>>
+	) -> Result<Json<Vec<GameShow>>, ApiError>
+{
+	let gameshow: GameShow = parsed_body(gameshow)?;
+	return manager.add_gameshow_and_refresh(&gameshow).await.map(Json);
+}
+
+// Also how a season moves through its statuses, draft to enrollment to live to finished.
+#[put("/api/gameshows/<id>", data = "<gameshow>")]
+async fn update_gameshow(
+	manager : &State<GameShowManager>,
+	_game_master : GameMaster,
+	id: i32,
+	gameshow: Result<Json<GameShow>, JsonError<'_>>
+	) -> Result<Json<GameShow>, ApiError>
+{
+	let gameshow: GameShow = parsed_body(gameshow)?;
+	return manager.update_gameshow(id, &gameshow).await.map(Json);
+}
+
+#[delete("/api/gameshows/<id>")]
+async fn delete_gameshow(manager : &State<GameShowManager>, game_master : GameMaster, id: i32) -> Result<Json<Vec<GameShow>>, ApiError>
+{
+	println!("[{}] attempting to delete seasonshow ID[{}]", game_master.session.user.name, id);
+	return manager.delete_gameshow_and_refresh(id).await.map(Json);
+}
+
+#[get("/api/gameshows/<game_show_id>/rounds")]
+async fn collect_rounds(
+	manager : &State<GameShowManager>,
+	game_show_id: i32
+	) -> Result<Json<Vec<Round>>, ApiError>
+{
+	return manager.collect_rounds(game_show_id).await.map(Json);
+}
+
+#[post("/api/gameshows/<game_show_id>/rounds", data = "<round>")]
+async fn create_round(
+	manager : &State<GameShowManager>,
+	_game_master : GameMaster,
+	game_show_id: i32,
+	round: Result<Json<Round>, JsonError<'_>>
+	) -> Result<Json<Vec<Round>>, ApiError>
+{
+	let round: Round = parsed_body(round)?;
+	return manager.create_round(game_show_id, &round).await.map(Json);
+}
+
+#[put("/api/gameshows/<game_show_id>/rounds/<round_number>", data = "<round>")]
+async fn update_round(
+	manager : &State<GameShowManager>,
+	_game_master : GameMaster,
+	game_show_id: i32,
+	round_number: i32,
+	round: Result<Json<Round>, JsonError<'_>>
+	) -> Result<Json<Vec<Round>>, ApiError>
+{
+	let round: Round = parsed_body(round)?;
+	return manager.update_round(game_show_id, round_number, &round).await.map(Json);
+}
+
+#[delete("/api/gameshows/<game_show_id>/rounds/<round_number>")]
+async fn delete_round(
+	manager : &State<GameShowManager>,
+	_game_master : GameMaster,
+	game_show_id: i32,
+	round_number: i32
+	) -> Result<Json<Vec<Round>>, ApiError>
+{
+	return manager.delete_round(game_show_id, round_number).await.map(Json);
+}
+
+#[get("/api/gameshows/<game_show_id>/tribes")]
+async fn collect_tribes(
+	manager : &State<GameShowManager>,
+	game_show_id: i32
+	) -> Result<Json<Vec<Tribe>>, ApiError>
+{
+	return manager.collect_tribes(game_show_id).await.map(Json);
+}
+
+#[post("/api/gameshows/<game_show_id>/tribes", data = "<tribe>")]
+async fn create_tribe(
+	manager : &State<GameShowManager>,
+	_game_master : GameMaster,
+	game_show_id: i32,
+	tribe: Result<Json<Tribe>, JsonError<'_>>
+	) -> Result<Json<Vec<Tribe>>, ApiError>
+{
+	let tribe: Tribe = parsed_body(tribe)?;
+	return manager.create_tribe(game_show_id, &tribe).await.map(Json);
+}
+
+#[put("/api/gameshows/<game_show_id>/tribes/<tribe_id>", data = "<tribe>")]
+async fn update_tribe(
+	manager : &State<GameShowManager>,
+	_game_master : GameMaster,
+	game_show_id: i32,
+	tribe_id: i32,
+	tribe: Result<Json<Tribe>, JsonError<'_>>
+	) -> Result<Json<Vec<Tribe>>, ApiError>
+{
+	let tribe: Tribe = parsed_body(tribe)?;
+	return manager.update_tribe(game_show_id, tribe_id, &tribe).await.map(Json);
+}
+
+#[delete("/api/gameshows/<game_show_id>/tribes/<tribe_id>")]
+async fn delete_tribe(
+	manager : &State<GameShowManager>,
+	_game_master : GameMaster,
+	game_show_id: i32,
+	tribe_id: i32
+	) -> Result<Json<Vec<Tribe>>, ApiError>
+{
+	return manager.delete_tribe(game_show_id, tribe_id).await.map(Json);
+}
+
+// Everyone still in the game joins the merged tribe from `from_round`.
+#[post("/api/gameshows/<game_show_id>/tribes/<tribe_id>/merge?<from_round>")]
+async fn merge_tribes(
+	manager : &State<GameShowManager>,
+	_game_master : GameMaster,
+	game_show_id: i32,
+	tribe_id: i32,
+	from_round: i32
+	) -> Result<Json<Vec<TribeAssignment>>, ApiError>
+{
+	return manager.merge_tribes(game_show_id, tribe_id, from_round).await.map(Json);
+}
+
+#[get("/api/gameshows/<game_show_id>/tribe_assignments")]
+async fn collect_tribe_assignments(
+	manager : &State<GameShowManager>,
+	game_show_id: i32
+	) -> Result<Json<Vec<TribeAssignment>>, ApiError>
+{
+	return manager.collect_tribe_assignments(game_show_id).await.map(Json);
+}
+
+// Swaps are recorded by assigning the contestant again from a later round.
+#[post("/api/gameshows/<game_show_id>/tribe_assignments", data = "<assignment>")]
+async fn assign_contestant_to_tribe(
+	manager : &State<GameShowManager>,
+	_game_master : GameMaster,
+	game_show_id: i32,
+	assignment: Result<Json<TribeAssignment>, JsonError<'_>>
+	) -> Result<Json<Vec<TribeAssignment>>, ApiError>
+{
+	let assignment: TribeAssignment = parsed_body(assignment)?;
+	return manager.assign_contestant_to_tribe(game_show_id, &assignment).await.map(Json);
+}
+
+#[delete("/api/gameshows/<game_show_id>/tribe_assignments?<contestant_id>&<from_round>")]
+async fn delete_tribe_assignment(
+	manager : &State<GameShowManager>,
+	_game_master : GameMaster,
+	game_show_id: i32,
+	contestant_id: i32,
+	from_round: i32
+	) -> Result<Json<Vec<TribeAssignment>>, ApiError>
+{
+	return manager.delete_tribe_assignment(game_show_id, contestant_id, from_round).await.map(Json);
+}
+
+#[get("/api/gameshows/<game_show_id>/events")]
+async fn collect_contestant_events(
+	manager : &State<GameShowManager>,
+	game_show_id: i32
+	) -> Result<Json<Vec<ContestantEvent>>, ApiError>
+{
+	return manager.collect_contestant_events(game_show_id).await.map(Json);
+}
+
+// Quits, returns, jury seats, immunity wins and idols. Eliminations and medevacs can also be
+// sent here, the elim and medevac routes are shorthands for them.
+#[post("/api/gameshows/<game_show_id>/events", data = "<event>")]
+async fn record_contestant_event(
+	manager : &State<GameShowManager>,
+	_game_master : GameMaster,
+	game_show_id: i32,
+	event: Result<Json<ContestantEvent>, JsonError<'_>>
+	) -> Result<Json<Vec<ContestantEvent>>, ApiError>
+{
+	let event: ContestantEvent = parsed_body(event)?;
+	return manager.record_contestant_event(game_show_id, &event).await.map(Json);
+}
+
+#[get("/api/gameshows/<game_show_id>/round_stats")]
+async fn collect_round_stats(
+	manager : &State<GameShowManager>,
+	game_show_id: i32
+	) -> Result<Json<Vec<ContestantRoundStats>>, ApiError>
+{
+	return manager.collect_round_stats(game_show_id).await.map(Json);
+}
+
+// Immunity and reward wins, idols and votes for one contestant in one round. Sending them again
+// for the same round replaces them.
+#[post("/api/gameshows/<game_show_id>/round_stats", data = "<stats>")]
+async fn record_round_stats(
+	manager : &State<GameShowManager>,
+	_game_master : GameMaster,
+	game_show_id: i32,
+	stats: Result<Json<ContestantRoundStats>, JsonError<'_>>
+	) -> Result<Json<Vec<ContestantRoundStats>>, ApiError>
+{
+	let stats: ContestantRoundStats = parsed_body(stats)?;
+	return manager.record_round_stats(game_show_id, &stats).await.map(Json);
+}
+
+#[delete("/api/gameshows/<game_show_id>/round_stats?<contestant_id>&<round_number>")]
+async fn delete_round_stats(
+	manager : &State<GameShowManager>,
+	_game_master : GameMaster,
+	game_show_id: i32,
+	contestant_id: i32,
+	round_number: i32
+	) -> Result<Json<Vec<ContestantRoundStats>>, ApiError>
+{
+	return manager.delete_round_stats(game_show_id, contestant_id, round_number).await.map(Json);
+}
+
+// Cast lists are sent as a JSON array of cast members or as CSV with a header row.
+#[post("/api/gameshows/<game_show_id>/import", format = "json", data = "<cast>")]
+async fn import_cast_json(
+	manager : &State<GameShowManager>,
+	_game_master : GameMaster,
+	game_show_id: i32,
+	cast: Result<Json<Vec<CastMember>>, JsonError<'_>>
+	) -> Result<Json<Vec<CastImportResult>>, ApiError>
+{
+	let cast: Vec<CastMember> = parsed_body(cast)?;
+	return manager.import_cast(game_show_id, &cast).await.map(Json);
+}
+
+#[post("/api/gameshows/<game_show_id>/import", format = "text/csv", data = "<cast>", rank = 2)]
+async fn import_cast_csv(
+	manager : &State<GameShowManager>,
+	_game_master : GameMaster,
+	game_show_id: i32,
+	cast: Data<'_>
+	) -> Result<Json<Vec<CastImportResult>>, ApiError>
+{
+	let csv_text = cast.open(1.mebibytes()).into_string().await
+		.map_err(|e: std::io::Error| ApiError::Validation(format!("The CSV could not be read: {}", e)))?;
+
+	if !csv_text.is_complete()
+	{
+		return Err(ApiError::Validation("The CSV is larger than 1 MiB".to_string()));
+	}
+
+	let cast: Vec<CastMember> = CastMember::parse_csv(&csv_text)?;
+	return manager.import_cast(game_show_id, &cast).await.map(Json);
+}
+
+#[post("/api/gameshows/<game_show_id>/import", rank = 3)]
+#[allow(unused_variables)]
+async fn import_cast_unsupported(_game_master : GameMaster, game_show_id: i32) -> ApiError
+{
+	return ApiError::Validation("Send the cast list as application/json or text/csv".to_string());
+}
+
+#[get("/api/gameshows/<game_show_id>/export")]
+async fn export_season(
+	manager : &State<SeasonArchiveManager>,
+	_game_master : GameMaster,
+	game_show_id: i32
+	) -> Result<Json<SeasonArchive>, ApiError>
+{
+	return manager.export_season(game_show_id).await.map(Json);
+}
+
+// Restores an exported season as a new one alongside whatever is already there.
+#[post("/api/gameshows/restore", data = "<archive>")]
+async fn restore_season(
+	manager : &State<SeasonArchiveManager>,
+	_game_master : GameMaster,
+	archive: Result<Json<SeasonArchive>, JsonError<'_>>
+	) -> Result<Json<RestoredSeason>, ApiError>
+{
+	let archive: SeasonArchive = parsed_body(archive)?;
+	return manager.restore_season(&archive).await.map(Json);
+}
+
+#[post("/api/contestants", data = "<contestant>")]
+async fn create_contestant(
+	manager : &State<GameShowManager>,
+	_game_master : GameMaster,
+	contestant: Result<Json<Contestant>, JsonError<'_>>
+	) -> Result<Json<Contestant>, ApiError>
+{
+	let contestant: Contestant = parsed_body(contestant)?;
+	let created_contestant: Contestant = manager.create_contestant(&contestant).await?;
+	println!("Created contestant with name [{}]", created_contestant.name);
+
+	return Ok(Json(created_contestant));
+}
+
+// Looks a contestant up by name. When several share the name, pass the disambiguator too.
+#[get("/api/contestants/select?<name>&<disambiguator>")]
+async fn select_contestant_by_name(
+	manager : &State<GameShowManager>,
+	name: &str,
+	disambiguator: Option<&str>
+	) -> Result<Json<Contestant>, ApiError>
+{
+	return manager.select_contestant_by_name(name, disambiguator).await.map(Json);
+}
+
+#[get("/api/contestants/<id>")]
+async fn select_contestant(
+	manager : &State<GameShowManager>,
+	id: i32
+	) -> Result<Json<Contestant>, ApiError>
+{
+	return manager.select_contestant(id).await.map(Json);
+}
+
+// Name search, best matches first. `season` keeps only contestants who played in that season.
+#[get("/api/contestants?<q>&<season>&<limit>&<offset>")]
+async fn search_contestants(
+	manager : &State<GameShowManager>,
+	q: Option<&str>,
+	season: Option<i32>,
+	limit: Option<i64>,
+	offset: Option<i64>
+	) -> Result<Json<ContestantPage>, ApiError>
+{
+	return manager.search_contestants(q.unwrap_or_default(), season, limit, offset).await.map(Json);
+}
+
+#[get("/api/contestants/on_show?<game_show_id>")]
+async fn fetch_contestants_on_show(
+	manager : &State<GameShowManager>,
+	game_show_id: i32
+	) -> Result<Json<Vec<Contestant>>, ApiError>
+{
+	return manager.fetch_contestants_on_show(game_show_id).await.map(Json);
+}
+
+#[get("/api/contestants/<id>/history")]
+async fn collect_contestant_history(manager : &State<GameShowManager>, id: i32) -> Result<Json<ContestantHistory>, ApiError>
+{
+	return manager.collect_contestant_history(id).await.map(Json);
+}
+
+#[delete("/api/contestants/<id>")]
+async fn delete_contestant(manager : &State<GameShowManager>, photos : &State<PhotoStore>, _game_master : GameMaster, id: i32) -> Result<(), ApiError>
+{
+	return manager.delete_contestant(photos, id).await;
+}
+
+#[put("/api/contestants/<id>/profile", data = "<profile>")]
+async fn update_contestant_profile(
+	manager : &State<GameShowManager>,
+	_game_master : GameMaster,
+	id: i32,
+	profile: Result<Json<ContestantProfile>, JsonError<'_>>
+	) -> Result<Json<Contestant>, ApiError>
+{
+	let profile: ContestantProfile = parsed_body(profile)?;
+	return manager.update_contestant_profile(id, &profile).await.map(Json);
+}
+
+// The body is the image itself, with its Content-Type saying which kind it is.
+#[post("/api/contestants/<id>/photo", data = "<photo>")]
+async fn upload_contestant_photo(
+	manager : &State<GameShowManager>,
+	photos : &State<PhotoStore>,
+	_game_master : GameMaster,
+	id: i32,
+	content_type: Option<&ContentType>,
+	photo: Data<'_>
+	) -> Result<Json<Contestant>, ApiError>
+{
+	let bytes = photo.open(MAX_PHOTO_MEBIBYTES.mebibytes()).into_bytes().await
+		.map_err(|e: std::io::Error| ApiError::Validation(format!("The photo could not be read: {}", e)))?;
+
+	if !bytes.is_complete()
+	{
+		return Err(ApiError::Validation(format!("The photo is larger than {} MiB", MAX_PHOTO_MEBIBYTES)));
+	}
+
+	return manager.upload_contestant_photo(photos, id, content_type.unwrap_or(&ContentType::Any), &bytes).await.map(Json);
+}
+
+#[delete("/api/contestants/<id>/photo")]
+async fn delete_contestant_photo(manager : &State<GameShowManager>, photos : &State<PhotoStore>, _game_master : GameMaster, id: i32) -> Result<Json<Contestant>, ApiError>
+{
+	return manager.delete_contestant_photo(photos, id).await.map(Json);
+}
+
+#[post("/api/contestants/enroll", data = "<enrollment>")]
+async fn enroll_contestant(
+	manager : &State<GameShowManager>,
+	_game_master : GameMaster,
+	enrollment: Result<Json<EnrollRequest>, JsonError<'_>>
+	) -> Result<(), ApiError>
+{
+	return manager.enter_contestant_onto_show(&parsed_body(enrollment)?).await;
+}
+
+#[post("/api/contestants/elim", data = "<elimination>")]
+async fn eliminate_contestant(
+	manager : &State<GameShowManager>,
+	_game_master : GameMaster,
+	elimination: Result<Json<EliminationRequest>, JsonError<'_>>
+	) -> Result<(), ApiError>
+{
+	return manager.eliminiate_contestant_from_show(&parsed_body(elimination)?).await;
+}
+
+#[post("/api/contestants/medevac", data = "<medevac>")]
+async fn medevac_contestant(
+	manager : &State<GameShowManager>,
+	_game_master : GameMaster,
+	medevac: Result<Json<MedevacRequest>, JsonError<'_>>
+	) -> Result<(), ApiError>
+{
+	return manager.medically_evacuate_contestant_from_show(&parsed_body(medevac)?).await;
+}
+
+// Takes back an elimination or medevac entered by mistake and returns the rescored leagues.
+#[post("/api/contestants/reinstate", data = "<reinstatement>")]
+async fn reinstate_contestant(
+	manager : &State<GameShowManager>,
+	_game_master : GameMaster,
+	reinstatement: Result<Json<ReinstateRequest>, JsonError<'_>>
+	) -> Result<Json<Reinstatement>, ApiError>
+{
+	return manager.reinstate_contestant(&parsed_body(reinstatement)?).await.map(Json);
+}
+
+#[get("/api/leagues/from_season?<id_showseason>")]
+async fn collect_leagues(
+	manager : &State<GameShowManager>,
+	id_showseason : i32) -> Result<Json<Vec<League>>, ApiError>
+{
+	return manager.collect_leagues(id_showseason).await.map(Json);
+}
+
+#[post("/api/leagues", data = "<league>")]
+async fn create_league(
+	manager : &State<GameShowManager>,
+	_game_master : GameMaster,
+	league: Result<Json<League>, JsonError<'_>>
+	) -> Result<(), ApiError>
+{
+	let league: League = parsed_body(league)?;
+	println!("BE-Main - create_league[{}], [{}]", league.name, league.id_showseason.unwrap_or(-1));
+	let creation_result = manager.create_league(&league).await;
+	return creation_result;
+}
+
+#[delete("/api/leagues/<league_id>")]
+async fn delete_league(manager : &State<GameShowManager>, _game_master : GameMaster, league_id: i32) -> Result<(), ApiError>
+{
+	return manager.delete_league(league_id).await;
+}
+
+#[post("/api/leagues?<user_id>&<league_id>")]
+async fn add_user_to_league(manager : &State<GameShowManager>, _game_master : GameMaster, user_id: i32, league_id: i32) -> Result<(), ApiError>
+{
+	return manager.add_user_to_league(user_id, league_id).await;
+}
+
+#[delete("/api/leagues?<user_id>&<league_id>")]
+async fn remove_user_from_league(manager : &State<GameShowManager>, _game_master : GameMaster, user_id: i32, league_id: i32) -> Result<(), ApiError>
+{
+	return manager.remove_user_from_league(user_id, league_id).await;
+}
+
+// Picks are always recorded against the logged in player.
+#[post("/api/leagues/set_pick?<league_id>&<round_number>&<contestant_id>&<rank_pick>")]
+async fn set_league_pick(manager : &State<GameShowManager>, player: Player, league_id: i32, round_number: i32, contestant_id: i32, rank_pick: i32) -> Result<(), ApiError>
+{
+	return manager.set_league_pick(league_id, player.session.id(), round_number, contestant_id, rank_pick).await;
+}
+
+#[get("/api/leagues/<league_id>/scores?<round_number>")]
+async fn collect_league_scores(
+	manager : &State<GameShowManager>,
+	league_id: i32,
+	round_number: Option<i32>
+	) -> Result<Json<Vec<UserScore>>, ApiError>
+{
+	return manager.calculate_league_scores(league_id, round_number).await.map(Json);
+}
+
+#[get("/api/leagues/<league_id>/standings")]
+async fn collect_league_standings(
+	manager : &State<GameShowManager>,
+	league_id: i32
+	) -> Result<Json<Vec<Standing>>, ApiError>
+{
+	return manager.calculate_league_standings(league_id).await.map(Json);
+}
+
+#[get("/api/leagues/<league_id>/scoring_rules")]
+async fn fetch_scoring_rules(manager : &State<GameShowManager>, league_id: i32) -> Result<Json<ScoringRules>, ApiError>
+{
+	return manager.fetch_scoring_rules(league_id).await.map(Json);
+}
+
+#[post("/api/leagues/<league_id>/scoring_rules", data = "<rules>")]
+async fn create_scoring_rules(
+	manager : &State<GameShowManager>,
+	_game_master : GameMaster,
+	league_id: i32,
+	rules: Result<Json<ScoringRules>, JsonError<'_>>
+	) -> Result<Json<ScoringRules>, ApiError>
+{
+	let rules: ScoringRules = parsed_body(rules)?;
+	return manager.create_scoring_rules(league_id, &rules).await.map(Json);
+}
+
+#[put("/api/leagues/<league_id>/scoring_rules", data = "<rules>")]
+async fn update_scoring_rules(
+	manager : &State<GameShowManager>,
+	_game_master : GameMaster,
+	league_id: i32,
+	rules: Result<Json<ScoringRules>, JsonError<'_>>
+	) -> Result<Json<ScoringRules>, ApiError>
+{
+	let rules: ScoringRules = parsed_body(rules)?;
+	return manager.update_scoring_rules(league_id, &rules).await.map(Json);
+}
+
+#[delete("/api/leagues/<league_id>/scoring_rules")]
+async fn delete_scoring_rules(manager : &State<GameShowManager>, _game_master : GameMaster, league_id: i32) -> Result<(), ApiError>
+{
+	return manager.delete_scoring_rules(league_id).await;
+}
+
+///// These are just fake endpoints added in to stop server warnings //////
+
+// Browsers automatically send out an options request before sending POST requests with Json payloads.
+// This just lets browsers know that it is ok.
+#[options("/api/gameshows")]
+fn gameshow_preflight() -> Status
+{
+	Status::NoContent
+}
+
+#[options("/api/gameshows/<id>")]
+#[allow(unused_variables)]
+fn gameshow_preflight_for_delete(id : i32) -> Status
+{
+	Status::NoContent
+}
+
+// I tried this adding this to stop a warning, but it didn't help.
+#[options("/api/contestants")]
+fn create_contestant_preflight(
+	) -> Result<(), String>
+{
+	return Ok(());
+}
+
+#[options("/api/contestants/<id>")]
+#[allow(unused_variables)]
+async fn delete_contestant_preflight(id: i32) -> Result<(), String>
+{
+	return Ok(());
+}
+
+#[options("/api/contestants/<id>/profile")]
+#[allow(unused_variables)]
+async fn contestant_profile_preflight(id: i32) -> Result<(), String>
+{
+	return Ok(());
+}
+
+#[options("/api/contestants/<id>/photo")]
+#[allow(unused_variables)]
+async fn contestant_photo_preflight(id: i32) -> Result<(), String>
+{
+	return Ok(());
+}
+
+#[options("/api/contestants/enroll")]
+async fn enroll_contestant_preflight() -> Result<(), String>
+{
+	return Ok(());
+}
+
+#[options("/api/leagues?<user_id>&<league_id>")]
+#[allow(unused_variables)]
+async fn add_user_to_league_preflight(user_id : i32, league_id: i32) -> Result<(), String>
+{
+	return Ok(());
+}
+
+#[options("/api/leagues")]
+async fn create_league_preflight() -> Result<(), String>
+{
+	return Ok(());
+}
+
+#[options("/api/leagues/set_pick?<league_id>&<round_number>&<contestant_id>&<rank_pick>")]
+#[allow(unused_variables)]
+async fn set_league_pick_preflight(manager : &State<GameShowManager>, league_id: i32, round_number: i32, contestant_id: i32, rank_pick: i32) -> Result<(), String>
+{
+	return Ok(());
+}
+
+#[options("/api/leagues/<league_id>/scoring_rules")]
+#[allow(unused_variables)]
+async fn scoring_rules_preflight(league_id: i32) -> Result<(), String>
+{
+	return Ok(());
+}
+
+#[options("/api/auth/login")]
+async fn login_preflight() -> Result<(), String>
+{
+	return Ok(());
+}
+
+#[options("/api/auth/logout")]
+async fn logout_preflight() -> Result<(), String>
+{
+	return Ok(());
+}
+
+#[options("/api/gameshows/<game_show_id>/rounds")]
+#[allow(unused_variables)]
+async fn rounds_preflight(game_show_id: i32) -> Result<(), String>
+{
+	return Ok(());
+}
+
+#[options("/api/gameshows/<game_show_id>/import")]
+#[allow(unused_variables)]
+async fn import_cast_preflight(game_show_id: i32) -> Result<(), String>
+{
+	return Ok(());
+}
+
+#[options("/api/gameshows/<game_show_id>/export")]
+#[allow(unused_variables)]
+async fn export_season_preflight(game_show_id: i32) -> Result<(), String>
+{
+	return Ok(());
+}
+
+#[options("/api/gameshows/restore")]
+async fn restore_season_preflight() -> Result<(), String>
+{
+	return Ok(());
+}
+
+#[options("/api/gameshows/<game_show_id>/rounds/<round_number>")]
+#[allow(unused_variables)]
+async fn round_preflight(game_show_id: i32, round_number: i32) -> Result<(), String>
+{
+	return Ok(());
+}
+
+#[options("/api/gameshows/<game_show_id>/tribes")]
+#[allow(unused_variables)]
+async fn tribes_preflight(game_show_id: i32) -> Result<(), String>
+{
+	return Ok(());
+}
+
+#[options("/api/gameshows/<game_show_id>/tribes/<tribe_id>")]
+#[allow(unused_variables)]
+async fn tribe_preflight(game_show_id: i32, tribe_id: i32) -> Result<(), String>
+{
+	return Ok(());
+}
+
+#[options("/api/gameshows/<game_show_id>/tribes/<tribe_id>/merge")]
+#[allow(unused_variables)]
+async fn merge_tribes_preflight(game_show_id: i32, tribe_id: i32) -> Result<(), String>
+{
+	return Ok(());
+}
+
+#[options("/api/gameshows/<game_show_id>/tribe_assignments")]
+#[allow(unused_variables)]
+async fn tribe_assignments_preflight(game_show_id: i32) -> Result<(), String>
+{
+	return Ok(());
+}
+
+#[options("/api/gameshows/<game_show_id>/events")]
+#[allow(unused_variables)]
+async fn contestant_events_preflight(game_show_id: i32) -> Result<(), String>
+{
+	return Ok(());
+}
+
+#[options("/api/gameshows/<game_show_id>/round_stats")]
+#[allow(unused_variables)]
+async fn round_stats_preflight(game_show_id: i32) -> Result<(), String>
+{
+	return Ok(());
+}
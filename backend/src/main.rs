@@ -1,295 +1,39 @@
-#[macro_use]
-extern crate rocket;
+#![allow(clippy::needless_return)]
 
-mod user_manager;
-mod gameshows_accessor;
-mod memberships_accessor;
-mod league_accessor;
-mod utilities;
-
-mod gameshow_manager;
-
-use rocket::serde::{ json::Json };
-use rocket::{ State, response::status::Custom, http::Status };
-use rocket_cors::{ CorsOptions, AllowedOrigins };
-
-use crate::utilities::storage::StorageConnector;
-use crate::gameshow_manager::{Contestant, GameShow, GameShowManager, League};
-use crate::user_manager::User;
-use crate::user_manager::UserManager;
+use backend::in_memory_accessor::InMemoryStore;
+use backend::utilities::migrations;
+use backend::utilities::storage::{ DatabaseConfig, StorageConnector };
 
 use std::sync::Arc;
 
-#[launch]
-async fn rocket() -> _
-{
-	let storage_connection : StorageConnector = StorageConnector::establish_connection().await;
-	let shared_storage: Arc<StorageConnector> = Arc::new(storage_connection);
-
-	let memberships_repository : memberships_accessor::UserRepository = memberships_accessor::UserRepository::new(Arc::clone(&shared_storage)).await;
-	let shared_memberships_repo : Arc<memberships_accessor::UserRepository> = Arc::new(memberships_repository);
-	let gameshows_respository : gameshows_accessor::GameShowRepository = gameshows_accessor::GameShowRepository::new(Arc::clone(&shared_storage)).await;
-	let shared_gameshows_repo : Arc<gameshows_accessor::GameShowRepository> = Arc::new(gameshows_respository);
-	let league_repository : league_accessor::LeagueRepository = league_accessor::LeagueRepository::new(Arc::clone(&shared_storage)).await;
-	let shared_leagues_repo : Arc<league_accessor::LeagueRepository> = Arc::new(league_repository);
-
-	let user_manager: UserManager = UserManager::create(Arc::clone(&shared_memberships_repo)).await;
-	let gameshow_manager : GameShowManager = GameShowManager::create(	Arc::clone(&shared_gameshows_repo),
-																							Arc::clone(&shared_leagues_repo)
-																						).await;
-
-	let cors: rocket_cors::Cors = CorsOptions::default()
-		.allowed_origins(AllowedOrigins::all())
-		.to_cors()
-		.expect("Error while building CORS");
-
-	rocket::build()
-		.manage(user_manager)
-		.manage(gameshow_manager)
-		.mount("/", routes![	add_user, collect_users, update_user, delete_user,
-									collect_gameshows, add_gameshow, delete_gameshow,
-									create_contestant, select_contestant_by_name, collect_contestants, delete_contestant, fetch_contestants_on_show,
-									enroll_contestant, eliminate_contestant, medevac_contestant,
-									gameshow_preflight, gameshow_preflight_for_delete, create_contestant_preflight, delete_contestant_preflight,
-									set_league_pick_preflight, enroll_contestant_preflight, add_user_to_league_preflight, create_league_preflight,
-									collect_leagues, create_league, delete_league, add_user_to_league, remove_user_from_league,
-									set_league_pick])
-		.attach(cors)
-}
-
-#[get("/api/users")]
-async fn collect_users(
-	manager : &State<UserManager>
-	) -> Result<Json<Vec<User>>, Custom<String>>
-{
-	return manager.collect_users().await.map(Json).map_err(|e: String| Custom(Status::InternalServerError, e));
-}
-
-#[post("/api/users", data = "<user>")]
-async fn add_user(
-	manager : &State<UserManager>,
-	user: Json<User>
-	) -> Result<Json<Vec<User>>, Custom<String>>
-{
-	return manager.add_user_and_refresh(&user).await.map(Json).map_err(|e: String| Custom(Status::InternalServerError, e));
-}
-
-#[put("/api/users/<id>", data = "<user>")]
-async fn update_user(
-	manager : &State<UserManager>,
-	id: i32,
-	user: Json<User>
-	) -> Result<Json<Vec<User>>, Custom<String>>
-{
-	return manager.edit_user_and_refresh(id, &user).await.map(Json).map_err(|e: String| Custom(Status::InternalServerError, e))
-}
-
-#[delete("/api/users/<id>")]
-async fn delete_user(manager : &State<UserManager>, id: i32) -> Result<Json<Vec<User>>, Custom<String>>
-{
-	return manager.delete_user_and_refresh(id).await.map(Json).map_err(|e: String| Custom(Status::InternalServerError, e));
-}
-
-#[get("/api/gameshows")]
-async fn collect_gameshows(
-	manager : &State<GameShowManager>
-	) -> Result<Json<Vec<GameShow>>, Custom<String>>
-{
-	return manager.collect_gameshows().await.map(Json).map_err(|e: String| Custom(Status::InternalServerError, e));
-}
+// Applies any pending database migrations and exits without starting the server.
+const MIGRATE_ONLY_FLAG : &str = "--migrate-only";
+// Runs against in-memory storage instead of Postgres. Everything is lost on shutdown.
+const IN_MEMORY_FLAG : &str = "--in-memory";
 
-#[post("/api/gameshows", data = "<gameshow>")]
-async fn add_gameshow(
-	manager : &State<GameShowManager>,
-	gameshow: Json<GameShow>
-	) -> Result<Json<Vec<GameShow>>, Custom<String>>
+#[rocket::main]
+async fn main()
 {
-	return manager.add_gameshow_and_refresh(&gameshow).await.map(Json).map_err(|e: String| Custom(Status::InternalServerError, e));
-}
-
-#[delete("/api/gameshows/<id>")]
-async fn delete_gameshow(manager : &State<GameShowManager>, id: i32) -> Result<Json<Vec<GameShow>>, Custom<String>>
-{
-	println!("attempting to delete seasonshow ID[{}]", id);
-	return manager.delete_gameshow_and_refresh(id).await.map(Json).map_err(|e: String| Custom(Status::InternalServerError, e));
-}
-
-#[post("/api/contestants", data = "<contestant>")]
-async fn create_contestant(
-	manager : &State<GameShowManager>,
-	contestant: Json<Contestant>
-	) -> Result<Json<Contestant>, Custom<String>>
-{
-	let creation_result =manager.create_contestant(&contestant).await.map_err(|e: String| e);
-	match creation_result
+	if std::env::args().any(|argument: String| argument == IN_MEMORY_FLAG)
 	{
-		Ok(_created_contestant) =>
-		{
-			println!("Created contestant with name [{}]", contestant.name);
-		}
-		_=> println!("Failed to create contestant. Error: {}", creation_result.err().unwrap_or("Unknown error".to_string())),
+		println!("Using in-memory storage, nothing will be persisted");
+		backend::rocket(Arc::new(StorageConnector::in_memory(InMemoryStore::new()))).launch().await
+			.expect("Failed to launch the server");
+		return;
 	}
-	let selection_result = manager.select_contestant_by_name(contestant.name.clone());
-	let selection_result_json = selection_result.await.map(Json).map_err(|e: String| Custom(Status::InternalServerError, e));
 
-	return selection_result_json;
-}
-
-#[get("/api/contestants/select?<name>")]
-async fn select_contestant_by_name(
-	manager : &State<GameShowManager>,
-	name: String
-	) -> Result<Json<Contestant>, Custom<String>>
-{
-	println!("SelConByNam>>>{}", name);
+	let database_config : DatabaseConfig = DatabaseConfig::from_environment();
+	let storage_connection : StorageConnector = StorageConnector::establish_connection(&database_config).await;
 
-	return manager.select_contestant_by_name(name).await.map(Json).map_err(|e: String| Custom(Status::InternalServerError, e));
-}
+	let applied_versions: Vec<i32> = migrations::run_pending_migrations(&storage_connection).await
+		.expect("Failed to apply database migrations");
+	println!("Database schema up to date, applied {} new migration(s)", applied_versions.len());
 
-#[get("/api/contestants")]
-async fn collect_contestants(
-	manager : &State<GameShowManager>
-	) -> Result<Json<Vec<Contestant>>, Custom<String>>
-{
-	return manager.collect_all_contestants().await.map(Json).map_err(|e: String| Custom(Status::InternalServerError, e));
-}
-
-#[get("/api/contestants/on_show?<game_show_id>")]
-async fn fetch_contestants_on_show(
-	manager : &State<GameShowManager>,
-	game_show_id: i32
-	) -> Result<Json<Vec<Contestant>>, Custom<String>>
-{
-	return manager.fetch_contestants_on_show(game_show_id).await.map(Json).map_err(|e: String| Custom(Status::InternalServerError, e));
-}
-
-#[delete("/api/contestants/<name>")]
-async fn delete_contestant(manager : &State<GameShowManager>, name: &str) -> Result<(), String>
-{
-	return manager.delete_contestant(name).await.map_err(|e: String| e);
-}
-
-#[post("/api/contestants/enroll" , data = "<contestant>")]
-async fn enroll_contestant(manager : &State<GameShowManager>, contestant: Json<Contestant>) -> Result<(), String>
-{
-	return manager.enter_contestant_onto_show(contestant.id.unwrap(), 
-															contestant.id_showseason.unwrap(),
-															contestant.nickname.clone().unwrap_or("".to_string()) ).await.map_err(|e: String| e);
-}
-
-#[post("/api/contestants/elim", data = "<contestant>")]
-async fn eliminate_contestant(manager : &State<GameShowManager>, contestant: Json<Contestant>) -> Result<(), String>
-{
-	return manager.eliminiate_contestant_from_show(contestant.id.unwrap(), 
-															contestant.id_showseason.unwrap(),
-															contestant.round_number).await.map_err(|e: String| e);
-}
-
-#[post("/api/contestants/medevac", data = "<contestant>")]
-async fn medevac_contestant(manager : &State<GameShowManager>, contestant: Json<Contestant>) -> Result<(), String>
-{
-	return manager.medically_evacuate_contestant_from_show(contestant.id.unwrap(), 
-															contestant.id_showseason.unwrap(),
-															contestant.round_number).await.map_err(|e: String| e);
-}
-
-#[get("/api/leagues/from_season?<id_showseason>")]
-async fn collect_leagues(
-	manager : &State<GameShowManager>,
-	id_showseason : i32) -> Result<Json<Vec<League>>, Custom<String>>
-{
-	return manager.collect_leagues(id_showseason).await.map(Json).map_err(|e: String| Custom(Status::InternalServerError, e));
-}
-
-#[post("/api/leagues", data = "<league>")]
-async fn create_league(
-	manager : &State<GameShowManager>,
-	league : Json<League>
-	) -> Result<(), String>
-{
-	println!("BE-Main - create_league[{}], [{}]", league.name, league.id_showseason.unwrap_or(-1));
-	let creation_result = manager.create_league(&league).await.map_err(|e: String| e);
-	return creation_result;
-}
-
-#[delete("/api/leagues/<league_id>")]
-async fn delete_league(manager : &State<GameShowManager>, league_id: i32) -> Result<(), String>
-{
-	return manager.delete_league(league_id).await.map_err(|e: String| e);
-}
-
-#[post("/api/leagues?<user_id>&<league_id>")]
-async fn add_user_to_league(manager : &State<GameShowManager>, user_id: i32, league_id: i32) -> Result<(), String>
-{
-	return manager.add_user_to_league(user_id, league_id).await.map_err(|e: String| e);
-}
-
-#[delete("/api/leagues?<user_id>&<league_id>")]
-async fn remove_user_from_league(manager : &State<GameShowManager>, user_id: i32, league_id: i32) -> Result<(), String>
-{
-	return manager.remove_user_from_league(user_id, league_id).await.map_err(|e: String| e);
-}
-
-#[post("/api/leagues/set_pick?<league_id>&<user_id>&<round_number>&<contestant_id>&<rank_pick>")]
-async fn set_league_pick(manager : &State<GameShowManager>, league_id: i32, user_id: i32, round_number: i32, contestant_id: i32, rank_pick: i32) -> Result<(), String>
-{
-	return manager.set_league_pick(league_id, user_id, round_number, contestant_id, rank_pick).await.map_err(|e: String| e);
-}
-
-///// These are just fake endpoints added in to stop server warnings //////
-
-// Browsers automatically send out an options request before sending POST requests with Json payloads.
-// This just lets browsers know that it is ok.
-#[options("/api/gameshows")]
-fn gameshow_preflight() -> Status
-{
-	Status::NoContent
-}
-
-#[options("/api/gameshows/<id>")]
-#[allow(unused_variables)]
-fn gameshow_preflight_for_delete(id : i32) -> Status
-{
-	Status::NoContent
-}
-
-// I tried this adding this to stop a warning, but it didn't help.
-#[options("/api/contestants")]
-fn create_contestant_preflight(
-	) -> Result<(), String>
-{
-	return Ok(());
-}
-
-#[options("/api/contestants/<name>")]
-#[allow(unused_variables)]
-async fn delete_contestant_preflight(name: &str) -> Result<(), String>
-{
-	return Ok(());
-}
-
-#[options("/api/contestants/enroll")]
-async fn enroll_contestant_preflight() -> Result<(), String>
-{
-	return Ok(());
-}
-
-#[options("/api/leagues?<user_id>&<league_id>")]
-#[allow(unused_variables)]
-async fn add_user_to_league_preflight(user_id : i32, league_id: i32) -> Result<(), String>
-{
-	return Ok(());
-}
+	if std::env::args().any(|argument: String| argument == MIGRATE_ONLY_FLAG)
+	{
+		return;
+	}
 
-#[options("/api/leagues")]
-async fn create_league_preflight() -> Result<(), String>
-{
-	return Ok(());
+	backend::rocket(Arc::new(storage_connection)).launch().await
+		.expect("Failed to launch the server");
 }
-
-#[options("/api/leagues/set_pick?<league_id>&<user_id>&<round_number>&<contestant_id>&<rank_pick>")]
-#[allow(unused_variables)]
-async fn set_league_pick_preflight(manager : &State<GameShowManager>, league_id: i32, user_id: i32, round_number: i32, contestant_id: i32, rank_pick: i32) -> Result<(), String>
-{
-	return Ok(());
-}
\ No newline at end of file
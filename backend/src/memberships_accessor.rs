@@ -1,87 +1,131 @@
 
 use crate::user_manager::User;
+use crate::utilities::errors::ApiError;
 use crate::utilities::storage::StorageConnector;
 
 use std::sync::Arc;
 
-pub struct UserRepository
+#[rocket::async_trait]
+pub trait UserRepository: Send + Sync
+{
+	async fn collect_users(&self) -> Result<Vec<User>, ApiError>;
+
+	async fn add_user(&self, user: &User, password_hash: Option<String>) -> Result<(), ApiError>;
+
+	async fn edit_user(&self, id: i32, user: &User) -> Result<(), ApiError>;
+
+	async fn delet_user(&self, id: i32) -> Result<(), ApiError>;
+
+	async fn set_password_hash(&self, id: i32, password_hash: &str) -> Result<(), ApiError>;
+
+	async fn select_user(&self, id: i32) -> Result<User, ApiError>;
+
+	async fn select_user_credentials(&self, email: &str) -> Result<Option<(User, Option<String>)>, ApiError>;
+}
+
+pub struct PostgresUserRepository
 {
 	connector: Arc<StorageConnector>,
 }
 
-impl UserRepository
+impl PostgresUserRepository
 {
-	pub async fn new(storage_connection: Arc<StorageConnector>) -> Self
+	pub fn new(storage_connection: Arc<StorageConnector>) -> Self
 	{
 
-		let user_repository: UserRepository = UserRepository
+		let user_repository: PostgresUserRepository = PostgresUserRepository
 		{
 			connector: Arc::clone(&storage_connection),
 		};
 
-		user_repository.initialize_storage().await;
-
 		return user_repository;
 	}
+}
 
-	async fn initialize_storage(&self) -> ()
-	{
-		self.connector.storage
-			.execute(
-				"CREATE TABLE IF NOT EXISTS users (
-					id SERIAL PRIMARY KEY,
-					name TEXT NOT NULL,
-					email TEXT NOT NULL,
-					atype TEXT NOT NULL,
-					num_league_tokens INTEGER DEFAULT 0
-				)",
-				&[]
-			).await
-			.expect("Failed to create table");
-	}
-
-	pub async fn collect_users(&self) -> Result<Vec<User>, String>
+#[rocket::async_trait]
+impl UserRepository for PostgresUserRepository
+{
+	async fn collect_users(&self) -> Result<Vec<User>, ApiError>
 	{
-		let users: Vec<User> = self.connector.storage
-			.query("SELECT id, name, email, atype FROM users", &[]).await
-			.map_err(|e: tokio_postgres::Error| e.to_string()) ?
+		let users: Vec<User> = self.connector.client().await?
+			.query("SELECT id, name, email, atype FROM users", &[]).await?
 			.iter()
-			.map(|row: &tokio_postgres::Row| User { id: Some(row.get(0)), name: row.get(1), email: row.get(2), account_type : row.get(3) })
+			.map(|row: &tokio_postgres::Row| User { id: Some(row.get(0)), name: row.get(1), email: row.get(2), account_type : row.get(3), password: None })
 			.collect::<Vec<User>>();
 
 		return Ok(users);
 	}
 
-	pub async fn add_user(&self, user: &User) -> Result<(), String>
+	async fn add_user(&self, user: &User, password_hash: Option<String>) -> Result<(), ApiError>
 	{
-		self.connector.storage
+		self.connector.client().await?
 			.execute(
-				"INSERT INTO users (name, email, atype) VALUES ($1, $2, $3)",
-				&[&user.name, &user.email, &user.account_type]
-			).await
-			.map_err(|e: tokio_postgres::Error| e.to_string())?;
+				"INSERT INTO users (name, email, atype, password_hash) VALUES ($1, $2, $3, $4)",
+				&[&user.name, &user.email, &user.account_type, &password_hash]
+			).await?;
 
 		return Ok(());
 	}
 
-	pub async fn edit_user(&self, id: i32, user: &User) -> Result<(), String>
+	async fn edit_user(&self, id: i32, user: &User) -> Result<(), ApiError>
 	{
-		self.connector.storage.execute(
+		let updated_rows: u64 = self.connector.client().await?.execute(
 		"UPDATE users SET name = $1, email = $2 WHERE id = $3",
 		&[&user.name, &user.email, &id]
-		).await
-		.map_err(|e: tokio_postgres::Error| e.to_string())?;
+		).await?;
+
+		if updated_rows == 0
+		{
+			return Err(ApiError::NotFound(format!("User [{}] not found", id)));
+		}
 		
 		return Ok(());
 	}
 
-	pub async fn delet_user(&self, id: i32) -> Result<(), String>
+	async fn delet_user(&self, id: i32) -> Result<(), ApiError>
 	{
-		self.connector.storage
-			.execute("DELETE FROM users WHERE id = $1", &[&id]).await
-			.map_err(|e|  e.to_string())?;
+		let deleted_rows: u64 = self.connector.client().await?
+			.execute("DELETE FROM users WHERE id = $1", &[&id]).await?;
+
+		if deleted_rows == 0
+		{
+			return Err(ApiError::NotFound(format!("User [{}] not found", id)));
+		}
 
 		return Ok(());
 	}
 
+	async fn set_password_hash(&self, id: i32, password_hash: &str) -> Result<(), ApiError>
+	{
+		self.connector.client().await?
+			.execute("UPDATE users SET password_hash = $1 WHERE id = $2", &[&password_hash, &id]).await?;
+
+		return Ok(());
+	}
+
+	async fn select_user(&self, id: i32) -> Result<User, ApiError>
+	{
+		let row_option: Option<tokio_postgres::Row> = self.connector.client().await?
+			.query_opt("SELECT id, name, email, atype FROM users WHERE id = $1", &[&id]).await?;
+
+		match row_option
+		{
+			Some(row) => return Ok(User { id: Some(row.get(0)), name: row.get(1), email: row.get(2), account_type: row.get(3), password: None }),
+			None => return Err(ApiError::NotFound(format!("User [{}] not found", id))),
+		}
+	}
+
+	async fn select_user_credentials(&self, email: &str) -> Result<Option<(User, Option<String>)>, ApiError>
+	{
+		let row_option: Option<tokio_postgres::Row> = self.connector.client().await?
+			.query_opt("SELECT id, name, email, atype, password_hash FROM users WHERE email = $1", &[&email]).await?;
+
+		let credentials = row_option.map(|row: tokio_postgres::Row|
+		{
+			let user: User = User { id: Some(row.get(0)), name: row.get(1), email: row.get(2), account_type: row.get(3), password: None };
+			(user, row.get(4))
+		});
+
+		return Ok(credentials);
+	}
 }
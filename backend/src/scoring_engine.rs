@@ -0,0 +1,290 @@
+use rocket::serde::{ Deserialize, Serialize };
+
+use crate::gameshow_manager::{ Contestant, ContestantEvent, ContestantEventType, ContestantRoundStats, LeaguePick, Round, RoundStatus, ScoringRules };
+use crate::user_manager::User;
+
+use std::collections::{ BTreeSet, HashMap };
+
+// A player's picks for a round are a ranked list of the cast.
+// rank_pick 1 is the contestant they trust most to survive, and the
+// highest rank_pick is the contestant they expect to be voted out.
+// How many points each outcome is worth comes from the league's ScoringRules.
+// Every picked contestant also earns the points for their stats that round
+// (immunity and reward wins, idols, votes), which leagues may leave at 0.
+// Who was in the game on a round comes from the event log, not from where the
+// contestant stands now, so someone who returns later still counts as out on
+// the rounds they missed and their original boot still pays off.
+
+pub const NOT_ELIMINATED : i32 = -1;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RoundScore
+{
+	pub round_number: i32,
+	pub points: i32,
+	pub cumulative_points: i32
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct UserScore
+{
+	pub user_id: i32,
+	pub rounds: Vec<RoundScore>,
+	pub total_points: i32
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Standing
+{
+	pub user_id: i32,
+	pub user_name: String,
+	pub rank: i32,
+	pub previous_rank: Option<i32>,
+	pub movement: i32,
+	pub total_points: i32,
+	pub rounds: Vec<RoundScore>
+}
+
+// One spell out of the game: from the round they left on until the round they came back
+// in, if they did.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct GameExit
+{
+	pub contestant_id: i32,
+	pub round_number: i32,
+	pub was_medically_evacuated: bool,
+	pub returned_in_round: Option<i32>
+}
+
+impl GameExit
+{
+	fn covers(&self, round_number: i32) -> bool
+	{
+		return self.round_number <= round_number && self.returned_in_round.is_none_or(|returned: i32| round_number < returned);
+	}
+}
+
+// Replays a season's event log into every contestant's spells out of the game. Reversed
+// events don't count.
+pub fn game_exits(events: &[ContestantEvent]) -> Vec<GameExit>
+{
+	let mut ordered_events: Vec<&ContestantEvent> = ContestantEvent::still_standing(events);
+	ordered_events.sort_by_key(|event: &&ContestantEvent| (event.round_number, event.id));
+
+	let mut exits: Vec<GameExit> = Vec::new();
+	for event in ordered_events
+	{
+		if event.event_type.is_exit()
+		{
+			exits.push(GameExit { contestant_id: event.contestant_id, round_number: event.round_number, was_medically_evacuated: event.event_type == ContestantEventType::Medevac, returned_in_round: None });
+		}
+		else if event.event_type == ContestantEventType::Returned
+			&& let Some(open_exit) = exits.iter_mut().rev().find(|exit: &&mut GameExit| exit.contestant_id == event.contestant_id && exit.returned_in_round.is_none())
+		{
+			open_exit.returned_in_round = Some(event.round_number);
+		}
+	}
+
+	return exits;
+}
+
+// Rounds are numbered from 1. A round counts as played once the gamemaster marks it scored, or
+// as soon as somebody left the game on it or has stats recorded for it, so rounds without a boot
+// still pay out their stats.
+pub fn latest_played_round(rounds: &[Round], exits: &[GameExit], round_stats: &[ContestantRoundStats]) -> i32
+{
+	let scored_rounds = rounds.iter()
+		.filter(|round: &&Round| round.status == RoundStatus::Scored)
+		.map(|round: &Round| round.round_number);
+
+	return scored_rounds
+		.chain(exits.iter().map(|exit: &GameExit| exit.round_number))
+		.chain(round_stats.iter().map(|stats: &ContestantRoundStats| stats.round_number))
+		.max()
+		.unwrap_or(NOT_ELIMINATED)
+		.max(0);
+}
+
+// Round stats keyed by (contestant_id, round_number).
+type StatsByRound<'a> = HashMap<(i32, i32), &'a ContestantRoundStats>;
+
+// Everything about the season a league is scored on: the cast, their spells out of the game
+// and their round stats.
+pub struct SeasonRecord<'a>
+{
+	pub contestants: &'a [Contestant],
+	pub exits: &'a [GameExit],
+	pub round_stats: &'a [ContestantRoundStats]
+}
+
+pub fn score_league(rules: &ScoringRules, member_ids: &[i32], picks: &[LeaguePick], season: &SeasonRecord, last_round: i32) -> Vec<UserScore>
+{
+	let exits_by_contestant: HashMap<i32, Vec<&GameExit>> = season.contestants.iter()
+		.filter_map(|contestant: &Contestant| contestant.id)
+		.map(|contestant_id: i32| (contestant_id, season.exits.iter().filter(|exit: &&GameExit| exit.contestant_id == contestant_id).collect()))
+		.collect();
+
+	let stats_by_round: StatsByRound = season.round_stats.iter()
+		.map(|stats: &ContestantRoundStats| ((stats.contestant_id, stats.round_number), stats))
+		.collect();
+
+	let user_ids: BTreeSet<i32> = member_ids.iter().copied()
+		.chain(picks.iter().map(|pick: &LeaguePick| pick.user_id))
+		.collect();
+
+	return user_ids.into_iter()
+		.map(|user_id: i32| score_user(rules, user_id, picks, &exits_by_contestant, &stats_by_round, last_round))
+		.collect();
+}
+
+// Each cast member's spells out of the game, keyed by contestant id.
+type ExitsByContestant<'a> = HashMap<i32, Vec<&'a GameExit>>;
+
+fn score_user(rules: &ScoringRules, user_id: i32, picks: &[LeaguePick], exits_by_contestant: &ExitsByContestant, stats_by_round: &StatsByRound, last_round: i32) -> UserScore
+{
+	let mut rounds: Vec<RoundScore> = Vec::new();
+	let mut cumulative_points: i32 = 0;
+
+	for round_number in 1..=last_round
+	{
+		let round_picks: Vec<&LeaguePick> = picks.iter()
+			.filter(|pick: &&LeaguePick| pick.user_id == user_id && pick.round_number == round_number)
+			.collect();
+
+		let points: i32 = score_round(rules, &round_picks, exits_by_contestant, stats_by_round, round_number);
+		cumulative_points += points;
+
+		rounds.push(RoundScore { round_number, points, cumulative_points });
+	}
+
+	return UserScore { user_id, rounds, total_points: cumulative_points };
+}
+
+fn score_round(rules: &ScoringRules, round_picks: &[&LeaguePick], exits_by_contestant: &ExitsByContestant, stats_by_round: &StatsByRound, round_number: i32) -> i32
+{
+	let mut points: i32 = 0;
+
+	for pick in round_picks.iter()
+	{
+		if let Some(stats) = stats_by_round.get(&(pick.contestant_id, round_number))
+		{
+			points += stat_points(rules, stats);
+		}
+
+		let Some(exits) = exits_by_contestant.get(&pick.contestant_id) else { continue; };
+
+		if survived_round(exits, round_number)
+		{
+			points += rules.survival_points;
+		}
+		else if left_on_round(exits, round_number).is_some_and(|exit: &GameExit| exit.was_medically_evacuated)
+		{
+			points += rules.medevac_points;
+		}
+	}
+
+	let predicted_boot: Option<&GameExit> = round_picks.iter()
+		.max_by_key(|pick: &&&LeaguePick| pick.rank_pick)
+		.and_then(|pick: &&LeaguePick| exits_by_contestant.get(&pick.contestant_id))
+		.and_then(|exits: &Vec<&GameExit>| left_on_round(exits, round_number));
+
+	if predicted_boot.is_some_and(|exit: &GameExit| rules.medevac_counts_as_boot || !exit.was_medically_evacuated)
+	{
+		points += rules.boot_prediction_bonus;
+	}
+
+	return points;
+}
+
+fn stat_points(rules: &ScoringRules, stats: &ContestantRoundStats) -> i32
+{
+	let mut points: i32 = stats.idols_found * rules.idol_found_points
+		+ stats.idols_played * rules.idol_played_points
+		+ stats.votes_received * rules.vote_received_points;
+
+	if stats.won_individual_immunity
+	{
+		points += rules.immunity_win_points;
+	}
+
+	if stats.won_reward
+	{
+		points += rules.reward_win_points;
+	}
+
+	if stats.cast_correct_vote
+	{
+		points += rules.correct_vote_points;
+	}
+
+	return points;
+}
+
+fn survived_round(exits: &[&GameExit], round_number: i32) -> bool
+{
+	return !exits.iter().any(|exit: &&GameExit| exit.covers(round_number));
+}
+
+fn left_on_round<'a>(exits: &[&'a GameExit], round_number: i32) -> Option<&'a GameExit>
+{
+	return exits.iter().copied().find(|exit: &&GameExit| exit.round_number == round_number);
+}
+
+// Orders players by total points. Tied players share a rank, and movement is
+// how many places a player climbed (positive) or fell (negative) in the latest round.
+pub fn build_standings(scores: &[UserScore], members: &[User]) -> Vec<Standing>
+{
+	let current_totals: Vec<(i32, i32)> = scores.iter()
+		.map(|score: &UserScore| (score.user_id, score.total_points))
+		.collect();
+
+	let round_count: usize = scores.iter().map(|score: &UserScore| score.rounds.len()).max().unwrap_or(0);
+	let mut previous_ranks: Option<HashMap<i32, i32>> = None;
+	if round_count >= 2
+	{
+		let previous_totals: Vec<(i32, i32)> = scores.iter()
+			.map(|score: &UserScore| (score.user_id, score.rounds.get(round_count - 2).map_or(0, |round: &RoundScore| round.cumulative_points)))
+			.collect();
+
+		previous_ranks = Some(rank_totals(&previous_totals));
+	}
+
+	let current_ranks: HashMap<i32, i32> = rank_totals(&current_totals);
+
+	let mut standings: Vec<Standing> = scores.iter()
+		.map(|score: &UserScore|
+		{
+			let rank: i32 = current_ranks[&score.user_id];
+			let previous_rank: Option<i32> = previous_ranks.as_ref().and_then(|ranks: &HashMap<i32, i32>| ranks.get(&score.user_id).copied());
+			let user_name: String = members.iter()
+				.find(|member: &&User| member.id == Some(score.user_id))
+				.map_or(format!("User {}", score.user_id), |member: &User| member.name.clone());
+
+			Standing
+			{
+				user_id: score.user_id,
+				user_name,
+				rank,
+				previous_rank,
+				movement: previous_rank.map_or(0, |previous: i32| previous - rank),
+				total_points: score.total_points,
+				rounds: score.rounds.clone()
+			}
+		})
+		.collect();
+
+	standings.sort_by_key(|standing: &Standing| (standing.rank, standing.user_id));
+
+	return standings;
+}
+
+fn rank_totals(totals: &[(i32, i32)]) -> HashMap<i32, i32>
+{
+	return totals.iter()
+		.map(|(user_id, points)|
+		{
+			let players_ahead: usize = totals.iter().filter(|(_, other_points)| other_points > points).count();
+			(*user_id, players_ahead as i32 + 1)
+		})
+		.collect();
+}
@@ -0,0 +1,453 @@
+use chrono::{ DateTime, Utc };
+use rocket::serde::{ Deserialize, Serialize };
+
+use crate::gameshow_manager::{ self, Contestant, ContestantEvent, ContestantEventType, ContestantRoundStats, GameShow, League, LeaguePick, Round, ScoringRules, SeasonStatus, Tribe, TribeAssignment };
+use crate::gameshows_accessor::GameShowRepository;
+use crate::league_accessor::LeagueRepository;
+use crate::memberships_accessor::UserRepository;
+use crate::scoring_engine::NOT_ELIMINATED;
+use crate::user_manager::User;
+use crate::utilities::errors::ApiError;
+use crate::utilities::storage::StorageConnector;
+use crate::utilities::validation::required;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+// Bump this whenever the shape of SeasonArchive changes in a way older archives can't be read
+// with, and keep restore_season able to read the versions before it. New fields that default
+// to empty don't need a bump, unless their default says something the archive never meant.
+//  1: seasons had no status, so it reads as draft. See upgraded().
+//  2: the season's status and dates.
+pub const SEASON_ARCHIVE_VERSION : u32 = 2;
+
+// Everything about one season, as a single JSON document. Ids are the ones from the database it
+// was exported from; restoring gives every row a new id and rewrites the references to match.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SeasonArchive
+{
+	pub version: u32,
+	pub exported_at: DateTime<Utc>,
+	pub season: GameShow,
+	pub rounds: Vec<Round>,
+	// The season's cast, with how far each contestant got.
+	pub cast: Vec<Contestant>,
+	// Older archives only have the elimination round and medevac flag on the cast.
+	#[serde(default)]
+	pub events: Vec<ContestantEvent>,
+	// Older archives only have each contestant's tribe name on the cast.
+	#[serde(default)]
+	pub tribes: Vec<Tribe>,
+	#[serde(default)]
+	pub tribe_assignments: Vec<TribeAssignment>,
+	#[serde(default)]
+	pub round_stats: Vec<ContestantRoundStats>,
+	pub leagues: Vec<ArchivedLeague>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ArchivedLeague
+{
+	pub league: League,
+	// None when the league played with the default rules.
+	pub scoring_rules: Option<ScoringRules>,
+	// Passwords are never exported.
+	pub members: Vec<User>,
+	// Players who left the league but still have picks in it.
+	#[serde(default)]
+	pub former_members: Vec<User>,
+	pub picks: Vec<LeaguePick>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RestoredSeason
+{
+	pub game_show_id: i32,
+	pub rounds: usize,
+	pub contestants: usize,
+	pub tribes: usize,
+	pub leagues: usize,
+	pub members: usize,
+	pub picks: usize,
+}
+
+pub struct SeasonArchiveManager
+{
+	pub game_show_repository: Arc<dyn GameShowRepository>,
+	pub league_repository: Arc<dyn LeagueRepository>,
+	pub user_repository: Arc<dyn UserRepository>,
+	pub storage: Arc<StorageConnector>,
+}
+
+impl SeasonArchiveManager
+{
+	pub fn create(	game_show_repository_in : Arc<dyn GameShowRepository>,
+						league_repository_in : Arc<dyn LeagueRepository>,
+						user_repository_in : Arc<dyn UserRepository>,
+						storage_in : Arc<StorageConnector>) -> Self
+	{
+		return SeasonArchiveManager
+		{
+			game_show_repository : game_show_repository_in,
+			league_repository : league_repository_in,
+			user_repository : user_repository_in,
+			storage : storage_in
+		};
+	}
+
+	pub async fn export_season(&self, game_show_id: i32) -> Result<SeasonArchive, ApiError>
+	{
+		let season: GameShow = self.game_show_repository.select_game_show(game_show_id).await?;
+		let rounds: Vec<Round> = self.game_show_repository.collect_rounds(game_show_id).await?;
+		let cast: Vec<Contestant> = self.game_show_repository.fetch_contestants_on_show(game_show_id).await?;
+		let events: Vec<ContestantEvent> = self.game_show_repository.collect_contestant_events(game_show_id).await?;
+		let tribes: Vec<Tribe> = self.game_show_repository.collect_tribes(game_show_id).await?;
+		let tribe_assignments: Vec<TribeAssignment> = self.game_show_repository.collect_tribe_assignments(game_show_id).await?;
+		let round_stats: Vec<ContestantRoundStats> = self.game_show_repository.collect_round_stats(game_show_id).await?;
+
+		let mut leagues: Vec<ArchivedLeague> = Vec::new();
+		for league in self.league_repository.collect_leagues(game_show_id).await?
+		{
+			let league_id: i32 = required(league.id, "league.id")?;
+			let members: Vec<User> = self.league_repository.collect_league_members(league_id).await?;
+			let picks: Vec<LeaguePick> = self.league_repository.collect_league_picks(league_id).await?;
+
+			let mut former_members: Vec<User> = Vec::new();
+			for pick in picks.iter()
+			{
+				let is_known: bool = members.iter().chain(former_members.iter()).any(|user: &User| user.id == Some(pick.user_id));
+				if !is_known
+				{
+					former_members.push(self.user_repository.select_user(pick.user_id).await?);
+				}
+			}
+
+			leagues.push(ArchivedLeague
+			{
+				scoring_rules: self.league_repository.select_scoring_rules(league_id).await?,
+				members,
+				former_members,
+				picks,
+				league
+			});
+		}
+
+		return Ok(SeasonArchive { version: SEASON_ARCHIVE_VERSION, exported_at: Utc::now(), season, rounds, cast, events, tribes, tribe_assignments, round_stats, leagues });
+	}
+
+	// Restores the archive as a new season, all or nothing. Contestants are matched by name and
+	// disambiguator and users by email, so restoring next to existing data reuses them instead of
+	// duplicating them. Users that don't exist yet come back without a password.
+	pub async fn restore_season(&self, archive: &SeasonArchive) -> Result<RestoredSeason, ApiError>
+	{
+		if archive.version < 1 || archive.version > SEASON_ARCHIVE_VERSION
+		{
+			return Err(ApiError::Validation(format!("Season archive version {} is not supported, expected 1 to {}", archive.version, SEASON_ARCHIVE_VERSION)));
+		}
+
+		let archive: &SeasonArchive = &Self::upgraded(archive);
+		return self.storage.transaction(async
+		{
+			let game_show_id: i32 = self.game_show_repository.add_gameshow(&archive.season).await?;
+
+			for round in archive.rounds.iter()
+			{
+				self.game_show_repository.create_round(game_show_id, round).await?;
+			}
+
+			let mut contestant_ids: HashMap<i32, i32> = HashMap::new();
+			for contestant in archive.cast.iter()
+			{
+				let archived_id: i32 = required(contestant.id, "cast.id")?;
+				let contestant_id: i32 = self.restore_contestant(game_show_id, contestant, archive.events.is_empty()).await?;
+				contestant_ids.insert(archived_id, contestant_id);
+
+				if archive.tribes.is_empty() && let Some(tribe_name) = contestant.tribe.as_deref()
+				{
+					gameshow_manager::assign_starting_tribe(self.game_show_repository.as_ref(), game_show_id, contestant_id, tribe_name).await?;
+				}
+			}
+
+			// Reversals come after the events they cancel, so those are always restored first.
+			let mut event_ids: HashMap<i32, i32> = HashMap::new();
+			for event in archive.events.iter()
+			{
+				let reverses_event_id: Option<i32> = match event.reverses_event_id
+				{
+					Some(archived_id) => Some(Self::remapped(&event_ids, archived_id, "event")?),
+					None => None,
+				};
+
+				let restored_event: ContestantEvent = ContestantEvent
+				{
+					id: None,
+					contestant_id: Self::remapped(&contestant_ids, event.contestant_id, "contestant")?,
+					reverses_event_id,
+					..event.clone()
+				};
+
+				let event_id: i32 = self.game_show_repository.record_contestant_event(game_show_id, &restored_event).await?;
+				if let Some(archived_id) = event.id
+				{
+					event_ids.insert(archived_id, event_id);
+				}
+			}
+
+			let mut tribe_ids: HashMap<i32, i32> = HashMap::new();
+			for tribe in archive.tribes.iter()
+			{
+				tribe.validate()?;
+				let archived_id: i32 = required(tribe.id, "tribes.id")?;
+				tribe_ids.insert(archived_id, self.game_show_repository.create_tribe(game_show_id, tribe).await?);
+			}
+
+			for assignment in archive.tribe_assignments.iter()
+			{
+				let restored_assignment: TribeAssignment = TribeAssignment
+				{
+					contestant_id: Self::remapped(&contestant_ids, assignment.contestant_id, "contestant")?,
+					tribe_id: Self::remapped(&tribe_ids, assignment.tribe_id, "tribe")?,
+					from_round: assignment.from_round
+				};
+
+				self.game_show_repository.assign_contestant_to_tribe(game_show_id, &restored_assignment).await?;
+			}
+
+			for stats in archive.round_stats.iter()
+			{
+				let restored_stats: ContestantRoundStats = ContestantRoundStats
+				{
+					contestant_id: Self::remapped(&contestant_ids, stats.contestant_id, "contestant")?,
+					..stats.clone()
+				};
+
+				restored_stats.validate()?;
+				self.game_show_repository.record_round_stats(game_show_id, &restored_stats).await?;
+			}
+
+			let mut user_ids: HashMap<i32, i32> = HashMap::new();
+			let mut member_count: usize = 0;
+			let mut pick_count: usize = 0;
+			for archived_league in archive.leagues.iter()
+			{
+				let league: League = League { id: None, name: archived_league.league.name.clone(), id_showseason: Some(game_show_id) };
+				let league_id: i32 = self.league_repository.create_league(&league).await?;
+
+				if let Some(rules) = &archived_league.scoring_rules
+				{
+					self.league_repository.create_scoring_rules(league_id, rules).await?;
+				}
+
+				for member in archived_league.members.iter()
+				{
+					let archived_id: i32 = required(member.id, "members.id")?;
+					let user_id: i32 = self.restore_user(member).await?;
+					user_ids.insert(archived_id, user_id);
+
+					self.league_repository.add_user_to_league(user_id, league_id).await?;
+					member_count += 1;
+				}
+
+				for former_member in archived_league.former_members.iter()
+				{
+					let archived_id: i32 = required(former_member.id, "former_members.id")?;
+					user_ids.insert(archived_id, self.restore_user(former_member).await?);
+				}
+
+				for pick in archived_league.picks.iter()
+				{
+					let restored_pick: LeaguePick = LeaguePick
+					{
+						league_id,
+						user_id: Self::remapped(&user_ids, pick.user_id, "user")?,
+						contestant_id: Self::remapped(&contestant_ids, pick.contestant_id, "contestant")?,
+						..pick.clone()
+					};
+
+					self.league_repository.restore_league_pick(&restored_pick).await?;
+					pick_count += 1;
+				}
+			}
+
+			return Ok(RestoredSeason
+			{
+				game_show_id,
+				rounds: archive.rounds.len(),
+				contestants: contestant_ids.len(),
+				tribes: archive.tribes.len(),
+				leagues: archive.leagues.len(),
+				members: member_count,
+				picks: pick_count
+			});
+		}).await;
+	}
+
+	// Brings an older archive up to the current version. Version 1 seasons all read as draft, but
+	// eliminations and picks only happen live, so a season with any of them comes back finished
+	// (it can be reopened from there) and one without stays a draft.
+	fn upgraded(archive: &SeasonArchive) -> SeasonArchive
+	{
+		let mut upgraded: SeasonArchive = archive.clone();
+		if archive.version == 1
+		{
+			let has_results: bool = !archive.events.is_empty()
+				|| !archive.round_stats.is_empty()
+				|| archive.cast.iter().any(|contestant: &Contestant| contestant.round_number != NOT_ELIMINATED)
+				|| archive.leagues.iter().any(|league: &ArchivedLeague| !league.picks.is_empty());
+
+			upgraded.season.status = if has_results { SeasonStatus::Finished } else { SeasonStatus::Draft };
+		}
+
+		upgraded.version = SEASON_ARCHIVE_VERSION;
+		return upgraded;
+	}
+
+	// Without an event log, the contestant's elimination is rebuilt from the cast entry.
+	async fn restore_contestant(&self, game_show_id: i32, contestant: &Contestant, from_cast_entry: bool) -> Result<i32, ApiError>
+	{
+		let existing: Contestant = match gameshow_manager::find_contestant_by_name(self.game_show_repository.as_ref(), &contestant.name, Some(&contestant.disambiguator)).await
+		{
+			Ok(existing) => existing,
+			Err(ApiError::NotFound(_)) => self.game_show_repository.create_contestant(contestant).await?,
+			Err(e) => return Err(e),
+		};
+
+		let contestant_id: i32 = required(existing.id, "contestant_id")?;
+		let nickname: String = contestant.nickname.clone().unwrap_or_default();
+		self.game_show_repository.enter_contestant_onto_show(contestant_id, game_show_id, nickname).await?;
+
+		if from_cast_entry && contestant.round_number != NOT_ELIMINATED
+		{
+			let event_type: ContestantEventType = if contestant.was_medically_evacuated { ContestantEventType::Medevac } else { ContestantEventType::VotedOut };
+			let event: ContestantEvent = ContestantEvent { id: None, contestant_id, round_number: contestant.round_number, event_type, recorded_at: None, reverses_event_id: None };
+			self.game_show_repository.record_contestant_event(game_show_id, &event).await?;
+		}
+
+		return Ok(contestant_id);
+	}
+
+	async fn restore_user(&self, member: &User) -> Result<i32, ApiError>
+	{
+		if let Some((existing, _)) = self.user_repository.select_user_credentials(&member.email).await?
+		{
+			return required(existing.id, "user.id");
+		}
+
+		self.user_repository.add_user(member, None).await?;
+
+		let created: Option<(User, Option<String>)> = self.user_repository.select_user_credentials(&member.email).await?;
+		return required(created.and_then(|(user, _)| user.id), "user.id");
+	}
+
+	fn remapped(ids: &HashMap<i32, i32>, archived_id: i32, kind: &str) -> Result<i32, ApiError>
+	{
+		return ids.get(&archived_id).copied()
+			.ok_or(ApiError::Validation(format!("The archive refers to {} [{}], which is not in it", kind, archived_id)));
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+	use crate::gameshow_manager::{ ContestantProfile, ContestantStatus, RoundStatus };
+	use crate::in_memory_accessor::{ InMemoryGameShowRepository, InMemoryLeagueRepository, InMemoryStore, InMemoryUserRepository };
+
+	fn manager() -> SeasonArchiveManager
+	{
+		let store: InMemoryStore = InMemoryStore::new();
+		return SeasonArchiveManager::create(
+			Arc::new(InMemoryGameShowRepository::new(store.clone())),
+			Arc::new(InMemoryLeagueRepository::new(store.clone())),
+			Arc::new(InMemoryUserRepository::new(store.clone())),
+			Arc::new(StorageConnector::in_memory(store))
+		);
+	}
+
+	fn archive_with_pick_for(contestant_id: i32) -> SeasonArchive
+	{
+		let member: User = User { id: Some(7), name: "Sandra".to_string(), email: "sandra@example.com".to_string(), account_type: "Player".to_string(), password: None };
+		let rob: Contestant = Contestant { id: Some(3), name: "Rob".to_string(), disambiguator: String::new(), id_showseason: Some(4), nickname: None, round_number: NOT_ELIMINATED, was_medically_evacuated: false, status: ContestantStatus::Active, tribe: None, tribe_color: None, profile: ContestantProfile::default(), photo: None };
+
+		return SeasonArchive
+		{
+			version: SEASON_ARCHIVE_VERSION,
+			exported_at: Utc::now(),
+			season: GameShow { id: Some(4), name: "Heroes vs Villains".to_string(), status: SeasonStatus::Live, start_date: None, end_date: None },
+			rounds: vec![Round { id: Some(11), game_show_id: Some(4), round_number: 1, title: "Episode 1".to_string(), air_date: None, lock_time: None, status: RoundStatus::Scored }],
+			cast: vec![rob],
+			events: Vec::new(),
+			tribes: vec![Tribe { id: Some(8), game_show_id: Some(4), name: "Villains".to_string(), color: "#AA0000".to_string() }],
+			tribe_assignments: vec![TribeAssignment { contestant_id: 3, tribe_id: 8, from_round: 1 }],
+			round_stats: vec![ContestantRoundStats { contestant_id: 3, round_number: 1, won_individual_immunity: true, ..ContestantRoundStats::default() }],
+			leagues: vec![ArchivedLeague
+			{
+				league: League { id: Some(5), name: "Office pool".to_string(), id_showseason: Some(4) },
+				scoring_rules: None,
+				members: vec![member],
+				former_members: Vec::new(),
+				picks: vec![LeaguePick { league_id: 5, user_id: 7, round_number: 1, contestant_id, rank_pick: 1 }]
+			}]
+		};
+	}
+
+	#[tokio::test]
+	async fn restoring_remaps_ids_and_ignores_round_locks()
+	{
+		let manager: SeasonArchiveManager = manager();
+
+		let restored: RestoredSeason = manager.restore_season(&archive_with_pick_for(3)).await.unwrap();
+		assert_eq!((restored.game_show_id, restored.picks), (1, 1));
+
+		let picks: Vec<LeaguePick> = manager.league_repository.collect_league_picks(1).await.unwrap();
+		assert_eq!((picks[0].league_id, picks[0].user_id, picks[0].contestant_id), (1, 1, 1));
+
+		let cast: Vec<Contestant> = manager.game_show_repository.fetch_contestants_on_show(1).await.unwrap();
+		assert_eq!((cast[0].tribe.as_deref(), cast[0].tribe_color.as_deref()), (Some("Villains"), Some("#AA0000")));
+		let round_stats: Vec<ContestantRoundStats> = manager.game_show_repository.collect_round_stats(1).await.unwrap();
+		assert_eq!((round_stats.len(), round_stats[0].contestant_id, round_stats[0].won_individual_immunity), (1, 1, true));
+		assert!(manager.user_repository.select_user_credentials("sandra@example.com").await.unwrap().is_some_and(|(_, password_hash)| password_hash.is_none()));
+	}
+
+	#[tokio::test]
+	async fn a_bad_archive_restores_nothing()
+	{
+		let manager: SeasonArchiveManager = manager();
+
+		assert!(matches!(manager.restore_season(&archive_with_pick_for(99)).await, Err(ApiError::Validation(_))));
+		assert!(manager.game_show_repository.collect_game_shows().await.unwrap().is_empty());
+		assert!(manager.game_show_repository.collect_all_contestants().await.unwrap().is_empty());
+		assert!(manager.user_repository.collect_users().await.unwrap().is_empty());
+	}
+
+	#[tokio::test]
+	async fn version_one_archives_come_back_finished_once_anything_happened()
+	{
+		let manager: SeasonArchiveManager = manager();
+
+		// What a version 1 export looked like: no season status or dates, tribes, events or stats.
+		let mut old_archive: serde_json::Value = serde_json::to_value(archive_with_pick_for(3)).unwrap();
+		old_archive["version"] = serde_json::json!(1);
+		for key in ["events", "tribes", "tribe_assignments", "round_stats"]
+		{
+			old_archive.as_object_mut().unwrap().remove(key);
+		}
+		for key in ["status", "start_date", "end_date"]
+		{
+			old_archive["season"].as_object_mut().unwrap().remove(key);
+		}
+
+		let with_picks: SeasonArchive = serde_json::from_value(old_archive.clone()).unwrap();
+		manager.restore_season(&with_picks).await.unwrap();
+		assert_eq!(manager.game_show_repository.select_game_show(1).await.unwrap().status, SeasonStatus::Finished);
+
+		old_archive["leagues"][0]["picks"] = serde_json::json!([]);
+		let untouched: SeasonArchive = serde_json::from_value(old_archive).unwrap();
+		manager.restore_season(&untouched).await.unwrap();
+		assert_eq!(manager.game_show_repository.select_game_show(2).await.unwrap().status, SeasonStatus::Draft);
+
+		// Current archives keep the status they were exported with.
+		manager.restore_season(&archive_with_pick_for(3)).await.unwrap();
+		assert_eq!(manager.game_show_repository.select_game_show(3).await.unwrap().status, SeasonStatus::Live);
+	}
+}
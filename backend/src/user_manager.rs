@@ -1,13 +1,15 @@
 
 use rocket::serde::{ Deserialize, Serialize };
 
+use crate::authentication::{ self, LoginRequest };
 use crate::memberships_accessor;
+use crate::utilities::errors::ApiError;
 
 use std::sync::Arc;
 
 pub struct UserManager
 {
-	pub repo: Arc<memberships_accessor::UserRepository>,
+	pub repo: Arc<dyn memberships_accessor::UserRepository>,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -17,11 +19,14 @@ pub struct User
 	pub name: String,
 	pub email: String,
 	pub account_type: String,
+	// Only ever read from requests; the stored hash never leaves the backend.
+	#[serde(default, skip_serializing)]
+	pub password: Option<String>,
 }
 
 impl UserManager
 {
-	pub async fn create(repository : Arc<memberships_accessor::UserRepository>) -> Self
+	pub fn create(repository : Arc<dyn memberships_accessor::UserRepository>) -> Self
 	{
 		let user_repository: UserManager = UserManager
 		{
@@ -31,42 +36,150 @@ impl UserManager
 		return user_repository;
 	}
 
-	pub async fn collect_users(&self) -> Result<Vec<User>, String>
+	pub async fn collect_users(&self) -> Result<Vec<User>, ApiError>
 	{
 		return self.repo.collect_users().await;
 	}
 
-	pub async fn add_user_and_refresh(&self, user: &User) -> Result<Vec<User>, String>
+	pub async fn add_user_and_refresh(&self, user: &User) -> Result<Vec<User>, ApiError>
 	{
-		self.add_user(&user).await?;
+		self.add_user(user).await?;
 		return self.collect_users().await;
 	}
 
-	async fn add_user(&self, user: &User) -> Result<(), String>
+	async fn add_user(&self, user: &User) -> Result<(), ApiError>
 	{
-		return self.repo.add_user(user).await;
+		let password_hash: Option<String> = Self::hash_optional_password(user)?;
+		return self.repo.add_user(user, password_hash).await;
 	}
 
-	pub async fn edit_user_and_refresh(&self, id: i32, user: &User) -> Result<Vec<User>, String>
+	pub async fn edit_user_and_refresh(&self, id: i32, user: &User) -> Result<Vec<User>, ApiError>
 	{
 		self.edit_user(id, user).await?;
 		return self.collect_users().await;
 	}
 
-	async fn edit_user(&self, id: i32, user: &User) -> Result<(), String>
+	async fn edit_user(&self, id: i32, user: &User) -> Result<(), ApiError>
 	{
-		return self.repo.edit_user(id, user).await;
+		self.repo.edit_user(id, user).await?;
+
+		if let Some(password_hash) = Self::hash_optional_password(user)?
+		{
+			self.repo.set_password_hash(id, &password_hash).await?;
+		}
+
+		return Ok(());
 	}
 
-	pub async fn delete_user_and_refresh(&self, id: i32) -> Result<Vec<User>, String>
+	pub async fn delete_user_and_refresh(&self, id: i32) -> Result<Vec<User>, ApiError>
 	{
-		self.delete_user(id).await.map_err(|e| e.to_string())?;
+		self.delete_user(id).await?;
 		return self.collect_users().await;
 	}
 
-	async fn delete_user(&self, id: i32) -> Result<(), String>
+	async fn delete_user(&self, id: i32) -> Result<(), ApiError>
 	{
 		return self.repo.delet_user(id).await;
 	}
 
-}
\ No newline at end of file
+	pub async fn select_user(&self, id: i32) -> Result<User, ApiError>
+	{
+		return self.repo.select_user(id).await;
+	}
+
+	pub async fn login(&self, login_request: &LoginRequest) -> Result<User, ApiError>
+	{
+		let credentials: Option<(User, Option<String>)> = self.repo.select_user_credentials(&login_request.email).await?;
+
+		match credentials
+		{
+			Some((user, Some(password_hash))) if authentication::verify_password(&login_request.password, &password_hash) =>
+			{
+				return Ok(user);
+			}
+
+			_ => return Err(ApiError::Unauthorized("Invalid email or password".to_string())),
+		}
+	}
+
+	fn hash_optional_password(user: &User) -> Result<Option<String>, ApiError>
+	{
+		match user.password.as_deref()
+		{
+			Some(password) if !password.is_empty() => return authentication::hash_password(password).map(Some).map_err(ApiError::Validation),
+			_ => return Ok(None),
+		}
+	}
+
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+	use crate::in_memory_accessor::{ InMemoryStore, InMemoryUserRepository };
+
+	fn manager() -> UserManager
+	{
+		return UserManager::create(Arc::new(InMemoryUserRepository::new(InMemoryStore::new())));
+	}
+
+	fn user(email: &str, password: Option<&str>) -> User
+	{
+		return User { id: None, name: "Sandra".to_string(), email: email.to_string(), account_type: "Player".to_string(), password: password.map(str::to_string) };
+	}
+
+	fn login_request(email: &str, password: &str) -> LoginRequest
+	{
+		return LoginRequest { email: email.to_string(), password: password.to_string() };
+	}
+
+	#[tokio::test]
+	async fn login_checks_the_hashed_password()
+	{
+		let manager: UserManager = manager();
+		manager.add_user_and_refresh(&user("sandra@example.com", Some("twice"))).await.unwrap();
+
+		let logged_in: User = manager.login(&login_request("sandra@example.com", "twice")).await.unwrap();
+		assert_eq!(logged_in.id, Some(1));
+		assert!(logged_in.password.is_none());
+
+		assert!(matches!(manager.login(&login_request("sandra@example.com", "once")).await, Err(ApiError::Unauthorized(_))));
+		assert!(matches!(manager.login(&login_request("nobody@example.com", "twice")).await, Err(ApiError::Unauthorized(_))));
+	}
+
+	#[tokio::test]
+	async fn users_without_a_password_cannot_log_in()
+	{
+		let manager: UserManager = manager();
+		manager.add_user_and_refresh(&user("sandra@example.com", None)).await.unwrap();
+
+		assert!(matches!(manager.login(&login_request("sandra@example.com", "")).await, Err(ApiError::Unauthorized(_))));
+	}
+
+	#[tokio::test]
+	async fn editing_a_user_can_change_the_password()
+	{
+		let manager: UserManager = manager();
+		manager.add_user_and_refresh(&user("sandra@example.com", Some("twice"))).await.unwrap();
+
+		let users: Vec<User> = manager.edit_user_and_refresh(1, &user("queen@example.com", Some("thrice"))).await.unwrap();
+		assert_eq!(users[0].email, "queen@example.com");
+		assert!(manager.login(&login_request("queen@example.com", "thrice")).await.is_ok());
+		assert!(manager.login(&login_request("queen@example.com", "twice")).await.is_err());
+
+		// Leaving the password out keeps the current one.
+		manager.edit_user_and_refresh(1, &user("queen@example.com", None)).await.unwrap();
+		assert!(manager.login(&login_request("queen@example.com", "thrice")).await.is_ok());
+	}
+
+	#[tokio::test]
+	async fn missing_users_are_not_found()
+	{
+		let manager: UserManager = manager();
+
+		assert!(matches!(manager.select_user(3).await, Err(ApiError::NotFound(_))));
+		assert!(matches!(manager.edit_user_and_refresh(3, &user("sandra@example.com", None)).await, Err(ApiError::NotFound(_))));
+		assert!(matches!(manager.delete_user_and_refresh(3).await, Err(ApiError::NotFound(_))));
+	}
+}
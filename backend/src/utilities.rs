@@ -1,2 +1,5 @@
-
-pub mod storage;
\ No newline at end of file
+pub mod storage;
+pub mod errors;
+pub mod validation;
+pub mod migrations;
+pub mod photo_store;
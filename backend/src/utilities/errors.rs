@@ -0,0 +1,110 @@
+use rocket::http::Status;
+use rocket::request::Request;
+use rocket::response::{ self, Responder, status::Custom };
+use rocket::serde::{ Deserialize, Serialize, json::Json };
+use tokio_postgres::error::SqlState;
+
+use std::fmt;
+
+// Every error the API sends back has this body, the catchers in authentication included,
+// so the frontend only has to know one shape.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ErrorBody
+{
+	pub error: String,
+	pub message: String,
+}
+
+#[derive(Debug, Clone)]
+pub enum ApiError
+{
+	NotFound(String),
+	Conflict(String),
+	Validation(String),
+	Unauthorized(String),
+	Forbidden(String),
+	Database(String),
+}
+
+impl ApiError
+{
+	pub fn status(&self) -> Status
+	{
+		match self
+		{
+			ApiError::NotFound(_) => return Status::NotFound,
+			ApiError::Conflict(_) => return Status::Conflict,
+			ApiError::Validation(_) => return Status::UnprocessableEntity,
+			ApiError::Unauthorized(_) => return Status::Unauthorized,
+			ApiError::Forbidden(_) => return Status::Forbidden,
+			ApiError::Database(_) => return Status::InternalServerError,
+		}
+	}
+
+	pub fn kind(&self) -> &'static str
+	{
+		match self
+		{
+			ApiError::NotFound(_) => return "not_found",
+			ApiError::Conflict(_) => return "conflict",
+			ApiError::Validation(_) => return "validation",
+			ApiError::Unauthorized(_) => return "unauthorized",
+			ApiError::Forbidden(_) => return "forbidden",
+			ApiError::Database(_) => return "database",
+		}
+	}
+
+	pub fn message(&self) -> &str
+	{
+		match self
+		{
+			ApiError::NotFound(message)
+			| ApiError::Conflict(message)
+			| ApiError::Validation(message)
+			| ApiError::Unauthorized(message)
+			| ApiError::Forbidden(message)
+			| ApiError::Database(message) => return message,
+		}
+	}
+}
+
+impl fmt::Display for ApiError
+{
+	fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result
+	{
+		return write!(formatter, "{}: {}", self.kind(), self.message());
+	}
+}
+
+// Constraint violations are the caller's fault, everything else Postgres complains about is ours.
+impl From<tokio_postgres::Error> for ApiError
+{
+	fn from(error: tokio_postgres::Error) -> Self
+	{
+		let message: String = error.as_db_error()
+			.map_or(error.to_string(), |db_error: &tokio_postgres::error::DbError| db_error.message().to_string());
+
+		match error.code()
+		{
+			Some(code) if *code == SqlState::UNIQUE_VIOLATION => return ApiError::Conflict(message),
+			Some(code) if *code == SqlState::FOREIGN_KEY_VIOLATION => return ApiError::Validation(message),
+			Some(code) if *code == SqlState::CHECK_VIOLATION => return ApiError::Validation(message),
+			Some(code) if *code == SqlState::NOT_NULL_VIOLATION => return ApiError::Validation(message),
+			_ => return ApiError::Database(message),
+		}
+	}
+}
+
+impl<'r> Responder<'r, 'static> for ApiError
+{
+	fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static>
+	{
+		if let ApiError::Database(message) = &self
+		{
+			eprintln!("Database error on {} {}: {}", request.method(), request.uri(), message);
+		}
+
+		let body: ErrorBody = ErrorBody { error: self.kind().to_string(), message: self.message().to_string() };
+		return Custom(self.status(), Json(body)).respond_to(request);
+	}
+}
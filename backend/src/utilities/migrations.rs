@@ -0,0 +1,106 @@
+use crate::utilities::errors::ApiError;
+use crate::utilities::storage::{ StorageClient, StorageConnector };
+
+use std::collections::HashSet;
+
+// Schema changes live in backend/migrations as numbered SQL files and are compiled into the binary.
+// Never edit a migration once it has been merged; add a new one with the next version instead.
+pub struct Migration
+{
+	pub version: i32,
+	pub name: &'static str,
+	pub sql: &'static str,
+}
+
+pub const MIGRATIONS : [Migration; 12] =
+[
+	Migration { version: 1, name: "initial_schema", sql: include_str!("../../migrations/0001_initial_schema.sql") },
+	Migration { version: 2, name: "user_passwords", sql: include_str!("../../migrations/0002_user_passwords.sql") },
+	Migration { version: 3, name: "scoring_rules", sql: include_str!("../../migrations/0003_scoring_rules.sql") },
+	Migration { version: 4, name: "rounds", sql: include_str!("../../migrations/0004_rounds.sql") },
+	Migration { version: 5, name: "cast_details", sql: include_str!("../../migrations/0005_cast_details.sql") },
+	Migration { version: 6, name: "tribes", sql: include_str!("../../migrations/0006_tribes.sql") },
+	Migration { version: 7, name: "contestant_events", sql: include_str!("../../migrations/0007_contestant_events.sql") },
+	Migration { version: 8, name: "round_stats", sql: include_str!("../../migrations/0008_round_stats.sql") },
+	Migration { version: 9, name: "contestant_identity", sql: include_str!("../../migrations/0009_contestant_identity.sql") },
+	Migration { version: 10, name: "contestant_profiles", sql: include_str!("../../migrations/0010_contestant_profiles.sql") },
+	Migration { version: 11, name: "season_status", sql: include_str!("../../migrations/0011_season_status.sql") },
+	Migration { version: 12, name: "reversed_events", sql: include_str!("../../migrations/0012_reversed_events.sql") },
+];
+
+// Held while migrating so two backends starting together don't both apply the same migration.
+const MIGRATION_LOCK_ID : i64 = 727_001;
+
+// Runs on a single pooled connection, the advisory lock and each BEGIN/COMMIT belong to its session.
+pub async fn run_pending_migrations(connector: &StorageConnector) -> Result<Vec<i32>, ApiError>
+{
+	let client: StorageClient = connector.client().await?;
+
+	client
+		.batch_execute(
+			"CREATE TABLE IF NOT EXISTS schema_migrations (
+				version INTEGER PRIMARY KEY,
+				name TEXT NOT NULL,
+				applied_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+			)"
+		).await?;
+
+	client.execute("SELECT pg_advisory_lock($1)", &[&MIGRATION_LOCK_ID]).await?;
+	let applied_result: Result<Vec<i32>, ApiError> = apply_missing_migrations(&client).await;
+	client.execute("SELECT pg_advisory_unlock($1)", &[&MIGRATION_LOCK_ID]).await?;
+
+	return applied_result;
+}
+
+async fn apply_missing_migrations(client: &StorageClient) -> Result<Vec<i32>, ApiError>
+{
+	let applied_versions: HashSet<i32> = client
+		.query("SELECT version FROM schema_migrations", &[]).await?
+		.iter()
+		.map(|row: &tokio_postgres::Row| row.get(0))
+		.collect::<HashSet<i32>>();
+
+	let mut newly_applied: Vec<i32> = Vec::new();
+
+	for migration in MIGRATIONS.iter().filter(|migration: &&Migration| !applied_versions.contains(&migration.version))
+	{
+		println!("Applying migration {:04}_{}", migration.version, migration.name);
+		apply_migration(client, migration).await?;
+		newly_applied.push(migration.version);
+	}
+
+	return Ok(newly_applied);
+}
+
+// Each migration and its schema_migrations row go in together or not at all.
+async fn apply_migration(client: &StorageClient, migration: &Migration) -> Result<(), ApiError>
+{
+	client.batch_execute("BEGIN").await?;
+
+	let migration_result: Result<(), ApiError> = async
+	{
+		client.batch_execute(migration.sql).await?;
+		client
+			.execute(
+				"INSERT INTO schema_migrations (version, name) VALUES ($1, $2)",
+				&[&migration.version, &migration.name]
+			).await?;
+
+		return Ok(());
+	}.await;
+
+	match migration_result
+	{
+		Ok(()) =>
+		{
+			client.batch_execute("COMMIT").await?;
+			return Ok(());
+		}
+
+		Err(e) =>
+		{
+			client.batch_execute("ROLLBACK").await?;
+			return Err(ApiError::Database(format!("Migration {:04}_{} failed: {}", migration.version, migration.name, e.message())));
+		}
+	}
+}
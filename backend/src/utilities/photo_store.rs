@@ -0,0 +1,106 @@
+use chrono::Utc;
+use rocket::http::ContentType;
+
+use crate::utilities::errors::ApiError;
+
+use std::path::{ Path, PathBuf };
+use std::sync::atomic::{ AtomicUsize, Ordering };
+
+// Where uploaded contestant photos go unless `photo_directory` is set in Rocket.toml or
+// ROCKET_PHOTO_DIRECTORY. Relative to the directory the backend is started from.
+pub const DEFAULT_PHOTO_DIRECTORY : &str = "contestant_photos";
+pub const MAX_PHOTO_MEBIBYTES : u32 = 5;
+
+// Photos are served from here by Rocket's FileServer, straight from the directory.
+pub const PHOTO_ROUTE : &str = "/api/contestant_photos";
+
+static TEMPORARY_STORES : AtomicUsize = AtomicUsize::new(0);
+
+// Contestant photos on local disk. Each upload gets a new file name, so browsers never show
+// a stale photo after it is replaced.
+pub struct PhotoStore
+{
+	directory: PathBuf,
+}
+
+impl PhotoStore
+{
+	pub fn new(directory: PathBuf) -> Self
+	{
+		std::fs::create_dir_all(&directory)
+			.unwrap_or_else(|e: std::io::Error| panic!("Could not create the photo directory [{}]: {}", directory.display(), e));
+
+		return PhotoStore { directory };
+	}
+
+	pub fn from_environment() -> Self
+	{
+		let directory: PathBuf = rocket::Config::figment()
+			.extract_inner::<PathBuf>("photo_directory")
+			.unwrap_or(PathBuf::from(DEFAULT_PHOTO_DIRECTORY));
+
+		return PhotoStore::new(directory);
+	}
+
+	// A fresh directory under the system temp directory, for in-memory runs and tests.
+	pub fn temporary() -> Self
+	{
+		let store_number: usize = TEMPORARY_STORES.fetch_add(1, Ordering::Relaxed);
+		return PhotoStore::new(std::env::temp_dir().join(format!("contestant_photos_{}_{}", std::process::id(), store_number)));
+	}
+
+	pub fn directory(&self) -> &Path
+	{
+		return &self.directory;
+	}
+
+	// Writes the photo and returns its new file name.
+	pub async fn save(&self, contestant_id: i32, content_type: &ContentType, bytes: &[u8]) -> Result<String, ApiError>
+	{
+		let extension: &str = photo_extension(content_type)?;
+		if bytes.is_empty()
+		{
+			return Err(ApiError::Validation("The photo is empty".to_string()));
+		}
+
+		let file_name: String = format!("{}-{}.{}", contestant_id, Utc::now().timestamp_millis(), extension);
+		tokio::fs::write(self.directory.join(&file_name), bytes).await
+			.map_err(|e: std::io::Error| ApiError::Database(format!("The photo could not be saved: {}", e)))?;
+
+		return Ok(file_name);
+	}
+
+	// A photo that is already gone is fine, there is nothing left to clean up.
+	pub async fn remove(&self, file_name: &str)
+	{
+		if let Err(e) = tokio::fs::remove_file(self.directory.join(file_name)).await && e.kind() != std::io::ErrorKind::NotFound
+		{
+			eprintln!("Could not remove the photo [{}]: {}", file_name, e);
+		}
+	}
+}
+
+fn photo_extension(content_type: &ContentType) -> Result<&'static str, ApiError>
+{
+	if *content_type == ContentType::PNG
+	{
+		return Ok("png");
+	}
+
+	if *content_type == ContentType::JPEG
+	{
+		return Ok("jpg");
+	}
+
+	if *content_type == ContentType::WEBP
+	{
+		return Ok("webp");
+	}
+
+	if *content_type == ContentType::GIF
+	{
+		return Ok("gif");
+	}
+
+	return Err(ApiError::Validation(format!("Send the photo as image/png, image/jpeg, image/webp or image/gif, not [{}]", content_type)));
+}
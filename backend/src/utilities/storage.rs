@@ -1,32 +1,254 @@
+use deadpool_postgres::{ Config, ManagerConfig, Pool, PoolConfig, RecyclingMethod, Runtime, Timeouts };
+use rocket::figment::{ Figment, providers::Env };
+use rocket::serde::Deserialize;
+use tokio_postgres::NoTls;
 
-use tokio_postgres::{ Client, NoTls };
+use crate::in_memory_accessor::InMemoryStore;
+use crate::utilities::errors::ApiError;
+
+use std::future::Future;
+use std::ops::Deref;
+use std::sync::Arc;
+use std::sync::atomic::{ AtomicBool, Ordering };
+use std::time::Duration;
+
+// Read from the [database] table of Rocket.toml, ROCKET_DATABASE, or DATABASE_* variables
+// (DATABASE_HOST, DATABASE_POOL_SIZE, ...), with the environment winning.
+#[derive(Deserialize, Clone)]
+#[serde(default)]
+pub struct DatabaseConfig
+{
+	pub host: String,
+	pub port: u16,
+	pub user: String,
+	pub password: String,
+	pub dbname: String,
+	pub pool_size: usize,
+	pub connect_timeout_secs: u64,
+	pub acquire_timeout_secs: u64,
+	pub startup_retries: u32,
+	pub retry_backoff_ms: u64,
+}
+
+impl Default for DatabaseConfig
+{
+	// Matches the Postgres container in compose.yml.
+	fn default() -> Self
+	{
+		DatabaseConfig
+		{
+			host: "localhost".to_string(),
+			port: 5432,
+			user: "postgres".to_string(),
+			password: "postgres".to_string(),
+			dbname: "postgres".to_string(),
+			pool_size: 16,
+			connect_timeout_secs: 5,
+			acquire_timeout_secs: 10,
+			startup_retries: 5,
+			retry_backoff_ms: 500,
+		}
+	}
+}
+
+impl DatabaseConfig
+{
+	pub fn from_environment() -> Self
+	{
+		let figment: Figment = rocket::Config::figment()
+			.merge(Env::prefixed("DATABASE_").map(|key| format!("database.{}", key.as_str()).into()));
+
+		match figment.extract_inner::<DatabaseConfig>("database")
+		{
+			Ok(config) => return config,
+			Err(e) if e.missing() => return DatabaseConfig::default(),
+			Err(e) => panic!("Invalid database configuration: {}", e),
+		}
+	}
+}
+
+tokio::task_local!
+{
+	// The connection holding the open transaction for the request being handled, if any.
+	static ACTIVE_TRANSACTION: Arc<TransactionConnection>;
+}
+
+// Either a connection of its own from the pool, or the connection of the transaction the
+// caller is running in. Derefs to a tokio_postgres::Client, so repositories query it directly.
+pub enum StorageClient
+{
+	Pooled(Box<deadpool_postgres::Client>),
+	Transaction(Arc<TransactionConnection>),
+}
+
+impl Deref for StorageClient
+{
+	type Target = tokio_postgres::Client;
+
+	fn deref(&self) -> &Self::Target
+	{
+		match self
+		{
+			StorageClient::Pooled(client) => return client,
+			StorageClient::Transaction(connection) => return connection.client(),
+		}
+	}
+}
+
+pub struct TransactionConnection
+{
+	client: Option<deadpool_postgres::Client>,
+	finished: AtomicBool,
+}
+
+impl TransactionConnection
+{
+	fn client(&self) -> &deadpool_postgres::Client
+	{
+		return self.client.as_ref().expect("The transaction connection is only taken out when dropped");
+	}
+}
+
+// If a transaction is abandoned halfway (the request future was dropped, or COMMIT failed) the
+// connection is closed instead of going back to the pool, which makes Postgres roll it back.
+impl Drop for TransactionConnection
+{
+	fn drop(&mut self)
+	{
+		if !self.finished.load(Ordering::SeqCst) && let Some(client) = self.client.take()
+		{
+			drop(deadpool_postgres::Object::take(client));
+		}
+	}
+}
 
 pub struct StorageConnector
 {
-	pub storage : Client,
+	backend : StorageBackend,
+}
+
+enum StorageBackend
+{
+	Postgres(Pool),
+	// Tests and `--in-memory` runs. Only the InMemory* repositories read it.
+	InMemory(InMemoryStore),
 }
 
 impl StorageConnector
 {
-	pub async fn establish_connection() -> Self
+	// Postgres often comes up after the backend (docker compose, restarts), so keep
+	// trying with a doubling delay before giving up on startup.
+	pub async fn establish_connection(config: &DatabaseConfig) -> Self
 	{
-		let (new_connection, connection) = tokio_postgres
-			::connect("host=localhost user=postgres password=postgres dbname=postgres", NoTls).await
-			.expect("Failed to connect to Postgres");
+		let pool: Pool = Self::create_pool(config);
+		let mut backoff: Duration = Duration::from_millis(config.retry_backoff_ms);
 
-		tokio::spawn(async move
+		for attempt in 1..=config.startup_retries.max(1)
 		{
-			if let Err(e) = connection.await
+			match pool.get().await
 			{
-				eprintln!("Failed to connect to Postgres: {}", e);
+				Ok(_) => return StorageConnector { backend: StorageBackend::Postgres(pool) },
+				Err(e) if attempt < config.startup_retries =>
+				{
+					eprintln!("Postgres at {}:{} not reachable (attempt {}): {}. Retrying in {:?}", config.host, config.port, attempt, e, backoff);
+					tokio::time::sleep(backoff).await;
+					backoff *= 2;
+				}
+				Err(e) => panic!("Failed to connect to Postgres at {}:{}: {}", config.host, config.port, e),
 			}
-		});
+		}
 
-		let storage_connector : StorageConnector = StorageConnector
+		unreachable!("the last connection attempt either returns or panics");
+	}
+
+	fn create_pool(config: &DatabaseConfig) -> Pool
+	{
+		let mut pool_config: PoolConfig = PoolConfig::new(config.pool_size);
+		pool_config.timeouts = Timeouts
 		{
-			storage: new_connection
+			wait: Some(Duration::from_secs(config.acquire_timeout_secs)),
+			create: Some(Duration::from_secs(config.connect_timeout_secs)),
+			recycle: Some(Duration::from_secs(config.connect_timeout_secs)),
 		};
 
-		return storage_connector;
+		let mut postgres_config: Config = Config::new();
+		postgres_config.host = Some(config.host.clone());
+		postgres_config.port = Some(config.port);
+		postgres_config.user = Some(config.user.clone());
+		postgres_config.password = Some(config.password.clone());
+		postgres_config.dbname = Some(config.dbname.clone());
+		postgres_config.connect_timeout = Some(Duration::from_secs(config.connect_timeout_secs));
+		postgres_config.pool = Some(pool_config);
+		// Fast recycling drops connections that died with a Postgres restart, so the pool heals on its own.
+		postgres_config.manager = Some(ManagerConfig { recycling_method: RecyclingMethod::Fast });
+
+		return postgres_config.create_pool(Some(Runtime::Tokio1), NoTls)
+			.expect("Invalid Postgres pool configuration");
+	}
+
+	pub fn in_memory(store: InMemoryStore) -> Self
+	{
+		return StorageConnector { backend: StorageBackend::InMemory(store) };
+	}
+
+	pub fn in_memory_store(&self) -> Option<&InMemoryStore>
+	{
+		match &self.backend
+		{
+			StorageBackend::InMemory(store) => return Some(store),
+			StorageBackend::Postgres(_) => return None,
+		}
+	}
+
+	pub async fn client(&self) -> Result<StorageClient, ApiError>
+	{
+		if let Ok(transaction_client) = ACTIVE_TRANSACTION.try_with(Arc::clone)
+		{
+			return Ok(StorageClient::Transaction(transaction_client));
+		}
+
+		return self.pooled_client().await.map(|client: deadpool_postgres::Client| StorageClient::Pooled(Box::new(client)));
+	}
+
+	async fn pooled_client(&self) -> Result<deadpool_postgres::Client, ApiError>
+	{
+		match &self.backend
+		{
+			StorageBackend::Postgres(pool) => return pool.get().await.map_err(|e: deadpool_postgres::PoolError| ApiError::Database(format!("No database connection available: {}", e))),
+			StorageBackend::InMemory(_) => return Err(ApiError::Database("In-memory storage has no database connection".to_string())),
+		}
+	}
+
+	// Runs `work` inside one database transaction. Every repository call made while it runs
+	// picks the transaction up through client(), so managers can group calls without passing
+	// a handle around. Commits if `work` returns Ok, rolls back on Err. Nested calls join the
+	// outer transaction. In memory, a failed `work` puts the data back the way it was.
+	pub async fn transaction<T, F>(&self, work: F) -> Result<T, ApiError>
+		where F: Future<Output = Result<T, ApiError>>
+	{
+		if let StorageBackend::InMemory(store) = &self.backend
+		{
+			return store.transaction(work).await;
+		}
+
+		if ACTIVE_TRANSACTION.try_with(|_| ()).is_ok()
+		{
+			return work.await;
+		}
+
+		let connection: Arc<TransactionConnection> = Arc::new(TransactionConnection
+		{
+			client: Some(self.pooled_client().await?),
+			finished: AtomicBool::new(false),
+		});
+
+		connection.client().batch_execute("BEGIN").await?;
+		let work_result: Result<T, ApiError> = ACTIVE_TRANSACTION.scope(Arc::clone(&connection), work).await;
+
+		let finish_statement: &str = if work_result.is_ok() { "COMMIT" } else { "ROLLBACK" };
+		connection.client().batch_execute(finish_statement).await?;
+		connection.finished.store(true, Ordering::SeqCst);
+
+		return work_result;
 	}
-}
\ No newline at end of file
+}
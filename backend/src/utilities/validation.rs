@@ -0,0 +1,20 @@
+use rocket::serde::json::{ Error as JsonError, Json };
+
+use crate::utilities::errors::ApiError;
+
+// Taking Result<Json<T>, JsonError> as the data guard lets a route turn a body that
+// doesn't parse into our own 422 instead of Rocket's default HTML error page.
+pub fn parsed_body<T>(body: Result<Json<T>, JsonError<'_>>) -> Result<T, ApiError>
+{
+	match body
+	{
+		Ok(Json(value)) => return Ok(value),
+		Err(JsonError::Parse(_, e)) => return Err(ApiError::Validation(format!("Malformed request body: {}", e))),
+		Err(JsonError::Io(e)) => return Err(ApiError::Validation(format!("Unreadable request body: {}", e))),
+	}
+}
+
+pub fn required<T>(value: Option<T>, field: &str) -> Result<T, ApiError>
+{
+	return value.ok_or(ApiError::Validation(format!("Missing required field [{}]", field)));
+}
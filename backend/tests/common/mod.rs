@@ -0,0 +1,123 @@
+#![allow(clippy::needless_return, dead_code)]
+
+use backend::in_memory_accessor::InMemoryStore;
+use backend::utilities::storage::StorageConnector;
+use rocket::http::{ ContentType, Method, Status };
+use rocket::local::asynchronous::{ Client, LocalResponse };
+use serde_json::{ Value, json };
+
+use std::sync::Arc;
+
+pub const GAMEMASTER_EMAIL : &str = "probst@example.com";
+pub const PASSWORD : &str = "fire-represents-life";
+
+// The whole app on fresh in-memory storage. The client keeps cookies between requests,
+// so logging in once covers the requests that follow.
+pub async fn client() -> Client
+{
+	let storage: Arc<StorageConnector> = Arc::new(StorageConnector::in_memory(InMemoryStore::new()));
+	return Client::tracked(backend::rocket(storage)).await.expect("The in-memory app should launch");
+}
+
+// Sends `body` as JSON when given and returns the status with the parsed response body,
+// Null when the route answered with nothing.
+pub async fn request(client: &Client, method: Method, uri: &str, body: Option<Value>) -> (Status, Value)
+{
+	let mut local_request = client.req(method, uri.to_string());
+	if let Some(body) = body
+	{
+		local_request = local_request.header(ContentType::JSON).body(body.to_string());
+	}
+
+	let response: LocalResponse<'_> = local_request.dispatch().await;
+	let status: Status = response.status();
+	let text: String = response.into_string().await.unwrap_or_default();
+	let parsed: Value = if text.is_empty() { Value::Null } else { serde_json::from_str(&text).expect("Responses should be JSON") };
+
+	return (status, parsed);
+}
+
+pub async fn get(client: &Client, uri: &str) -> (Status, Value)
+{
+	return request(client, Method::Get, uri, None).await;
+}
+
+pub async fn post(client: &Client, uri: &str, body: Value) -> (Status, Value)
+{
+	return request(client, Method::Post, uri, Some(body)).await;
+}
+
+pub async fn put(client: &Client, uri: &str, body: Value) -> (Status, Value)
+{
+	return request(client, Method::Put, uri, Some(body)).await;
+}
+
+pub async fn delete(client: &Client, uri: &str) -> (Status, Value)
+{
+	return request(client, Method::Delete, uri, None).await;
+}
+
+pub fn assert_error(response: &(Status, Value), status: Status, kind: &str)
+{
+	assert_eq!(response.0, status, "unexpected response {}", response.1);
+	assert_eq!(response.1["error"], kind, "unexpected response {}", response.1);
+	assert!(response.1["message"].as_str().is_some_and(|message: &str| !message.is_empty()));
+}
+
+pub async fn login(client: &Client, email: &str) -> (Status, Value)
+{
+	return post(client, "/api/auth/login", json!({ "email": email, "password": PASSWORD })).await;
+}
+
+// The first account on a fresh store is allowed to be a gamemaster. Leaves it logged in.
+pub async fn sign_in_gamemaster(client: &Client) -> i64
+{
+	let (status, users) = post(client, "/api/users", json!({ "name": "Jeff", "email": GAMEMASTER_EMAIL, "account_type": "GameMaster", "password": PASSWORD })).await;
+	assert_eq!(status, Status::Ok);
+
+	let (status, user) = login(client, GAMEMASTER_EMAIL).await;
+	assert_eq!(status, Status::Ok);
+	assert_eq!(users[0]["id"], user["id"]);
+
+	return user["id"].as_i64().unwrap();
+}
+
+pub async fn add_player(client: &Client, name: &str) -> i64
+{
+	let email: String = format!("{}@example.com", name.to_lowercase());
+	let (status, users) = post(client, "/api/users", json!({ "name": name, "email": email, "account_type": "Player", "password": PASSWORD })).await;
+	assert_eq!(status, Status::Ok);
+
+	return users.as_array().unwrap().iter()
+		.find(|user: &&Value| user["email"] == email.as_str())
+		.and_then(|user: &Value| user["id"].as_i64())
+		.unwrap();
+}
+
+// Show 1 with an open first round and Rob (1) and Parvati (2) on the cast, still in draft so
+// the cast can change. Needs a gamemaster session.
+pub async fn seed_cast(client: &Client)
+{
+	assert_eq!(post(client, "/api/gameshows", json!({ "name": "Heroes vs Villains" })).await.0, Status::Ok);
+	assert_eq!(post(client, "/api/gameshows/1/rounds", json!({ "round_number": 1, "title": "Episode 1", "status": "open" })).await.0, Status::Ok);
+
+	for (contestant_id, name) in [(1, "Rob"), (2, "Parvati")]
+	{
+		let (status, contestant) = post(client, "/api/contestants", json!({ "name": name, "round_number": -1, "was_medically_evacuated": false })).await;
+		assert_eq!(status, Status::Ok);
+		assert_eq!(contestant["id"], contestant_id);
+
+		let enrollment: Value = json!({ "contestant_id": contestant_id, "game_show_id": 1, "nickname": name.to_uppercase() });
+		assert_eq!(post(client, "/api/contestants/enroll", enrollment).await.0, Status::Ok);
+	}
+}
+
+// The seeded show, live so contestants can be eliminated and picked.
+pub async fn seed_show(client: &Client)
+{
+	seed_cast(client).await;
+	for status in ["enrollment", "live"]
+	{
+		assert_eq!(put(client, "/api/gameshows/1", json!({ "name": "Heroes vs Villains", "status": status })).await.0, Status::Ok);
+	}
+}
@@ -0,0 +1,451 @@
+#![allow(clippy::needless_return)]
+
+mod common;
+
+use common::{ GAMEMASTER_EMAIL, add_player, assert_error, client, delete, get, login, post, put, seed_cast, seed_show, sign_in_gamemaster };
+use rocket::http::{ ContentType, Status };
+use serde_json::{ Value, json };
+
+#[rocket::async_test]
+async fn gamemasters_manage_game_shows()
+{
+	let client = client().await;
+	assert_error(&post(&client, "/api/gameshows", json!({ "name": "Survivor" })).await, Status::Unauthorized, "unauthorized");
+
+	sign_in_gamemaster(&client).await;
+	let (status, game_shows) = post(&client, "/api/gameshows", json!({ "name": "Survivor" })).await;
+	assert_eq!(status, Status::Ok);
+	assert_eq!(game_shows, json!([{ "id": 1, "name": "Survivor", "status": "draft", "start_date": null, "end_date": null }]));
+	assert_eq!(get(&client, "/api/gameshows").await.1, game_shows);
+
+	assert_error(&delete(&client, "/api/gameshows/9").await, Status::NotFound, "not_found");
+	let (status, game_shows) = delete(&client, "/api/gameshows/1").await;
+	assert_eq!(status, Status::Ok);
+	assert_eq!(game_shows, json!([]));
+}
+
+#[rocket::async_test]
+async fn seasons_move_through_their_statuses_in_order()
+{
+	let client = client().await;
+	sign_in_gamemaster(&client).await;
+	seed_cast(&client).await;
+	let sandra = add_player(&client, "Sandra").await;
+	post(&client, "/api/leagues", json!({ "name": "Office pool", "id_showseason": 1 })).await;
+	post(&client, &format!("/api/leagues?user_id={}&league_id=1", sandra), json!({})).await;
+	post(&client, "/api/gameshows/1/tribes", json!({ "name": "Heroes", "color": "#1E90FF" })).await;
+
+	assert_error(&put(&client, "/api/gameshows/1", json!({ "name": "Heroes vs Villains", "status": "live" })).await, Status::Conflict, "conflict");
+	assert_error(&post(&client, "/api/contestants/elim", json!({ "contestant_id": 1, "game_show_id": 1, "round_number": 1 })).await, Status::Conflict, "conflict");
+	let dates: Value = json!({ "name": "Heroes vs Villains", "status": "draft", "start_date": "2010-02-11T20:00:00Z", "end_date": "2010-02-10T20:00:00Z" });
+	assert_error(&put(&client, "/api/gameshows/1", dates).await, Status::UnprocessableEntity, "validation");
+	assert_error(&put(&client, "/api/gameshows/9", json!({ "name": "Survivor", "status": "draft" })).await, Status::NotFound, "not_found");
+
+	let (status, game_show) = put(&client, "/api/gameshows/1", json!({ "name": "Heroes vs Villains", "status": "enrollment" })).await;
+	assert_eq!(status, Status::Ok);
+	assert_eq!(game_show["status"], "enrollment");
+	assert_error(&post(&client, "/api/contestants/enroll", json!({ "contestant_id": 1, "game_show_id": 1 })).await, Status::Conflict, "conflict");
+
+	login(&client, "sandra@example.com").await;
+	assert_error(&post(&client, "/api/leagues/set_pick?league_id=1&round_number=1&contestant_id=1&rank_pick=1", json!({})).await, Status::Conflict, "conflict");
+
+	login(&client, GAMEMASTER_EMAIL).await;
+	let (status, game_show) = put(&client, "/api/gameshows/1", json!({ "name": "Heroes vs Villains", "status": "live" })).await;
+	assert_eq!(status, Status::Ok);
+	assert!(game_show["start_date"].is_string());
+	assert_eq!(game_show["end_date"], Value::Null);
+	assert_error(&put(&client, "/api/gameshows/1", json!({ "name": "Heroes vs Villains", "status": "draft" })).await, Status::Conflict, "conflict");
+	assert_error(&post(&client, "/api/gameshows/1/import", json!([{ "name": "Tony" }])).await, Status::Conflict, "conflict");
+
+	login(&client, "sandra@example.com").await;
+	assert_eq!(post(&client, "/api/leagues/set_pick?league_id=1&round_number=1&contestant_id=1&rank_pick=1", json!({})).await.0, Status::Ok);
+
+	login(&client, GAMEMASTER_EMAIL).await;
+	let finished: Value = json!({ "name": "Heroes vs Villains", "status": "finished", "start_date": game_show["start_date"] });
+	let (status, game_show) = put(&client, "/api/gameshows/1", finished).await;
+	assert_eq!(status, Status::Ok);
+	assert!(game_show["end_date"].is_string());
+	assert_eq!(get(&client, "/api/gameshows").await.1[0]["status"], "finished");
+	assert_error(&post(&client, "/api/contestants/elim", json!({ "contestant_id": 1, "game_show_id": 1, "round_number": 1 })).await, Status::Conflict, "conflict");
+
+	// Nothing the final standings are worked out from can change any more.
+	let rules: Value = json!({ "survival_points": 2, "boot_prediction_bonus": 5, "medevac_points": 0, "medevac_counts_as_boot": false });
+	let refused: Vec<(Status, Value)> = vec![
+		post(&client, "/api/gameshows/1/rounds", json!({ "round_number": 2, "title": "Episode 2", "status": "open" })).await,
+		put(&client, "/api/gameshows/1/rounds/1", json!({ "round_number": 1, "title": "Episode 1", "status": "scored" })).await,
+		delete(&client, "/api/gameshows/1/rounds/1").await,
+		post(&client, "/api/gameshows/1/tribes", json!({ "name": "Villains", "color": "#B22222" })).await,
+		put(&client, "/api/gameshows/1/tribes/1", json!({ "name": "Heroes", "color": "#000080" })).await,
+		delete(&client, "/api/gameshows/1/tribes/1").await,
+		post(&client, "/api/gameshows/1/tribe_assignments", json!({ "contestant_id": 1, "tribe_id": 1, "from_round": 1 })).await,
+		post(&client, "/api/gameshows/1/tribes/1/merge?from_round=1", json!({})).await,
+		post(&client, "/api/leagues/1/scoring_rules", rules.clone()).await,
+		put(&client, "/api/leagues/1/scoring_rules", rules).await,
+		delete(&client, "/api/leagues/1/scoring_rules").await,
+	];
+	for response in refused
+	{
+		assert_error(&response, Status::Conflict, "conflict");
+	}
+}
+
+#[rocket::async_test]
+async fn rounds_are_validated()
+{
+	let client = client().await;
+	sign_in_gamemaster(&client).await;
+	post(&client, "/api/gameshows", json!({ "name": "Survivor" })).await;
+
+	let (status, rounds) = post(&client, "/api/gameshows/1/rounds", json!({ "round_number": 1, "title": "Premiere", "status": "upcoming" })).await;
+	assert_eq!(status, Status::Ok);
+	assert_eq!(rounds[0]["game_show_id"], 1);
+	assert_eq!(rounds[0]["status"], "upcoming");
+
+	assert_error(&post(&client, "/api/gameshows/1/rounds", json!({ "round_number": 1, "title": "Again", "status": "open" })).await, Status::Conflict, "conflict");
+	assert_error(&post(&client, "/api/gameshows/1/rounds", json!({ "round_number": 0, "title": "Zero", "status": "open" })).await, Status::UnprocessableEntity, "validation");
+	assert_error(&post(&client, "/api/gameshows/9/rounds", json!({ "round_number": 1, "title": "Orphan", "status": "open" })).await, Status::NotFound, "not_found");
+
+	let late_lock: Value = json!({ "round_number": 1, "title": "Premiere", "status": "open", "air_date": "2026-03-01T01:00:00Z", "lock_time": "2026-03-01T02:00:00Z" });
+	assert_error(&put(&client, "/api/gameshows/1/rounds/1", late_lock).await, Status::UnprocessableEntity, "validation");
+
+	let (status, rounds) = put(&client, "/api/gameshows/1/rounds/1", json!({ "round_number": 1, "title": "Premiere", "status": "open" })).await;
+	assert_eq!(status, Status::Ok);
+	assert_eq!(rounds[0]["status"], "open");
+	assert_eq!(get(&client, "/api/gameshows/1/rounds").await.1, rounds);
+	assert_error(&put(&client, "/api/gameshows/1/rounds/4", json!({ "round_number": 4, "title": "Missing", "status": "open" })).await, Status::NotFound, "not_found");
+
+	assert_error(&delete(&client, "/api/gameshows/1/rounds/4").await, Status::NotFound, "not_found");
+	assert_eq!(delete(&client, "/api/gameshows/1/rounds/1").await, (Status::Ok, json!([])));
+}
+
+#[rocket::async_test]
+async fn contestants_are_created_looked_up_and_deleted()
+{
+	let client = client().await;
+	sign_in_gamemaster(&client).await;
+
+	let (status, contestant) = post(&client, "/api/contestants", json!({ "name": "Boston Rob", "round_number": -1, "was_medically_evacuated": false })).await;
+	assert_eq!(status, Status::Ok);
+	assert_eq!(contestant["id"], 1);
+
+	assert_eq!(get(&client, "/api/contestants/select?name=Boston%20Rob").await.1["id"], 1);
+	assert_eq!(get(&client, "/api/contestants/1").await.1["name"], "Boston Rob");
+	assert_error(&get(&client, "/api/contestants/select?name=Russell").await, Status::NotFound, "not_found");
+	assert_error(&get(&client, "/api/contestants/9").await, Status::NotFound, "not_found");
+	assert_eq!(get(&client, "/api/contestants").await.1["total"], 1);
+
+	// A second Boston Rob needs a disambiguator, and then the name alone is ambiguous.
+	assert_error(&post(&client, "/api/contestants", json!({ "name": "Boston Rob", "round_number": -1, "was_medically_evacuated": false })).await, Status::Conflict, "conflict");
+	let (status, namesake) = post(&client, "/api/contestants", json!({ "name": "Boston Rob", "disambiguator": "Junior", "round_number": -1, "was_medically_evacuated": false })).await;
+	assert_eq!((status, namesake["id"].clone(), namesake["disambiguator"].clone()), (Status::Ok, json!(2), json!("Junior")));
+	assert_error(&get(&client, "/api/contestants/select?name=Boston%20Rob").await, Status::Conflict, "conflict");
+	assert_eq!(get(&client, "/api/contestants/select?name=Boston%20Rob&disambiguator=").await.1["id"], 1);
+
+	assert_eq!(delete(&client, "/api/contestants/1").await.0, Status::Ok);
+	assert_error(&delete(&client, "/api/contestants/1").await, Status::NotFound, "not_found");
+	assert_eq!(get(&client, "/api/contestants/select?name=Boston%20Rob").await.1["id"], 2);
+	assert_eq!(delete(&client, "/api/contestants/2").await.0, Status::Ok);
+	assert_eq!(get(&client, "/api/contestants").await.1["contestants"], json!([]));
+}
+
+#[rocket::async_test]
+async fn contestants_cast_on_a_started_season_are_kept()
+{
+	let client = client().await;
+	sign_in_gamemaster(&client).await;
+	seed_cast(&client).await;
+
+	assert_eq!(delete(&client, "/api/contestants/2").await.0, Status::Ok);
+
+	put(&client, "/api/gameshows/1", json!({ "name": "Heroes vs Villains", "status": "enrollment" })).await;
+	assert_error(&delete(&client, "/api/contestants/1").await, Status::Conflict, "conflict");
+	assert_eq!(get(&client, "/api/contestants/1").await.0, Status::Ok);
+}
+
+#[rocket::async_test]
+async fn enrollment_checks_the_request()
+{
+	let client = client().await;
+	sign_in_gamemaster(&client).await;
+	seed_cast(&client).await;
+
+	let (status, cast) = get(&client, "/api/contestants/on_show?game_show_id=1").await;
+	assert_eq!(status, Status::Ok);
+	assert_eq!(cast[0]["nickname"], "ROB");
+	assert_eq!(cast[1]["id_showseason"], 1);
+
+	assert_error(&post(&client, "/api/contestants/enroll", json!({ "contestant_id": 1, "game_show_id": 1 })).await, Status::Conflict, "conflict");
+	assert_error(&post(&client, "/api/contestants/enroll", json!({ "game_show_id": 1 })).await, Status::UnprocessableEntity, "validation");
+	assert_error(&post(&client, "/api/contestants/enroll", json!({ "contestant_id": 7, "game_show_id": 1 })).await, Status::UnprocessableEntity, "validation");
+	assert_error(&post(&client, "/api/contestants/enroll", json!({ "contestant_id": "Rob" })).await, Status::UnprocessableEntity, "validation");
+}
+
+#[rocket::async_test]
+async fn eliminations_and_medevacs_update_the_cast()
+{
+	let client = client().await;
+	sign_in_gamemaster(&client).await;
+	seed_show(&client).await;
+
+	assert_error(&post(&client, "/api/contestants/elim", json!({ "contestant_id": 1, "game_show_id": 1, "round_number": 5 })).await, Status::NotFound, "not_found");
+	assert_error(&post(&client, "/api/contestants/elim", json!({ "contestant_id": 9, "game_show_id": 1, "round_number": 1 })).await, Status::NotFound, "not_found");
+	assert_error(&post(&client, "/api/contestants/elim", json!({ "contestant_id": 1, "game_show_id": 1 })).await, Status::UnprocessableEntity, "validation");
+
+	assert_eq!(post(&client, "/api/contestants/elim", json!({ "contestant_id": 1, "game_show_id": 1, "round_number": 1 })).await.0, Status::Ok);
+	assert_eq!(post(&client, "/api/contestants/medevac", json!({ "contestant_id": 2, "game_show_id": 1, "round_number": 1 })).await.0, Status::Ok);
+	assert_error(&post(&client, "/api/contestants/medevac", json!({ "contestant_id": 2, "game_show_id": 1, "round_number": 3 })).await, Status::NotFound, "not_found");
+
+	let (_, cast) = get(&client, "/api/contestants/on_show?game_show_id=1").await;
+	assert_eq!((cast[0]["round_number"].clone(), cast[0]["was_medically_evacuated"].clone()), (json!(1), json!(false)));
+	assert_eq!((cast[1]["round_number"].clone(), cast[1]["was_medically_evacuated"].clone()), (json!(1), json!(true)));
+}
+
+#[rocket::async_test]
+async fn cast_lists_import_from_json_and_csv()
+{
+	let client = client().await;
+	sign_in_gamemaster(&client).await;
+	seed_cast(&client).await;
+
+	let cast: Value = json!([{ "name": "Rob", "nickname": "Boston Rob" }, { "name": "Sandra", "tribe": "Dakal", "bio": "Two-time winner" }, { "nickname": "Nameless" }]);
+	let (status, results) = post(&client, "/api/gameshows/1/import", cast).await;
+	assert_eq!(status, Status::Ok);
+	assert_eq!(results[0]["outcome"], "already_enrolled");
+	assert_eq!(results[1]["outcome"], "created");
+	assert_eq!(results[1]["contestant_id"], 3);
+	assert_eq!(results[2]["outcome"], "failed");
+	assert!(results[2]["message"].as_str().unwrap().contains("name"));
+
+	let csv: &str = "name,tribe\nParvati,Heroes\nTony,Villains\n";
+	let response = client.post("/api/gameshows/1/import").header(ContentType::CSV).body(csv).dispatch().await;
+	assert_eq!(response.status(), Status::Ok);
+	let results: Value = response.into_json().await.unwrap();
+	assert_eq!(results[0]["outcome"], "already_enrolled");
+	assert_eq!(results[1]["outcome"], "created");
+
+	let (_, cast) = get(&client, "/api/contestants/on_show?game_show_id=1").await;
+	assert_eq!(cast.as_array().unwrap().len(), 4);
+	assert_eq!(cast[2]["tribe"], "Dakal");
+	assert_eq!(cast[2]["bio"], "Two-time winner");
+
+	let response = client.post("/api/gameshows/1/import").header(ContentType::CSV).body("tribe\nHeroes\n").dispatch().await;
+	assert_eq!(response.status(), Status::UnprocessableEntity);
+	let response = client.post("/api/gameshows/1/import").header(ContentType::Plain).body("Rob").dispatch().await;
+	assert_eq!(response.status(), Status::UnprocessableEntity);
+	assert_error(&post(&client, "/api/gameshows/9/import", json!([{ "name": "Rob" }])).await, Status::NotFound, "not_found");
+	assert_error(&post(&client, "/api/gameshows/1/import", json!({ "name": "Rob" })).await, Status::UnprocessableEntity, "validation");
+}
+
+#[rocket::async_test]
+async fn tribes_record_swaps_and_the_merge()
+{
+	let client = client().await;
+	sign_in_gamemaster(&client).await;
+	seed_cast(&client).await;
+
+	assert_eq!(post(&client, "/api/gameshows/1/tribes", json!({ "name": "Heroes", "color": "#1E90FF" })).await.0, Status::Ok);
+	let (status, tribes) = post(&client, "/api/gameshows/1/tribes", json!({ "name": "Villains", "color": "#B22222" })).await;
+	assert_eq!(status, Status::Ok);
+	assert_eq!(tribes[1], json!({ "id": 2, "game_show_id": 1, "name": "Villains", "color": "#B22222" }));
+	assert_error(&post(&client, "/api/gameshows/1/tribes", json!({ "name": "Heroes", "color": "#000000" })).await, Status::Conflict, "conflict");
+	assert_error(&post(&client, "/api/gameshows/1/tribes", json!({ "name": "Yin Yang", "color": "orange" })).await, Status::UnprocessableEntity, "validation");
+
+	for (contestant_id, tribe_id) in [(1, 2), (2, 1)]
+	{
+		let assignment: Value = json!({ "contestant_id": contestant_id, "tribe_id": tribe_id, "from_round": 1 });
+		assert_eq!(post(&client, "/api/gameshows/1/tribe_assignments", assignment).await.0, Status::Ok);
+	}
+
+	// Parvati swaps to the Villains, then everyone merges.
+	assert_eq!(post(&client, "/api/gameshows/1/tribe_assignments", json!({ "contestant_id": 2, "tribe_id": 2, "from_round": 4 })).await.0, Status::Ok);
+	let (_, cast) = get(&client, "/api/contestants/on_show?game_show_id=1").await;
+	assert_eq!((cast[1]["tribe"].clone(), cast[1]["tribe_color"].clone()), (json!("Villains"), json!("#B22222")));
+
+	post(&client, "/api/gameshows/1/tribes", json!({ "name": "Yin Yang", "color": "#FFD700" })).await;
+	let (status, assignments) = post(&client, "/api/gameshows/1/tribes/3/merge?from_round=7", json!({})).await;
+	assert_eq!(status, Status::Ok);
+	assert_eq!(assignments.as_array().unwrap().len(), 5);
+	let (_, cast) = get(&client, "/api/contestants/on_show?game_show_id=1").await;
+	assert_eq!(cast[0]["tribe"], "Yin Yang");
+
+	assert_error(&post(&client, "/api/gameshows/1/tribe_assignments", json!({ "contestant_id": 9, "tribe_id": 1, "from_round": 1 })).await, Status::UnprocessableEntity, "validation");
+	assert_error(&post(&client, "/api/gameshows/1/tribe_assignments", json!({ "contestant_id": 1, "tribe_id": 9, "from_round": 1 })).await, Status::NotFound, "not_found");
+	assert_error(&delete(&client, "/api/gameshows/1/tribe_assignments?contestant_id=1&from_round=2").await, Status::NotFound, "not_found");
+
+	let (status, tribes) = delete(&client, "/api/gameshows/1/tribes/3").await;
+	assert_eq!(status, Status::Ok);
+	assert_eq!(tribes.as_array().unwrap().len(), 2);
+	let (_, assignments) = get(&client, "/api/gameshows/1/tribe_assignments").await;
+	assert_eq!(assignments.as_array().unwrap().len(), 3);
+
+	assert_eq!(put(&client, "/api/gameshows/1/tribes/2", json!({ "name": "Villains", "color": "#8B0000" })).await.0, Status::Ok);
+	let (_, cast) = get(&client, "/api/contestants/on_show?game_show_id=1").await;
+	assert_eq!(cast[0]["tribe_color"], "#8B0000");
+
+	// Once live, swaps still happen but tribes can't be taken away.
+	for status in ["enrollment", "live"]
+	{
+		put(&client, "/api/gameshows/1", json!({ "name": "Heroes vs Villains", "status": status })).await;
+	}
+	assert_eq!(post(&client, "/api/gameshows/1/tribe_assignments", json!({ "contestant_id": 1, "tribe_id": 1, "from_round": 5 })).await.0, Status::Ok);
+	assert_error(&delete(&client, "/api/gameshows/1/tribes/1").await, Status::Conflict, "conflict");
+}
+
+#[rocket::async_test]
+async fn the_event_log_drives_contestant_status()
+{
+	let client = client().await;
+	sign_in_gamemaster(&client).await;
+	seed_show(&client).await;
+	post(&client, "/api/gameshows/1/rounds", json!({ "round_number": 2, "title": "Episode 2", "status": "upcoming" })).await;
+
+	assert_eq!(post(&client, "/api/gameshows/1/events", json!({ "contestant_id": 1, "round_number": 1, "event_type": "won_immunity" })).await.0, Status::Ok);
+	assert_eq!(post(&client, "/api/contestants/elim", json!({ "contestant_id": 2, "game_show_id": 1, "round_number": 1 })).await.0, Status::Ok);
+	let (status, events) = post(&client, "/api/gameshows/1/events", json!({ "contestant_id": 2, "round_number": 2, "event_type": "joined_jury" })).await;
+	assert_eq!(status, Status::Ok);
+	assert_eq!(events.as_array().unwrap().iter().map(|event| event["event_type"].clone()).collect::<Vec<Value>>(), vec![json!("won_immunity"), json!("voted_out"), json!("joined_jury")]);
+
+	let (_, cast) = get(&client, "/api/contestants/on_show?game_show_id=1").await;
+	assert_eq!((cast[0]["status"].clone(), cast[1]["status"].clone(), cast[1]["round_number"].clone()), (json!("active"), json!("jury"), json!(1)));
+
+	assert_error(&post(&client, "/api/contestants/elim", json!({ "contestant_id": 2, "game_show_id": 1, "round_number": 2 })).await, Status::Conflict, "conflict");
+	assert_error(&post(&client, "/api/gameshows/1/events", json!({ "contestant_id": 1, "round_number": 1, "event_type": "fell_in_the_ocean" })).await, Status::UnprocessableEntity, "validation");
+	assert_error(&post(&client, "/api/gameshows/1/events", json!({ "contestant_id": 1, "round_number": 1, "event_type": "reversed", "reverses_event_id": 1 })).await, Status::UnprocessableEntity, "validation");
+	assert_eq!(get(&client, "/api/gameshows/1/events").await.1.as_array().unwrap().len(), 3);
+}
+
+#[rocket::async_test]
+async fn round_stats_are_recorded_per_contestant_and_round()
+{
+	let client = client().await;
+	sign_in_gamemaster(&client).await;
+	seed_show(&client).await;
+
+	let (status, round_stats) = post(&client, "/api/gameshows/1/round_stats", json!({ "contestant_id": 1, "round_number": 1, "won_reward": true, "votes_received": 3 })).await;
+	assert_eq!(status, Status::Ok);
+	assert_eq!((round_stats[0]["won_reward"].clone(), round_stats[0]["votes_received"].clone(), round_stats[0]["idols_found"].clone()), (json!(true), json!(3), json!(0)));
+
+	post(&client, "/api/gameshows/1/round_stats", json!({ "contestant_id": 1, "round_number": 1, "cast_correct_vote": true })).await;
+	let (_, round_stats) = get(&client, "/api/gameshows/1/round_stats").await;
+	assert_eq!(round_stats.as_array().unwrap().len(), 1);
+	assert_eq!((round_stats[0]["won_reward"].clone(), round_stats[0]["cast_correct_vote"].clone()), (json!(false), json!(true)));
+
+	assert_error(&post(&client, "/api/gameshows/1/round_stats", json!({ "contestant_id": 1, "round_number": 1, "idols_played": -1 })).await, Status::UnprocessableEntity, "validation");
+	assert_error(&post(&client, "/api/gameshows/1/round_stats", json!({ "contestant_id": 9, "round_number": 1 })).await, Status::NotFound, "not_found");
+
+	assert_eq!(delete(&client, "/api/gameshows/1/round_stats?contestant_id=1&round_number=1").await.1, json!([]));
+	assert_error(&delete(&client, "/api/gameshows/1/round_stats?contestant_id=1&round_number=1").await, Status::NotFound, "not_found");
+}
+
+#[rocket::async_test]
+async fn contestants_are_searched_by_name_and_season()
+{
+	let client = client().await;
+	sign_in_gamemaster(&client).await;
+	seed_show(&client).await;
+	for name in ["Robert", "Sandra", "Tony"]
+	{
+		post(&client, "/api/contestants", json!({ "name": name, "round_number": -1, "was_medically_evacuated": false })).await;
+	}
+
+	let names = |page: &Value| page["contestants"].as_array().unwrap().iter().map(|contestant| contestant["name"].clone()).collect::<Vec<Value>>();
+
+	let (status, page) = get(&client, "/api/contestants?q=ROB").await;
+	assert_eq!(status, Status::Ok);
+	assert_eq!(names(&page), vec![json!("Rob"), json!("Robert")]);
+
+	assert_eq!(names(&get(&client, "/api/contestants?q=sandar").await.1), vec![json!("Sandra")]);
+	assert_eq!(names(&get(&client, "/api/contestants?q=rob&season=1").await.1), vec![json!("Rob")]);
+
+	let (_, page) = get(&client, "/api/contestants?limit=2&offset=2").await;
+	assert_eq!((page["total"].clone(), page["limit"].clone(), page["offset"].clone()), (json!(5), json!(2), json!(2)));
+	assert_eq!(names(&page), vec![json!("Robert"), json!("Sandra")]);
+
+	assert_error(&get(&client, "/api/contestants?limit=0").await, Status::UnprocessableEntity, "validation");
+	assert_error(&get(&client, "/api/contestants?season=9").await, Status::NotFound, "not_found");
+}
+
+#[rocket::async_test]
+async fn contestant_profiles_and_photos()
+{
+	let client = client().await;
+	sign_in_gamemaster(&client).await;
+	seed_show(&client).await;
+
+	let profile: Value = json!({ "bio": "Four-time player", "age": 34, "hometown": " Boston ", "occupation": "", "prior_seasons": ["Marquesas", " ", "All-Stars"] });
+	let (status, rob) = put(&client, "/api/contestants/1/profile", profile).await;
+	assert_eq!(status, Status::Ok);
+	assert_eq!((rob["age"].clone(), rob["hometown"].clone(), rob["occupation"].clone()), (json!(34), json!("Boston"), Value::Null));
+	assert_eq!(rob["prior_seasons"], json!(["Marquesas", "All-Stars"]));
+
+	assert_error(&put(&client, "/api/contestants/1/profile", json!({ "age": 0 })).await, Status::UnprocessableEntity, "validation");
+	assert_error(&put(&client, "/api/contestants/9/profile", json!({ "age": 30 })).await, Status::NotFound, "not_found");
+
+	let response = client.post("/api/contestants/1/photo").header(ContentType::PNG).body(b"first photo".as_slice()).dispatch().await;
+	assert_eq!(response.status(), Status::Ok);
+	let first_photo: String = response.into_json::<Value>().await.unwrap()["photo"].as_str().unwrap().to_string();
+
+	let response = client.get(format!("/api/contestant_photos/{}", first_photo)).dispatch().await;
+	assert_eq!((response.status(), response.content_type()), (Status::Ok, Some(ContentType::PNG)));
+	assert_eq!(response.into_bytes().await.unwrap(), b"first photo");
+
+	// A new photo replaces the file of the old one.
+	let response = client.post("/api/contestants/1/photo").header(ContentType::JPEG).body(b"second photo".as_slice()).dispatch().await;
+	let second_photo: String = response.into_json::<Value>().await.unwrap()["photo"].as_str().unwrap().to_string();
+	assert!(second_photo.ends_with(".jpg"));
+	assert_eq!(client.get(format!("/api/contestant_photos/{}", first_photo)).dispatch().await.status(), Status::NotFound);
+
+	let (_, cast) = get(&client, "/api/contestants/on_show?game_show_id=1").await;
+	assert_eq!((cast[0]["photo"].clone(), cast[0]["hometown"].clone()), (json!(second_photo), json!("Boston")));
+
+	let response = client.post("/api/contestants/1/photo").header(ContentType::Plain).body("not an image").dispatch().await;
+	assert_eq!(response.status(), Status::UnprocessableEntity);
+	let response = client.post("/api/contestants/9/photo").header(ContentType::PNG).body(b"photo".as_slice()).dispatch().await;
+	assert_eq!(response.status(), Status::NotFound);
+
+	let (status, rob) = delete(&client, "/api/contestants/1/photo").await;
+	assert_eq!((status, rob["photo"].clone()), (Status::Ok, Value::Null));
+	assert_eq!(client.get(format!("/api/contestant_photos/{}", second_photo)).dispatch().await.status(), Status::NotFound);
+	assert_error(&delete(&client, "/api/contestants/1/photo").await, Status::NotFound, "not_found");
+}
+
+#[rocket::async_test]
+async fn contestant_history_covers_every_season_played()
+{
+	let client = client().await;
+	sign_in_gamemaster(&client).await;
+	seed_show(&client).await;
+	let sandra = add_player(&client, "Sandra").await;
+	post(&client, "/api/leagues", json!({ "name": "Office pool", "id_showseason": 1 })).await;
+	post(&client, &format!("/api/leagues?user_id={}&league_id=1", sandra), json!({})).await;
+
+	post(&client, "/api/gameshows", json!({ "name": "Winners at War" })).await;
+	post(&client, "/api/contestants/enroll", json!({ "contestant_id": 1, "game_show_id": 2, "nickname": "" })).await;
+
+	login(&client, "sandra@example.com").await;
+	post(&client, "/api/leagues/set_pick?league_id=1&round_number=1&contestant_id=1&rank_pick=2", json!({})).await;
+	post(&client, "/api/leagues/set_pick?league_id=1&round_number=1&contestant_id=2&rank_pick=1", json!({})).await;
+
+	login(&client, GAMEMASTER_EMAIL).await;
+	post(&client, "/api/contestants/elim", json!({ "contestant_id": 1, "game_show_id": 1, "round_number": 1 })).await;
+
+	let (status, history) = get(&client, "/api/contestants/1/history").await;
+	assert_eq!(status, Status::Ok);
+	assert_eq!(history["contestant"]["name"], "Rob");
+	assert_eq!((history["times_picked"].clone(), history["times_ranked"].clone(), history["average_rank"].clone()), (json!(0), json!(1), json!(2.0)));
+
+	let seasons = history["seasons"].as_array().unwrap();
+	assert_eq!(seasons.len(), 2);
+	assert_eq!((seasons[0]["game_show_name"].clone(), seasons[0]["nickname"].clone()), (json!("Heroes vs Villains"), json!("ROB")));
+	assert_eq!((seasons[0]["status"].clone(), seasons[0]["eliminated_in_round"].clone()), (json!("voted_out"), json!(1)));
+	assert_eq!((seasons[0]["finish"].clone(), seasons[0]["cast_size"].clone()), (json!(2), json!(2)));
+	assert_eq!((seasons[1]["game_show_name"].clone(), seasons[1]["finish"].clone()), (json!("Winners at War"), Value::Null));
+	assert_eq!((seasons[1]["times_ranked"].clone(), seasons[1]["average_rank"].clone()), (json!(0), Value::Null));
+
+	let parvati = get(&client, "/api/contestants/2/history").await.1["seasons"][0].clone();
+	assert_eq!((parvati["times_picked"].clone(), parvati["average_rank"].clone(), parvati["finish"].clone()), (json!(1), json!(1.0), Value::Null));
+
+	put(&client, "/api/gameshows/1", json!({ "name": "Heroes vs Villains", "status": "finished" })).await;
+	assert_eq!(get(&client, "/api/contestants/2/history").await.1["seasons"][0]["finish"], json!(1));
+	assert_error(&get(&client, "/api/contestants/9/history").await, Status::NotFound, "not_found");
+}
@@ -0,0 +1,216 @@
+#![allow(clippy::needless_return)]
+
+mod common;
+
+use common::{ add_player, assert_error, client, delete, get, login, post, put, seed_show, sign_in_gamemaster };
+use rocket::http::Status;
+use serde_json::json;
+
+#[rocket::async_test]
+async fn gamemasters_manage_league_membership()
+{
+	let client = client().await;
+	sign_in_gamemaster(&client).await;
+	seed_show(&client).await;
+	let sandra = add_player(&client, "Sandra").await;
+
+	assert_eq!(post(&client, "/api/leagues", json!({ "name": "Office pool", "id_showseason": 1 })).await.0, Status::Ok);
+	assert_eq!(get(&client, "/api/leagues/from_season?id_showseason=1").await.1, json!([{ "id": 1, "name": "Office pool", "id_showseason": 1 }]));
+
+	assert_eq!(post(&client, &format!("/api/leagues?user_id={}&league_id=1", sandra), json!({})).await.0, Status::Ok);
+	assert_error(&post(&client, &format!("/api/leagues?user_id={}&league_id=1", sandra), json!({})).await, Status::Conflict, "conflict");
+	assert_error(&post(&client, "/api/leagues?user_id=42&league_id=1", json!({})).await, Status::UnprocessableEntity, "validation");
+	assert_eq!(get(&client, &format!("/api/users/{}/leagues", sandra)).await.1[0]["id"], 1);
+
+	assert_eq!(delete(&client, &format!("/api/leagues?user_id={}&league_id=1", sandra)).await.0, Status::Ok);
+	assert_error(&delete(&client, &format!("/api/leagues?user_id={}&league_id=1", sandra)).await, Status::NotFound, "not_found");
+	assert_eq!(get(&client, &format!("/api/users/{}/leagues", sandra)).await.1, json!([]));
+
+	assert_eq!(delete(&client, "/api/leagues/1").await.0, Status::Ok);
+	assert_error(&delete(&client, "/api/leagues/1").await, Status::NotFound, "not_found");
+	assert_eq!(get(&client, "/api/leagues/from_season?id_showseason=1").await.1, json!([]));
+}
+
+#[rocket::async_test]
+async fn players_set_picks_while_the_round_is_open()
+{
+	let client = client().await;
+	sign_in_gamemaster(&client).await;
+	seed_show(&client).await;
+	let sandra = add_player(&client, "Sandra").await;
+	post(&client, "/api/leagues", json!({ "name": "Office pool", "id_showseason": 1 })).await;
+	post(&client, &format!("/api/leagues?user_id={}&league_id=1", sandra), json!({})).await;
+	post(&client, "/api/gameshows/1/rounds", json!({ "round_number": 2, "title": "Episode 2", "status": "locked" })).await;
+
+	post(&client, "/api/auth/logout", json!({})).await;
+	assert_error(&post(&client, "/api/leagues/set_pick?league_id=1&round_number=1&contestant_id=1&rank_pick=1", json!({})).await, Status::Unauthorized, "unauthorized");
+
+	login(&client, "sandra@example.com").await;
+	assert_eq!(post(&client, "/api/leagues/set_pick?league_id=1&round_number=1&contestant_id=1&rank_pick=1", json!({})).await.0, Status::Ok);
+	assert_eq!(post(&client, "/api/leagues/set_pick?league_id=1&round_number=1&contestant_id=1&rank_pick=2", json!({})).await.0, Status::Ok);
+	assert_error(&post(&client, "/api/leagues/set_pick?league_id=1&round_number=2&contestant_id=1&rank_pick=1", json!({})).await, Status::Conflict, "conflict");
+	assert_error(&post(&client, "/api/leagues/set_pick?league_id=1&round_number=8&contestant_id=1&rank_pick=1", json!({})).await, Status::UnprocessableEntity, "validation");
+	assert_error(&post(&client, "/api/leagues/set_pick?league_id=1&round_number=1&contestant_id=9&rank_pick=1", json!({})).await, Status::UnprocessableEntity, "validation");
+
+	// A contestant who exists but was never cast on this season can't be picked either.
+	login(&client, common::GAMEMASTER_EMAIL).await;
+	post(&client, "/api/contestants", json!({ "name": "Tony", "round_number": -1, "was_medically_evacuated": false })).await;
+	login(&client, "sandra@example.com").await;
+	assert_error(&post(&client, "/api/leagues/set_pick?league_id=1&round_number=1&contestant_id=3&rank_pick=1", json!({})).await, Status::UnprocessableEntity, "validation");
+}
+
+#[rocket::async_test]
+async fn only_league_members_can_pick()
+{
+	let client = client().await;
+	sign_in_gamemaster(&client).await;
+	seed_show(&client).await;
+	let sandra = add_player(&client, "Sandra").await;
+	add_player(&client, "Tony").await;
+	post(&client, "/api/leagues", json!({ "name": "Office pool", "id_showseason": 1 })).await;
+	post(&client, &format!("/api/leagues?user_id={}&league_id=1", sandra), json!({})).await;
+
+	login(&client, "tony@example.com").await;
+	assert_error(&post(&client, "/api/leagues/set_pick?league_id=1&round_number=1&contestant_id=1&rank_pick=1", json!({})).await, Status::Forbidden, "forbidden");
+
+	let (status, scores) = get(&client, "/api/leagues/1/scores").await;
+	assert_eq!(status, Status::Ok);
+	assert_eq!(scores.as_array().unwrap().len(), 1);
+}
+
+#[rocket::async_test]
+async fn scores_and_standings_follow_eliminations()
+{
+	let client = client().await;
+	sign_in_gamemaster(&client).await;
+	seed_show(&client).await;
+	let sandra = add_player(&client, "Sandra").await;
+	let tony = add_player(&client, "Tony").await;
+	post(&client, "/api/leagues", json!({ "name": "Office pool", "id_showseason": 1 })).await;
+	for user_id in [sandra, tony]
+	{
+		post(&client, &format!("/api/leagues?user_id={}&league_id=1", user_id), json!({})).await;
+	}
+
+	// Sandra calls Rob's boot, Tony expects Parvati to go.
+	login(&client, "sandra@example.com").await;
+	post(&client, "/api/leagues/set_pick?league_id=1&round_number=1&contestant_id=2&rank_pick=1", json!({})).await;
+	post(&client, "/api/leagues/set_pick?league_id=1&round_number=1&contestant_id=1&rank_pick=2", json!({})).await;
+	login(&client, "tony@example.com").await;
+	post(&client, "/api/leagues/set_pick?league_id=1&round_number=1&contestant_id=1&rank_pick=1", json!({})).await;
+	post(&client, "/api/leagues/set_pick?league_id=1&round_number=1&contestant_id=2&rank_pick=2", json!({})).await;
+
+	login(&client, common::GAMEMASTER_EMAIL).await;
+	post(&client, "/api/contestants/elim", json!({ "contestant_id": 1, "game_show_id": 1, "round_number": 1 })).await;
+
+	let (status, scores) = get(&client, "/api/leagues/1/scores").await;
+	assert_eq!(status, Status::Ok);
+	assert_eq!(scores[0]["total_points"], 4);
+	assert_eq!(scores[1]["total_points"], 1);
+
+	let (status, standings) = get(&client, "/api/leagues/1/standings").await;
+	assert_eq!(status, Status::Ok);
+	assert_eq!((standings[0]["user_name"].clone(), standings[0]["rank"].clone()), (json!("Sandra"), json!(1)));
+	assert_eq!((standings[1]["user_name"].clone(), standings[1]["rank"].clone()), (json!("Tony"), json!(2)));
+
+	assert_error(&get(&client, "/api/leagues/9/standings").await, Status::NotFound, "not_found");
+
+	// Undoing the boot puts Rob back in the game and hands back the rescored league.
+	let (status, reinstatement) = post(&client, "/api/contestants/reinstate", json!({ "contestant_id": 1, "game_show_id": 1, "round_number": 1 })).await;
+	assert_eq!(status, Status::Ok);
+	assert_eq!(reinstatement["contestant"]["status"], "active");
+	assert_eq!(reinstatement["rescored_leagues"][0]["league_id"], 1);
+	let (_, standings) = get(&client, "/api/leagues/1/standings").await;
+	assert_eq!(reinstatement["rescored_leagues"][0]["standings"], standings);
+	assert_eq!(standings[0]["total_points"], standings[1]["total_points"]);
+
+	assert_error(&post(&client, "/api/contestants/reinstate", json!({ "contestant_id": 1, "game_show_id": 1, "round_number": 1 })).await, Status::NotFound, "not_found");
+}
+
+#[rocket::async_test]
+async fn scoring_rules_fall_back_to_the_defaults()
+{
+	let client = client().await;
+	sign_in_gamemaster(&client).await;
+	seed_show(&client).await;
+	post(&client, "/api/leagues", json!({ "name": "Office pool", "id_showseason": 1 })).await;
+
+	let (status, defaults) = get(&client, "/api/leagues/1/scoring_rules").await;
+	assert_eq!(status, Status::Ok);
+	assert_eq!(defaults["id"], json!(null));
+	assert_eq!(defaults["boot_prediction_bonus"], 3);
+
+	let rules = json!({ "survival_points": 2, "boot_prediction_bonus": 5, "medevac_points": 1, "medevac_counts_as_boot": true });
+	let (status, saved) = post(&client, "/api/leagues/1/scoring_rules", rules.clone()).await;
+	assert_eq!(status, Status::Ok);
+	assert_eq!(saved["survival_points"], 2);
+	assert_error(&post(&client, "/api/leagues/1/scoring_rules", rules.clone()).await, Status::Conflict, "conflict");
+	assert_error(&post(&client, "/api/leagues/9/scoring_rules", rules.clone()).await, Status::NotFound, "not_found");
+
+	let (status, updated) = put(&client, "/api/leagues/1/scoring_rules", json!({ "survival_points": 3, "boot_prediction_bonus": 5, "medevac_points": 1, "medevac_counts_as_boot": true })).await;
+	assert_eq!(status, Status::Ok);
+	assert_eq!(updated["survival_points"], 3);
+
+	assert_eq!(delete(&client, "/api/leagues/1/scoring_rules").await.0, Status::Ok);
+	assert_eq!(get(&client, "/api/leagues/1/scoring_rules").await.1, defaults);
+	assert_error(&put(&client, "/api/leagues/1/scoring_rules", rules).await, Status::NotFound, "not_found");
+}
+
+#[rocket::async_test]
+async fn seasons_export_and_restore_into_a_fresh_database()
+{
+	let client = client().await;
+	sign_in_gamemaster(&client).await;
+	seed_show(&client).await;
+	let sandra = add_player(&client, "Sandra").await;
+	post(&client, "/api/leagues", json!({ "name": "Office pool", "id_showseason": 1 })).await;
+	post(&client, &format!("/api/leagues?user_id={}&league_id=1", sandra), json!({})).await;
+	post(&client, "/api/leagues/1/scoring_rules", json!({ "survival_points": 2, "boot_prediction_bonus": 5, "medevac_points": 0, "medevac_counts_as_boot": false })).await;
+	login(&client, "sandra@example.com").await;
+	post(&client, "/api/leagues/set_pick?league_id=1&round_number=1&contestant_id=2&rank_pick=1", json!({})).await;
+	post(&client, "/api/leagues/set_pick?league_id=1&round_number=1&contestant_id=1&rank_pick=2", json!({})).await;
+	login(&client, common::GAMEMASTER_EMAIL).await;
+	put(&client, "/api/gameshows/1/rounds/1", json!({ "round_number": 1, "title": "Episode 1", "status": "scored" })).await;
+	post(&client, "/api/contestants/elim", json!({ "contestant_id": 1, "game_show_id": 1, "round_number": 1 })).await;
+
+	assert_error(&get(&client, "/api/gameshows/9/export").await, Status::NotFound, "not_found");
+	let (status, archive) = get(&client, "/api/gameshows/1/export").await;
+	assert_eq!(status, Status::Ok);
+	assert_eq!(archive["version"], 2);
+	assert_eq!(archive["leagues"][0]["picks"].as_array().unwrap().len(), 2);
+	let (_, original_standings) = get(&client, "/api/leagues/1/standings").await;
+
+	// A fresh database whose ids will not line up with the archive's.
+	let fresh = client_with_other_data().await;
+	let (status, restored) = post(&fresh, "/api/gameshows/restore", archive.clone()).await;
+	assert_eq!(status, Status::Ok, "{}", restored);
+	assert_eq!(restored, json!({ "game_show_id": 2, "rounds": 1, "contestants": 2, "tribes": 0, "leagues": 1, "members": 1, "picks": 2 }));
+
+	let (_, cast) = get(&fresh, "/api/contestants/on_show?game_show_id=2").await;
+	let rob = cast.as_array().unwrap().iter().find(|contestant| contestant["name"] == "Rob").unwrap();
+	assert_eq!(rob["round_number"], 1);
+	let (_, leagues) = get(&fresh, "/api/leagues/from_season?id_showseason=2").await;
+	let league_id = leagues[0]["id"].as_i64().unwrap();
+	assert_eq!(get(&fresh, &format!("/api/leagues/{}/scoring_rules", league_id)).await.1["survival_points"], 2);
+
+	let (_, restored_standings) = get(&fresh, &format!("/api/leagues/{}/standings", league_id)).await;
+	assert_eq!(restored_standings[0]["user_name"], original_standings[0]["user_name"]);
+	assert_eq!(restored_standings[0]["total_points"], original_standings[0]["total_points"]);
+
+	let mut future_archive = archive.clone();
+	future_archive["version"] = json!(99);
+	assert_error(&post(&fresh, "/api/gameshows/restore", future_archive).await, Status::UnprocessableEntity, "validation");
+	assert_error(&post(&fresh, "/api/gameshows/restore", json!({ "version": 1 })).await, Status::UnprocessableEntity, "validation");
+}
+
+// A gamemaster session on a store that already has a season, a contestant and a user in it.
+async fn client_with_other_data() -> rocket::local::asynchronous::Client
+{
+	let client = client().await;
+	sign_in_gamemaster(&client).await;
+	post(&client, "/api/gameshows", json!({ "name": "Borneo" })).await;
+	post(&client, "/api/contestants", json!({ "name": "Richard", "round_number": -1, "was_medically_evacuated": false })).await;
+	add_player(&client, "Kelly").await;
+
+	return client;
+}
@@ -0,0 +1,148 @@
+#![allow(clippy::needless_return)]
+
+mod common;
+
+use common::{ GAMEMASTER_EMAIL, PASSWORD, add_player, assert_error, client, delete, get, login, post, put, sign_in_gamemaster };
+use rocket::http::{ Header, Status };
+use serde_json::json;
+
+#[rocket::async_test]
+async fn only_the_first_account_can_make_itself_a_gamemaster()
+{
+	let client = client().await;
+	sign_in_gamemaster(&client).await;
+	post(&client, "/api/auth/logout", json!({})).await;
+
+	let second_gamemaster = post(&client, "/api/users", json!({ "name": "Mark", "email": "mark@example.com", "account_type": "GameMaster", "password": PASSWORD })).await;
+	assert_error(&second_gamemaster, Status::Forbidden, "forbidden");
+
+	add_player(&client, "Sandra").await;
+	login(&client, GAMEMASTER_EMAIL).await;
+	let (status, users) = get(&client, "/api/users").await;
+	assert_eq!(status, Status::Ok);
+	assert_eq!(users.as_array().unwrap().len(), 2);
+	assert!(users[1].get("password").is_none());
+}
+
+#[rocket::async_test]
+async fn portal_signups_are_players()
+{
+	let client = client().await;
+	sign_in_gamemaster(&client).await;
+	post(&client, "/api/auth/logout", json!({})).await;
+
+	for (name, account_type) in [("Sandra", "Player"), ("Russell", "Developer")]
+	{
+		let email: String = format!("{}@example.com", name.to_lowercase());
+		let (status, _) = post(&client, "/api/users", json!({ "name": name, "email": email, "account_type": account_type, "password": PASSWORD })).await;
+		assert_eq!(status, Status::Ok);
+
+		assert_eq!(login(&client, &email).await.0, Status::Ok);
+		assert_error(&post(&client, "/api/gameshows", json!({ "name": "Borneo" })).await, Status::Forbidden, "forbidden");
+	}
+}
+
+#[rocket::async_test]
+async fn sessions_follow_login_and_logout()
+{
+	let client = client().await;
+	assert_error(&get(&client, "/api/auth/session").await, Status::Unauthorized, "unauthorized");
+
+	let gamemaster_id = sign_in_gamemaster(&client).await;
+	let (status, session) = get(&client, "/api/auth/session").await;
+	assert_eq!(status, Status::Ok);
+	assert_eq!(session["id"], gamemaster_id);
+	assert_eq!(session["email"], GAMEMASTER_EMAIL);
+
+	let (status, _) = post(&client, "/api/auth/logout", json!({})).await;
+	assert_eq!(status, Status::NoContent);
+	assert_error(&get(&client, "/api/auth/session").await, Status::Unauthorized, "unauthorized");
+
+	let wrong_password = post(&client, "/api/auth/login", json!({ "email": GAMEMASTER_EMAIL, "password": "wrong" })).await;
+	assert_error(&wrong_password, Status::Unauthorized, "unauthorized");
+	assert_error(&login(&client, "nobody@example.com").await, Status::Unauthorized, "unauthorized");
+}
+
+#[rocket::async_test]
+async fn gamemasters_edit_and_delete_users()
+{
+	let client = client().await;
+	sign_in_gamemaster(&client).await;
+	let sandra = add_player(&client, "Sandra").await;
+
+	let (status, users) = put(&client, &format!("/api/users/{}", sandra), json!({ "name": "Queen Sandra", "email": "sandra@example.com", "account_type": "Player" })).await;
+	assert_eq!(status, Status::Ok);
+	assert_eq!(users[1]["name"], "Queen Sandra");
+
+	assert_error(&put(&client, "/api/users/99", json!({ "name": "Nobody", "email": "nobody@example.com", "account_type": "Player" })).await, Status::NotFound, "not_found");
+	assert_error(&delete(&client, "/api/users/99").await, Status::NotFound, "not_found");
+
+	let (status, users) = delete(&client, &format!("/api/users/{}", sandra)).await;
+	assert_eq!(status, Status::Ok);
+	assert_eq!(users.as_array().unwrap().len(), 1);
+}
+
+#[rocket::async_test]
+async fn players_cannot_manage_users()
+{
+	let client = client().await;
+	sign_in_gamemaster(&client).await;
+	let sandra = add_player(&client, "Sandra").await;
+	assert_eq!(login(&client, "sandra@example.com").await.0, Status::Ok);
+
+	assert_error(&delete(&client, "/api/users/1").await, Status::Forbidden, "forbidden");
+	assert_error(&put(&client, &format!("/api/users/{}", sandra), json!({ "name": "Sandra", "email": "sandra@example.com", "account_type": "GameMaster" })).await, Status::Forbidden, "forbidden");
+
+	post(&client, "/api/auth/logout", json!({})).await;
+	assert_error(&delete(&client, "/api/users/1").await, Status::Unauthorized, "unauthorized");
+}
+
+#[rocket::async_test]
+async fn malformed_bodies_get_the_error_body()
+{
+	let client = client().await;
+	assert_error(&post(&client, "/api/users", json!({ "name": 5 })).await, Status::UnprocessableEntity, "validation");
+	assert_error(&post(&client, "/api/auth/login", json!({ "email": GAMEMASTER_EMAIL })).await, Status::UnprocessableEntity, "validation");
+
+	sign_in_gamemaster(&client).await;
+	for uri in ["/api/contestants", "/api/gameshows", "/api/gameshows/1/rounds", "/api/leagues", "/api/leagues/1/scoring_rules"]
+	{
+		assert_error(&post(&client, uri, json!([])).await, Status::UnprocessableEntity, "validation");
+	}
+
+	for uri in ["/api/users/1", "/api/gameshows/1/rounds/1", "/api/leagues/1/scoring_rules"]
+	{
+		assert_error(&put(&client, uri, json!("not an object")).await, Status::UnprocessableEntity, "validation");
+	}
+}
+
+#[rocket::async_test]
+async fn users_and_their_leagues_are_only_shown_to_who_may_see_them()
+{
+	let client = client().await;
+	let gamemaster = sign_in_gamemaster(&client).await;
+	let sandra = add_player(&client, "Sandra").await;
+	post(&client, "/api/auth/logout", json!({})).await;
+
+	assert_error(&get(&client, "/api/users").await, Status::Unauthorized, "unauthorized");
+	assert_error(&get(&client, &format!("/api/users/{}/leagues", sandra)).await, Status::Unauthorized, "unauthorized");
+
+	login(&client, "sandra@example.com").await;
+	assert_error(&get(&client, "/api/users").await, Status::Forbidden, "forbidden");
+	assert_eq!(get(&client, &format!("/api/users/{}/leagues", sandra)).await.0, Status::Ok);
+	assert_error(&get(&client, &format!("/api/users/{}/leagues", gamemaster)).await, Status::Forbidden, "forbidden");
+
+	login(&client, GAMEMASTER_EMAIL).await;
+	assert_eq!(get(&client, &format!("/api/users/{}/leagues", sandra)).await.0, Status::Ok);
+}
+
+#[rocket::async_test]
+async fn only_the_frontend_origin_is_trusted_with_credentials()
+{
+	let client = client().await;
+	for (origin, allowed) in [("http://127.0.0.1:8080", Some("http://127.0.0.1:8080")), ("https://evil.example.com", None)]
+	{
+		let response = client.get("/api/gameshows").header(Header::new("Origin", origin)).dispatch().await;
+		assert_eq!(response.headers().get_one("Access-Control-Allow-Origin"), allowed);
+	}
+}
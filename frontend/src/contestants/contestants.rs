@@ -1,9 +1,13 @@
 use yew::prelude::*;
-use gloo::net::http::Request;
+use gloo::net::http::{ Request, RequestCredentials };
 use serde::{ Deserialize, Serialize };
-use wasm_bindgen_futures::spawn_local;
+use wasm_bindgen_futures::{ JsFuture, spawn_local };
+use web_sys::js_sys;
 
-use crate::web_server::PLATFORM_URL;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::web_server::{ PLATFORM_URL, error_message };
 
 use crate::logger;
 
@@ -11,10 +15,35 @@ use crate::logger;
 pub struct ContestantState
 {
 	pub name: String,
+	// Tells apart contestants who share a name, e.g. "Mariano" for one Rob. Usually empty.
+	#[serde(default)]
+	pub disambiguator: String,
 	pub id: Option<i32>,
 	pub id_showseason: Option<i32>,
 	pub round_number : Option<i32>,
-	pub was_medically_evacuated: Option<bool>
+	pub was_medically_evacuated: Option<bool>,
+	// Where they stand in the game (active, voted_out, jury, ...), from the season's event log.
+	#[serde(default)]
+	pub status: Option<String>,
+	// The tribe they are on now, as reported by the backend for contestants on a show.
+	#[serde(default)]
+	pub tribe: Option<String>,
+	#[serde(default)]
+	pub tribe_color: Option<String>,
+	// Their profile, for players to read while ranking.
+	#[serde(default)]
+	pub bio: Option<String>,
+	#[serde(default)]
+	pub age: Option<i32>,
+	#[serde(default)]
+	pub hometown: Option<String>,
+	#[serde(default)]
+	pub occupation: Option<String>,
+	#[serde(default)]
+	pub prior_seasons: Vec<String>,
+	// File name of their photo on the backend, see photo_url().
+	#[serde(default)]
+	pub photo: Option<String>
 }
 
 impl ContestantState
@@ -24,11 +53,61 @@ impl ContestantState
 		ContestantState
 		{
 			name : name_in,
+			disambiguator : String::new(),
 			id : id_in,
 			id_showseason : id_showseason_in,
 			round_number: Some(-1),
-			was_medically_evacuated: Some(false)
+			was_medically_evacuated: Some(false),
+			status: None,
+			tribe: None,
+			tribe_color: None,
+			bio: None,
+			age: None,
+			hometown: None,
+			occupation: None,
+			prior_seasons: Vec::new(),
+			photo: None
+		}
+	}
+
+	pub fn photo_url(&self) -> Option<String>
+	{
+		return self.photo.as_ref().map(|photo: &String| format!(concat!(PLATFORM_URL!(), "/contestant_photos/{}"), photo));
+	}
+
+	// "34, Boston, Construction" with whatever parts are known.
+	pub fn profile_summary(&self) -> String
+	{
+		let parts: Vec<String> = [self.age.map(|age: i32| age.to_string()), self.hometown.clone(), self.occupation.clone()]
+			.into_iter()
+			.flatten()
+			.collect();
+
+		return parts.join(", ");
+	}
+
+	// Body for PUT /contestants/<id>/profile.
+	pub fn convert_to_profile_json(&self) -> serde_json::Value
+	{
+		return serde_json::json!(
+		{
+			"bio": self.bio,
+			"age": self.age,
+			"hometown": self.hometown,
+			"occupation": self.occupation,
+			"prior_seasons": self.prior_seasons
+		});
+	}
+
+	// "Rob (Mariano)", or just the name when there is no disambiguator.
+	pub fn display_name(&self) -> String
+	{
+		if self.disambiguator.is_empty()
+		{
+			return self.name.clone();
 		}
+
+		return format!("{} ({})", self.name, self.disambiguator);
 	}
 
 	pub fn to_string(&self) -> String
@@ -41,18 +120,76 @@ impl ContestantState
 			self.was_medically_evacuated.unwrap_or_default());
 	}
 
-	pub fn convert_to_json(&self) -> serde_json::Value
+	// Body for the enroll, elim and medevac routes, which only want the ids and the round.
+	pub fn convert_to_action_json(&self) -> serde_json::Value
 	{
-		let json = serde_json::json!(
+		return serde_json::json!(
 		{
-			"name": self.name,
-			"id": self.id,
-			"id_showseason": self.id_showseason,
-			"round_number": self.round_number.unwrap_or_default(),
-			"was_medically_evacuated": self.was_medically_evacuated.unwrap_or_default()
+			"contestant_id": self.id,
+			"game_show_id": self.id_showseason,
+			"round_number": self.round_number
 		});
+	}
+}
+
+// A dot in the contestant's tribe color with the tribe's name, for contestant cards.
+pub fn tribe_badge(contestant: &ContestantState) -> Html
+{
+	let Some(tribe) = contestant.tribe.clone() else
+	{
+		return html! {};
+	};
 
-		return json;
+	let color: String = contestant.tribe_color.clone().unwrap_or("#808080".to_string());
+	html!
+	{
+		<span class="inline-flex items-center text-sm text-gray-400">
+			<span class="inline-block w-3 h-3 rounded-full mr-1" style={format!("background-color: {}", color)}></span>
+			{ tribe }
+		</span>
+	}
+}
+
+// The backend's report on one row of an imported cast list.
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct CastImportResult
+{
+	pub row: usize,
+	pub name: String,
+	pub contestant_id: Option<i32>,
+	pub outcome: String,
+	pub message: Option<String>
+}
+
+// What a contestant did in one round (immunity, reward, idols, votes), which leagues can score.
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct RoundStatsState
+{
+	pub contestant_id: i32,
+	pub round_number: i32,
+	pub won_individual_immunity: bool,
+	pub won_reward: bool,
+	pub idols_found: i32,
+	pub idols_played: i32,
+	pub votes_received: i32,
+	pub cast_correct_vote: bool
+}
+
+impl RoundStatsState
+{
+	pub fn from_default() -> Self
+	{
+		RoundStatsState
+		{
+			contestant_id: -1,
+			round_number: 1,
+			won_individual_immunity: false,
+			won_reward: false,
+			idols_found: 0,
+			idols_played: 0,
+			votes_received: 0,
+			cast_correct_vote: false
+		}
 	}
 }
 
@@ -72,12 +209,14 @@ fn create_contestant(contestant_state: &UseStateHandle<ContestantState>,
 			{
 				let contestant_data: serde_json::Value = serde_json::json!({ 
 					"name": contestant_state.name,
+					"disambiguator": contestant_state.disambiguator,
 					"round_number": contestant_state.round_number,
 					"was_medically_evacuated": contestant_state.was_medically_evacuated
 				});
 
 				let url:&str = concat!(PLATFORM_URL!(), "/contestants");
 				let response: Result<gloo::net::http::Response, gloo::net::Error> = Request::post(url)
+					.credentials(RequestCredentials::Include)
 					.header("Content-Type", "application/json")
 					.body(contestant_data.to_string())
 					.send().await;
@@ -92,11 +231,14 @@ fn create_contestant(contestant_state: &UseStateHandle<ContestantState>,
 							let name = json.get("name").and_then(|v| v.as_str()).unwrap_or("").to_string();
 							let id_showseason = json.get("id_showseason").and_then(|v| v.as_i64()).map(|v| v as i32);
 
-							contestant_state.set(ContestantState::new(id, name.clone(), id_showseason));
-							message.set(format!("Contestant[{}] created successfully. With have ID[{}]", name, id.unwrap_or(-1)));
+							let mut created_contestant: ContestantState = ContestantState::new(id, name, id_showseason);
+							created_contestant.disambiguator = json.get("disambiguator").and_then(|v| v.as_str()).unwrap_or("").to_string();
+							message.set(format!("Contestant[{}] created successfully. With have ID[{}]", created_contestant.display_name(), id.unwrap_or(-1)));
+							contestant_state.set(created_contestant);
 						}
 					}
 
+					Ok(resp) => message.set(error_message(resp, "Failed to create contestant").await),
 					_ => message.set("Failed to create contestant".into()),
 				}
 
@@ -123,7 +265,12 @@ fn select_contestant_by_name(
 
 				logger::logger::log("Select Contestant By Name >>>".to_string() + contestant_state.name.to_string().as_str());
 
-				let url: String = format!(concat!(PLATFORM_URL!(), "/contestants/select?name={}"), contestant_state.name);
+				let mut url: String = format!(concat!(PLATFORM_URL!(), "/contestants/select?name={}"), contestant_state.name);
+				if !contestant_state.disambiguator.is_empty()
+				{
+					url.push_str(&format!("&disambiguator={}", contestant_state.disambiguator));
+				}
+
 				let response: Result<gloo::net::http::Response, gloo::net::Error> = Request::get(&url)
 					.send().await;
 
@@ -131,17 +278,16 @@ fn select_contestant_by_name(
 				{
 					Ok(resp) if resp.ok() =>
 					{
-						if let Ok(json) = resp.json::<serde_json::Value>().await
+						// The whole contestant comes back, profile included, so it can be edited right away.
+						if let Ok(selected_contestant) = resp.json::<ContestantState>().await
 						{
-							let id = json.get("id").and_then(|v| v.as_i64()).map(|v| v as i32);
-							let name = json.get("name").and_then(|v| v.as_str()).unwrap_or("").to_string();
-							let id_showseason = json.get("id_showseason").and_then(|v| v.as_i64()).map(|v| v as i32);
-
-							contestant_state.set(ContestantState::new(id, name.clone(), id_showseason));
-							message.set(format!("Contestant[{}] selected successfully. They have ID[{}]", name, id.unwrap_or(-1)));
+							message.set(format!("Contestant[{}] selected successfully. They have ID[{}]", selected_contestant.display_name(), selected_contestant.id.unwrap_or(-1)));
+							contestant_state.set(selected_contestant);
 						}
 					}
 
+					// Several contestants share the name when this is a conflict, the message says so.
+					Ok(resp) => message.set(error_message(resp, "Failed to select contestant").await),
 					_ => message.set("Failed to select contestant".into()),
 				}
 			});
@@ -149,30 +295,124 @@ fn select_contestant_by_name(
 	};
 }
 
-fn delete_contestant(contestant_state: &UseStateHandle<ContestantState>,
+// One season in a contestant's history, as returned by GET /contestants/<id>/history.
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct SeasonAppearanceState
+{
+	pub game_show_id: i32,
+	pub game_show_name: String,
+	pub nickname: Option<String>,
+	pub status: String,
+	pub eliminated_in_round: Option<i32>,
+	pub was_medically_evacuated: bool,
+	pub finish: Option<i32>,
+	pub cast_size: usize,
+	pub times_picked: usize,
+	pub times_ranked: usize,
+	pub average_rank: Option<f64>
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct ContestantHistoryState
+{
+	pub contestant: ContestantState,
+	pub seasons: Vec<SeasonAppearanceState>,
+	pub times_picked: usize,
+	pub times_ranked: usize,
+	pub average_rank: Option<f64>
+}
+
+// One page of the contestant search, as returned by GET /contestants.
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct ContestantPage
+{
+	pub total: usize,
+	pub contestants: Vec<ContestantState>
+}
+
+// How many suggestions the name box shows while typing.
+pub const SUGGESTION_LIMIT : usize = 8;
+
+// Typing fires a search per keystroke and the answers can come back in any order, so only
+// the answer to the latest query is shown.
+fn search_contestants(suggestions: &UseStateHandle<ContestantPage>,
+	latest_query: &Rc<RefCell<String>>,
 	message: &UseStateHandle<String>) -> Callback<String>
+{
+	return
+	{
+		let message: UseStateHandle<String> = message.clone();
+		let suggestions: UseStateHandle<ContestantPage> = suggestions.clone();
+		let latest_query: Rc<RefCell<String>> = latest_query.clone();
+		Callback::from(move |query: String|
+		{
+			let message: UseStateHandle<String> = message.clone();
+			let suggestions: UseStateHandle<ContestantPage> = suggestions.clone();
+			let latest_query: Rc<RefCell<String>> = latest_query.clone();
+
+			let query: String = query.trim().to_string();
+			*latest_query.borrow_mut() = query.clone();
+
+			if query.is_empty()
+			{
+				suggestions.set(ContestantPage { total: 0, contestants: Vec::new() });
+				return;
+			}
+
+			spawn_local(async move
+			{
+				let encoded_query: String = js_sys::encode_uri_component(&query).into();
+				let url: String = format!(concat!(PLATFORM_URL!(), "/contestants?q={}&limit={}"), encoded_query, SUGGESTION_LIMIT);
+				let response: Result<gloo::net::http::Response, gloo::net::Error> = Request::get(&url).send().await;
+
+				if *latest_query.borrow() != query
+				{
+					return;
+				}
+
+				match response
+				{
+					Ok(resp) if resp.ok() =>
+					{
+						if let Ok(page) = resp.json::<ContestantPage>().await && *latest_query.borrow() == query
+						{
+							suggestions.set(page);
+						}
+					}
+
+					Ok(resp) => message.set(error_message(resp, "Failed to search contestants").await),
+					_ => message.set("Failed to search contestants".into()),
+				}
+			});
+		})
+	};
+}
+
+fn delete_contestant(contestant_state: &UseStateHandle<ContestantState>,
+	message: &UseStateHandle<String>) -> Callback<i32>
 {
 	return
 	{
 		let message: UseStateHandle<String> = message.clone();
 		let contestant_state: UseStateHandle<ContestantState> = contestant_state.clone();
-		Callback::from(move |_|
+		Callback::from(move |contestant_id: i32|
 		{
 			let message: UseStateHandle<String> = message.clone();
 			let contestant_state: UseStateHandle<ContestantState> = contestant_state.clone();
 
 			spawn_local(async move
 			{
-				let url: String = format!(concat!(PLATFORM_URL!(), "/contestants/{}"), contestant_state.name);
-				let response: Result<gloo::net::http::Response, gloo::net::Error> = Request::delete(&url).send().await;
+				let url: String = format!(concat!(PLATFORM_URL!(), "/contestants/{}"), contestant_id);
+				let response: Result<gloo::net::http::Response, gloo::net::Error> = Request::delete(&url).credentials(RequestCredentials::Include).send().await;
 
 				match response
 				{
 					Ok(resp) if resp.ok() =>
 					{
-						message.set(format!("Contestant[{}] deleted successfully", contestant_state.name));
+						message.set(format!("Contestant[{}] deleted successfully", contestant_state.display_name()));
 					}
 
+					Ok(resp) => message.set(error_message(resp, "Failed to delete contestant").await),
 					_ => message.set("Failed to delete contestant".into()),
 				}
 			});
@@ -180,6 +420,135 @@ fn delete_contestant(contestant_state: &UseStateHandle<ContestantState>,
 	};
 }
 
+fn update_contestant_profile(contestant_state: &UseStateHandle<ContestantState>,
+	message: &UseStateHandle<String>) -> Callback<ContestantState>
+{
+	return
+	{
+		let message: UseStateHandle<String> = message.clone();
+		let contestant_state: UseStateHandle<ContestantState> = contestant_state.clone();
+		Callback::from(move |incoming_state: ContestantState|
+		{
+			let message: UseStateHandle<String> = message.clone();
+			let contestant_state: UseStateHandle<ContestantState> = contestant_state.clone();
+
+			let Some(contestant_id) = incoming_state.id else
+			{
+				message.set("Select a contestant before saving their profile".into());
+				return;
+			};
+
+			spawn_local(async move
+			{
+				let url: String = format!(concat!(PLATFORM_URL!(), "/contestants/{}/profile"), contestant_id);
+				let response: Result<gloo::net::http::Response, gloo::net::Error> = Request::put(&url)
+					.credentials(RequestCredentials::Include)
+					.header("Content-Type", "application/json")
+					.body(incoming_state.convert_to_profile_json().to_string())
+					.send().await;
+
+				match response
+				{
+					Ok(resp) if resp.ok() =>
+					{
+						if let Ok(updated_contestant) = resp.json::<ContestantState>().await
+						{
+							message.set(format!("Saved the profile of {}", updated_contestant.display_name()));
+							contestant_state.set(updated_contestant);
+						}
+					}
+
+					Ok(resp) => message.set(error_message(resp, "Failed to save the profile").await),
+					_ => message.set("Failed to save the profile".into()),
+				}
+			});
+		})
+	};
+}
+
+fn upload_contestant_photo(contestant_state: &UseStateHandle<ContestantState>,
+	message: &UseStateHandle<String>) -> Callback<(Option<i32>, web_sys::File)>
+{
+	return
+	{
+		let message: UseStateHandle<String> = message.clone();
+		let contestant_state: UseStateHandle<ContestantState> = contestant_state.clone();
+		Callback::from(move |(contestant_id, file): (Option<i32>, web_sys::File)|
+		{
+			let message: UseStateHandle<String> = message.clone();
+			let contestant_state: UseStateHandle<ContestantState> = contestant_state.clone();
+
+			let Some(contestant_id) = contestant_id else
+			{
+				message.set("Select a contestant before uploading a photo".into());
+				return;
+			};
+
+			spawn_local(async move
+			{
+				// The image goes up as is, the backend picks the file type from the Content-Type.
+				let url: String = format!(concat!(PLATFORM_URL!(), "/contestants/{}/photo"), contestant_id);
+				let response: Result<gloo::net::http::Response, gloo::net::Error> = Request::post(&url)
+					.credentials(RequestCredentials::Include)
+					.header("Content-Type", &file.type_())
+					.body(file.clone())
+					.send().await;
+
+				match response
+				{
+					Ok(resp) if resp.ok() =>
+					{
+						if let Ok(updated_contestant) = resp.json::<ContestantState>().await
+						{
+							message.set(format!("Uploaded [{}] for {}", file.name(), updated_contestant.display_name()));
+							contestant_state.set(updated_contestant);
+						}
+					}
+
+					Ok(resp) => message.set(error_message(resp, "Failed to upload the photo").await),
+					_ => message.set("Failed to upload the photo".into()),
+				}
+			});
+		})
+	};
+}
+
+fn fetch_contestant_history(contestant_history: &UseStateHandle<Option<ContestantHistoryState>>,
+	message: &UseStateHandle<String>) -> Callback<i32>
+{
+	return
+	{
+		let message: UseStateHandle<String> = message.clone();
+		let contestant_history: UseStateHandle<Option<ContestantHistoryState>> = contestant_history.clone();
+		Callback::from(move |contestant_id: i32|
+		{
+			let message: UseStateHandle<String> = message.clone();
+			let contestant_history: UseStateHandle<Option<ContestantHistoryState>> = contestant_history.clone();
+
+			spawn_local(async move
+			{
+				let url: String = format!(concat!(PLATFORM_URL!(), "/contestants/{}/history"), contestant_id);
+				let response: Result<gloo::net::http::Response, gloo::net::Error> = Request::get(&url).send().await;
+
+				match response
+				{
+					Ok(resp) if resp.ok() =>
+					{
+						if let Ok(history) = resp.json::<ContestantHistoryState>().await
+						{
+							message.set(format!("Fetched the history of {}, {} season(s)", history.contestant.display_name(), history.seasons.len()));
+							contestant_history.set(Some(history));
+						}
+					}
+
+					Ok(resp) => message.set(error_message(resp, "Failed to fetch the contestant's history").await),
+					_ => message.set("Failed to fetch the contestant's history".into()),
+				}
+			});
+		})
+	};
+}
+
 fn fetch_contestants_on_show(contestants: &UseStateHandle<Vec<ContestantState>>,
 	message: &UseStateHandle<String>) -> Callback<i32>
 {
@@ -218,6 +587,135 @@ fn fetch_contestants_on_show(contestants: &UseStateHandle<Vec<ContestantState>>,
 	};
 }
 
+fn collect_round_stats(round_stats: &UseStateHandle<Vec<RoundStatsState>>,
+	message: &UseStateHandle<String>) -> Callback<i32>
+{
+	return
+	{
+		let message: UseStateHandle<String> = message.clone();
+		let round_stats: UseStateHandle<Vec<RoundStatsState>> = round_stats.clone();
+		Callback::from(move |game_show_id: i32|
+		{
+			let message: UseStateHandle<String> = message.clone();
+			let round_stats: UseStateHandle<Vec<RoundStatsState>> = round_stats.clone();
+
+			spawn_local(async move
+			{
+				let url: String = format!(concat!(PLATFORM_URL!(), "/gameshows/{}/round_stats"), game_show_id);
+				match Request::get(&url).send().await
+				{
+					Ok(resp) if resp.ok() =>
+					{
+						if let Ok(fetched_stats) = resp.json::<Vec<RoundStatsState>>().await
+						{
+							message.set(format!("Fetched {} round stat line(s) for showseason [{}]", fetched_stats.len(), game_show_id));
+							round_stats.set(fetched_stats);
+						}
+					}
+
+					_ => message.set("Failed to fetch round stats".into()),
+				}
+			});
+		})
+	};
+}
+
+// Records the stats for one contestant and round, replacing any already recorded for it.
+fn record_round_stats(round_stats: &UseStateHandle<Vec<RoundStatsState>>,
+	message: &UseStateHandle<String>) -> Callback<(i32, RoundStatsState)>
+{
+	return
+	{
+		let message: UseStateHandle<String> = message.clone();
+		let round_stats: UseStateHandle<Vec<RoundStatsState>> = round_stats.clone();
+		Callback::from(move | (game_show_id, stats) : (i32, RoundStatsState) |
+		{
+			let message: UseStateHandle<String> = message.clone();
+			let round_stats: UseStateHandle<Vec<RoundStatsState>> = round_stats.clone();
+
+			spawn_local(async move
+			{
+				let url: String = format!(concat!(PLATFORM_URL!(), "/gameshows/{}/round_stats"), game_show_id);
+				let response: Result<gloo::net::http::Response, gloo::net::Error> = Request::post(&url)
+					.credentials(RequestCredentials::Include)
+					.header("Content-Type", "application/json")
+					.body(serde_json::to_string(&stats).unwrap())
+					.send().await;
+
+				match response
+				{
+					Ok(resp) if resp.ok() =>
+					{
+						if let Ok(saved_stats) = resp.json::<Vec<RoundStatsState>>().await
+						{
+							round_stats.set(saved_stats);
+						}
+						message.set(format!("Recorded round [{}] stats for contestant [{}]", stats.round_number, stats.contestant_id));
+					}
+
+					Ok(resp) => message.set(error_message(resp, "Failed to record round stats").await),
+					_ => message.set("Failed to record round stats".into()),
+				}
+			});
+		})
+	};
+}
+
+// Sends a .csv or .json cast list file to the season's import route.
+fn import_cast(import_results: &UseStateHandle<Vec<CastImportResult>>,
+	message: &UseStateHandle<String>) -> Callback<(Option<i32>, web_sys::File)>
+{
+	return
+	{
+		let message: UseStateHandle<String> = message.clone();
+		let import_results: UseStateHandle<Vec<CastImportResult>> = import_results.clone();
+		Callback::from(move |(game_show_id, file): (Option<i32>, web_sys::File)|
+		{
+			let message: UseStateHandle<String> = message.clone();
+			let import_results: UseStateHandle<Vec<CastImportResult>> = import_results.clone();
+
+			let Some(game_show_id) = game_show_id else
+			{
+				message.set("Select a show season before importing a cast list".into());
+				return;
+			};
+
+			spawn_local(async move
+			{
+				let Some(cast_list) = JsFuture::from(file.text()).await.ok().and_then(|text| text.as_string()) else
+				{
+					message.set(format!("Could not read [{}]", file.name()));
+					return;
+				};
+
+				let content_type: &str = if file.name().to_lowercase().ends_with(".csv") { "text/csv" } else { "application/json" };
+				let url: String = format!(concat!(PLATFORM_URL!(), "/gameshows/{}/import"), game_show_id);
+				let response: Result<gloo::net::http::Response, gloo::net::Error> = Request::post(&url)
+					.credentials(RequestCredentials::Include)
+					.header("Content-Type", content_type)
+					.body(cast_list)
+					.send().await;
+
+				match response
+				{
+					Ok(resp) if resp.ok() =>
+					{
+						if let Ok(results) = resp.json::<Vec<CastImportResult>>().await
+						{
+							let failed_rows: usize = results.iter().filter(|result: &&CastImportResult| result.outcome == "failed").count();
+							message.set(format!("Imported [{}] with {} of {} rows failing", file.name(), failed_rows, results.len()));
+							import_results.set(results);
+						}
+					}
+
+					Ok(resp) => message.set(error_message(resp, "Failed to import cast list").await),
+					_ => message.set("Failed to import cast list".into()),
+				}
+			});
+		})
+	};
+}
+
 fn enroll_contestant_onto_show(
 	message: &UseStateHandle<String>) -> Callback<ContestantState>
 {
@@ -232,9 +730,10 @@ fn enroll_contestant_onto_show(
 
 			spawn_local(async move
 			{
-				let contestant_data: serde_json::Value = incoming_state.convert_to_json();
+				let contestant_data: serde_json::Value = incoming_state.convert_to_action_json();
 				let url:&str = concat!(PLATFORM_URL!(), "/contestants/enroll");
 				let response: Result<gloo::net::http::Response, gloo::net::Error> = Request::post(url)
+					.credentials(RequestCredentials::Include)
 					.header("Content-Type", "application/json")
 					.body(contestant_data.to_string())
 					.send().await;
@@ -246,6 +745,7 @@ fn enroll_contestant_onto_show(
 						message.set(format!("Contestant enrolled successfully. [{}]", incoming_state.to_string()).into());
 					}
 
+					Ok(resp) => message.set(error_message(resp, "Failed to enroll contestant onto show").await),
 					_ => message.set("Failed to enroll contestant onto show".into()),
 				}
 			});
@@ -265,9 +765,10 @@ fn eliminiate_contestant_from_show(message: &UseStateHandle<String>) -> Callback
 
 			spawn_local(async move
 			{
-				let contestant_data: serde_json::Value = incoming_state.convert_to_json();
+				let contestant_data: serde_json::Value = incoming_state.convert_to_action_json();
 				let url:&str = concat!(PLATFORM_URL!(), "/contestants/elim");
 				let response: Result<gloo::net::http::Response, gloo::net::Error> = Request::post(url)
+					.credentials(RequestCredentials::Include)
 					.header("Content-Type", "application/json")
 					.body(contestant_data.to_string())
 					.send().await;
@@ -279,6 +780,7 @@ fn eliminiate_contestant_from_show(message: &UseStateHandle<String>) -> Callback
 						message.set(format!("The Tribe Has Spoken 💨. [{}]", incoming_state.to_string()).into());
 					}
 
+					Ok(resp) => message.set(error_message(resp, "Failed to eliminate contestant from show").await),
 					_ => message.set("Failed to eliminate contestant from show".into()),
 				}
 			});
@@ -299,9 +801,10 @@ fn medevac_contestant(message: &UseStateHandle<String>) -> Callback<ContestantSt
 
 			spawn_local(async move
 			{
-				let contestant_data: serde_json::Value = incoming_state.convert_to_json();
+				let contestant_data: serde_json::Value = incoming_state.convert_to_action_json();
 				let url:&str = concat!(PLATFORM_URL!(), "/contestants/medevac");
 				let response: Result<gloo::net::http::Response, gloo::net::Error> = Request::post(url)
+					.credentials(RequestCredentials::Include)
 					.header("Content-Type", "application/json")
 					.body(contestant_data.to_string())
 					.send().await;
@@ -313,6 +816,7 @@ fn medevac_contestant(message: &UseStateHandle<String>) -> Callback<ContestantSt
 						message.set(format!("Contestant medevaced successfully. [{}]", incoming_state.to_string()).into());
 					}
 
+					Ok(resp) => message.set(error_message(resp, "Failed to medevac contestant from show").await),
 					_ => message.set("Failed to medevac contestant from show".into()),
 				}
 			});
@@ -320,6 +824,83 @@ fn medevac_contestant(message: &UseStateHandle<String>) -> Callback<ContestantSt
 	};
 }
 
+// Undoes a boot or medevac entered for the wrong contestant or round. The backend rescores
+// every league on the season and sends the new standings back.
+fn reinstate_contestant(message: &UseStateHandle<String>) -> Callback<ContestantState>
+{
+	return
+	{
+		let message: UseStateHandle<String> = message.clone();
+		Callback::from(move |incoming_state: ContestantState|
+		{
+			let message: UseStateHandle<String> = message.clone();
+
+			spawn_local(async move
+			{
+				let contestant_data: serde_json::Value = incoming_state.convert_to_action_json();
+				let url:&str = concat!(PLATFORM_URL!(), "/contestants/reinstate");
+				let response: Result<gloo::net::http::Response, gloo::net::Error> = Request::post(url)
+					.credentials(RequestCredentials::Include)
+					.header("Content-Type", "application/json")
+					.body(contestant_data.to_string())
+					.send().await;
+
+				match response
+				{
+					Ok(resp) if resp.ok() =>
+					{
+						let rescored_leagues: usize = resp.json::<serde_json::Value>().await
+							.map(|reinstatement: serde_json::Value| reinstatement["rescored_leagues"].as_array().map_or(0, |leagues| leagues.len()))
+							.unwrap_or_default();
+						message.set(format!("{} is back in the game, rescored {} league(s)", incoming_state.name, rescored_leagues));
+					}
+
+					Ok(resp) => message.set(error_message(resp, "Failed to reinstate contestant").await),
+					_ => message.set("Failed to reinstate contestant".into()),
+				}
+			});
+		})
+	};
+}
+
+// The events the gamemaster can log from the portal. Eliminations and medevacs have their own buttons.
+pub const CONTESTANT_EVENT_TYPES : [&str; 5] = ["quit", "returned", "joined_jury", "won_immunity", "found_idol"];
+
+fn record_contestant_event(message: &UseStateHandle<String>) -> Callback<(ContestantState, String)>
+{
+	return
+	{
+		let message: UseStateHandle<String> = message.clone();
+		Callback::from(move | (incoming_state, event_type) : (ContestantState, String) |
+		{
+			let message: UseStateHandle<String> = message.clone();
+
+			spawn_local(async move
+			{
+				let event_data: serde_json::Value = serde_json::json!(
+				{
+					"contestant_id": incoming_state.id,
+					"round_number": incoming_state.round_number,
+					"event_type": event_type
+				});
+
+				let url: String = format!(concat!(PLATFORM_URL!(), "/gameshows/{}/events"), incoming_state.id_showseason.unwrap_or(-1));
+				let response: Result<gloo::net::http::Response, gloo::net::Error> = Request::post(&url)
+					.credentials(RequestCredentials::Include)
+					.header("Content-Type", "application/json")
+					.body(event_data.to_string())
+					.send().await;
+
+				match response
+				{
+					Ok(resp) if resp.ok() => message.set(format!("Recorded [{}] for {} in round [{}]", event_type, incoming_state.name, incoming_state.round_number.unwrap_or_default())),
+					Ok(resp) => message.set(error_message(resp, "Failed to record the event").await),
+					_ => message.set("Failed to record the event".into()),
+				}
+			});
+		})
+	};
+}
 
 #[derive(Clone)]
 pub struct ContestantSystem
@@ -327,30 +908,63 @@ pub struct ContestantSystem
 	pub contestant_state : UseStateHandle<ContestantState>,
 	pub create_contestant: yew::Callback<yew::MouseEvent>,
 	pub select_contestant: yew::Callback<yew::MouseEvent>,
-	pub delete_contestant: Callback<String>,
+	pub delete_contestant: Callback<i32>,
+	pub search_contestants: Callback<String>,
+	pub contestant_suggestions : UseStateHandle<ContestantPage>,
+	pub update_contestant_profile: Callback<ContestantState>,
+	pub upload_contestant_photo: Callback<(Option<i32>, web_sys::File)>,
+	pub fetch_contestant_history: Callback<i32>,
+	pub contestant_history : UseStateHandle<Option<ContestantHistoryState>>,
 	pub fetch_contestants_on_show : Callback<i32>,
 	pub enroll_contestant_onto_show: Callback<ContestantState>,
 	pub eliminate_contestant_from_show: Callback<ContestantState>,
 	pub medevac_contestant_from_show: Callback<ContestantState>,
-	pub contestants_on_show : UseStateHandle<Vec<ContestantState>>
+	pub reinstate_contestant: Callback<ContestantState>,
+	pub record_contestant_event: Callback<(ContestantState, String)>,
+	pub contestant_event_type: UseStateHandle<String>,
+	pub contestants_on_show : UseStateHandle<Vec<ContestantState>>,
+	pub import_cast: Callback<(Option<i32>, web_sys::File)>,
+	pub cast_import_results : UseStateHandle<Vec<CastImportResult>>,
+	pub round_stats_entry : UseStateHandle<RoundStatsState>,
+	pub round_stats : UseStateHandle<Vec<RoundStatsState>>,
+	pub collect_round_stats: Callback<i32>,
+	pub record_round_stats: Callback<(i32, RoundStatsState)>
 }
 
 #[hook]
 pub fn use_compile_contestant_system(message: UseStateHandle<String>) -> ContestantSystem
 {
-	let contestant_state : UseStateHandle<ContestantState> = use_state(|| ContestantState { name: "".to_string(), id: None, id_showseason: None, round_number : Some(-1), was_medically_evacuated: Some(false) });
+	let contestant_state : UseStateHandle<ContestantState> = use_state(|| ContestantState::new(None, "".to_string(), None));
 	let contestants_on_show : UseStateHandle<Vec<ContestantState>> = use_state(|| Vec::new());
+	let cast_import_results : UseStateHandle<Vec<CastImportResult>> = use_state(|| Vec::new());
+	let round_stats_entry : UseStateHandle<RoundStatsState> = use_state(|| RoundStatsState::from_default());
+	let round_stats : UseStateHandle<Vec<RoundStatsState>> = use_state(|| Vec::new());
+	let contestant_suggestions : UseStateHandle<ContestantPage> = use_state(|| ContestantPage { total: 0, contestants: Vec::new() });
+	let contestant_history : UseStateHandle<Option<ContestantHistoryState>> = use_state(|| None);
 
 	let create_contestant : yew::Callback<yew::MouseEvent> = create_contestant(&contestant_state, &message);
 	let select_contestant : yew::Callback<yew::MouseEvent> = select_contestant_by_name(&contestant_state, &message);
-	let delete_contestant : Callback<String> = delete_contestant(&contestant_state, &message);
+	let delete_contestant : Callback<i32> = delete_contestant(&contestant_state, &message);
+	let latest_contestant_query : Rc<RefCell<String>> = use_mut_ref(String::new);
+	let search_contestants : Callback<String> = search_contestants(&contestant_suggestions, &latest_contestant_query, &message);
+	let update_contestant_profile : Callback<ContestantState> = update_contestant_profile(&contestant_state, &message);
+	let upload_contestant_photo : Callback<(Option<i32>, web_sys::File)> = upload_contestant_photo(&contestant_state, &message);
+	let fetch_contestant_history : Callback<i32> = fetch_contestant_history(&contestant_history, &message);
 	let fetch_contestants_on_show : Callback<i32> = fetch_contestants_on_show(&contestants_on_show, &message);
 	let enroll_contestant_onto_show : Callback<ContestantState> = enroll_contestant_onto_show(&message);
 	let eliminate_contestant_from_show : Callback<ContestantState> = eliminiate_contestant_from_show(&message);
 	let medevac_contestant_from_show : Callback<ContestantState> = medevac_contestant(&message);
+	let reinstate_contestant : Callback<ContestantState> = reinstate_contestant(&message);
+	let record_contestant_event : Callback<(ContestantState, String)> = record_contestant_event(&message);
+	let contestant_event_type : UseStateHandle<String> = use_state(|| CONTESTANT_EVENT_TYPES[0].to_string());
+	let import_cast : Callback<(Option<i32>, web_sys::File)> = import_cast(&cast_import_results, &message);
+	let collect_round_stats : Callback<i32> = collect_round_stats(&round_stats, &message);
+	let record_round_stats : Callback<(i32, RoundStatsState)> = record_round_stats(&round_stats, &message);
 
 	return ContestantSystem { contestant_state, create_contestant, select_contestant, delete_contestant,
-		fetch_contestants_on_show,
-		enroll_contestant_onto_show, eliminate_contestant_from_show, medevac_contestant_from_show,
-		contestants_on_show };
+		search_contestants, contestant_suggestions, update_contestant_profile, upload_contestant_photo,
+		fetch_contestant_history, contestant_history, fetch_contestants_on_show,
+		enroll_contestant_onto_show, eliminate_contestant_from_show, medevac_contestant_from_show, reinstate_contestant, record_contestant_event, contestant_event_type,
+		contestants_on_show, import_cast, cast_import_results,
+		round_stats_entry, round_stats, collect_round_stats, record_round_stats };
 }
\ No newline at end of file
@@ -4,6 +4,8 @@ use crate::users::users::UserState;
 use crate::users::users::*;
 use crate::gameshows::gameshows::*;
 use crate::contestants::contestants::*;
+use crate::scoring::scoring::*;
+use crate::session::session::*;
 use crate::logger;
 
 
@@ -11,7 +13,9 @@ pub fn build_gamemaster_portal_page(
 	message: &UseStateHandle<String>,
 	user_system : &UserSystem,
 	gameshow_system : &GameShowSystem,
-	contestant_system : &ContestantSystem
+	contestant_system : &ContestantSystem,
+	scoring_system : &ScoringSystem,
+	session_system : &SessionSystem
 ) -> Html
 {
 	html!
@@ -19,10 +23,40 @@ pub fn build_gamemaster_portal_page(
 		<body class="bg-[#121212]  min-h-screen">
 			<div class="container mx-auto p-4">
 				<h1 class="text-4xl font-bold text-[#FF8C00] mb-4">{ "Game Master Portal" }</h1>
+
+				// The backend refuses changes from anyone who isn't logged in as a gamemaster.
+				if let Some(user) = (*session_system.session_user).clone()
+				{
+					<p class="text-white mb-4">{ format!("Logged in as {} ({})", user.name, user.account_type) }</p>
+				}
+				else
+				{
+					<a href="/login" class="text-blue-500 hover:underline mb-4 block">{ "Log in as a gamemaster to make changes" }</a>
+				}
 			{
 				build_showseason_mangement(gameshow_system, contestant_system)
 			}
 
+			{
+				build_contestant_profile_management(contestant_system)
+			}
+
+			{
+				build_contestant_history(contestant_system)
+			}
+
+			{
+				build_round_management(gameshow_system)
+			}
+
+			{
+				build_tribe_management(gameshow_system, contestant_system)
+			}
+
+			{
+				build_round_stats_management(gameshow_system, contestant_system)
+			}
+
 			{
 				build_user_management(message, user_system, gameshow_system)
 			}
@@ -31,6 +65,10 @@ pub fn build_gamemaster_portal_page(
 				build_league_management(gameshow_system)
 			}
 
+			{
+				build_scoring_rules_management(gameshow_system, scoring_system)
+			}
+
 			</div>
 		</body>
 	}
@@ -144,6 +182,708 @@ fn build_league_management(gameshow_system : &GameShowSystem) -> Html
 	}
 }
 
+fn build_scoring_rules_management(gameshow_system : &GameShowSystem, scoring_system : &ScoringSystem) -> Html
+{
+	let league_id: i32 = gameshow_system.league_state.id.unwrap_or(-1);
+	let rules: ScoringRulesState = (*scoring_system.scoring_rules_state).clone();
+
+	let edit_points = |apply: fn(&mut ScoringRulesState, i32)| -> Callback<InputEvent>
+	{
+		let scoring_rules_state_clone: UseStateHandle<ScoringRulesState> = scoring_system.scoring_rules_state.clone();
+		Callback::from(move |e: InputEvent|
+		{
+			let input = e.target_dyn_into::<web_sys::HtmlInputElement>().unwrap();
+
+			let mut edited_rules: ScoringRulesState = (*scoring_rules_state_clone).clone();
+			apply(&mut edited_rules, input.value().parse::<i32>().unwrap_or(0));
+
+			scoring_rules_state_clone.set(edited_rules);
+		})
+	};
+
+	html!
+	{
+		<div class="mb-4">
+			<h2 class="text-2xl font-bold text-[#FF8C00] mb-2">{ "Scoring Rules" }</h2>
+
+			<button
+				onclick={scoring_system.fetch_scoring_rules.clone().reform(move |_| league_id)}
+				class="bg-gray-500 hover:bg-gray-700 text-white font-bold py-2 px-4 rounded mb-4">
+				{ "Fetch Scoring Rules" }
+			</button>
+
+			<div class="mb-2">
+				<label class="text-white mr-2">{ "Points per surviving pick" }</label>
+				<input type="number"
+					value={rules.survival_points.to_string()}
+					oninput={edit_points(|rules, points| rules.survival_points = points)}
+					class="border rounded px-4 py-2 mr-2"/>
+			</div>
+
+			<div class="mb-2">
+				<label class="text-white mr-2">{ "Bonus for predicting the boot" }</label>
+				<input type="number"
+					value={rules.boot_prediction_bonus.to_string()}
+					oninput={edit_points(|rules, points| rules.boot_prediction_bonus = points)}
+					class="border rounded px-4 py-2 mr-2"/>
+			</div>
+
+			<div class="mb-2">
+				<label class="text-white mr-2">{ "Points for a medevaced pick (negative for a penalty)" }</label>
+				<input type="number"
+					value={rules.medevac_points.to_string()}
+					oninput={edit_points(|rules, points| rules.medevac_points = points)}
+					class="border rounded px-4 py-2 mr-2"/>
+			</div>
+
+			<div class="mb-2">
+				<label class="text-white mr-2">{ "Medevac counts as a correct boot prediction" }</label>
+				<input type="checkbox"
+					checked={rules.medevac_counts_as_boot}
+					onchange={Callback::from(
+					{
+						let scoring_rules_state_clone = scoring_system.scoring_rules_state.clone();
+						move |e: Event|
+						{
+							let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+
+							let mut edited_rules: ScoringRulesState = (*scoring_rules_state_clone).clone();
+							edited_rules.medevac_counts_as_boot = input.checked();
+
+							scoring_rules_state_clone.set(edited_rules);
+						}
+					})}/>
+			</div>
+
+			<div class="mb-2">
+				<label class="text-white mr-2">{ "Points for an individual immunity win" }</label>
+				<input type="number"
+					value={rules.immunity_win_points.to_string()}
+					oninput={edit_points(|rules, points| rules.immunity_win_points = points)}
+					class="border rounded px-4 py-2 mr-2"/>
+			</div>
+
+			<div class="mb-2">
+				<label class="text-white mr-2">{ "Points for a reward win" }</label>
+				<input type="number"
+					value={rules.reward_win_points.to_string()}
+					oninput={edit_points(|rules, points| rules.reward_win_points = points)}
+					class="border rounded px-4 py-2 mr-2"/>
+			</div>
+
+			<div class="mb-2">
+				<label class="text-white mr-2">{ "Points per idol found" }</label>
+				<input type="number"
+					value={rules.idol_found_points.to_string()}
+					oninput={edit_points(|rules, points| rules.idol_found_points = points)}
+					class="border rounded px-4 py-2 mr-2"/>
+			</div>
+
+			<div class="mb-2">
+				<label class="text-white mr-2">{ "Points per idol played" }</label>
+				<input type="number"
+					value={rules.idol_played_points.to_string()}
+					oninput={edit_points(|rules, points| rules.idol_played_points = points)}
+					class="border rounded px-4 py-2 mr-2"/>
+			</div>
+
+			<div class="mb-2">
+				<label class="text-white mr-2">{ "Points per vote received (negative for a penalty)" }</label>
+				<input type="number"
+					value={rules.vote_received_points.to_string()}
+					oninput={edit_points(|rules, points| rules.vote_received_points = points)}
+					class="border rounded px-4 py-2 mr-2"/>
+			</div>
+
+			<div class="mb-2">
+				<label class="text-white mr-2">{ "Points for voting with the majority" }</label>
+				<input type="number"
+					value={rules.correct_vote_points.to_string()}
+					oninput={edit_points(|rules, points| rules.correct_vote_points = points)}
+					class="border rounded px-4 py-2 mr-2"/>
+			</div>
+
+			<button
+				onclick={scoring_system.save_scoring_rules.clone().reform(move |_| league_id)}
+				class="bg-blue-500 hover:bg-blue-700 text-white font-bold py-2 px-4 rounded">
+				{ "Save Scoring Rules" }
+			</button>
+
+			<button
+				onclick={scoring_system.delete_scoring_rules.clone().reform(move |_| league_id)}
+				class="bg-red-500 hover:bg-red-700 text-white font-bold py-2 px-4 rounded">
+				{ "Reset To Default Rules" }
+			</button>
+		</div>
+	}
+}
+
+fn build_round_management(gameshow_system : &GameShowSystem) -> Html
+{
+	let showseason_id: i32 = gameshow_system.gameshow_state.id.unwrap_or(-1);
+
+	let edit_round = |apply: fn(&mut RoundState, String)| -> Callback<InputEvent>
+	{
+		let round_entry_clone: UseStateHandle<RoundState> = gameshow_system.round_entry.clone();
+		Callback::from(move |e: InputEvent|
+		{
+			let input = e.target_dyn_into::<web_sys::HtmlInputElement>().unwrap();
+
+			let mut edited_round: RoundState = (*round_entry_clone).clone();
+			apply(&mut edited_round, input.value());
+
+			round_entry_clone.set(edited_round);
+		})
+	};
+
+	let on_select_status: Callback<Event> =
+	{
+		let round_entry_clone: UseStateHandle<RoundState> = gameshow_system.round_entry.clone();
+		Callback::from(move |e: Event|
+		{
+			let select = e.target_dyn_into::<web_sys::HtmlSelectElement>().unwrap();
+
+			let mut edited_round: RoundState = (*round_entry_clone).clone();
+			edited_round.status = select.value();
+
+			round_entry_clone.set(edited_round);
+		})
+	};
+
+	html!
+	{
+		<div class="mb-4">
+			<h2 class="text-2xl font-bold text-[#FF8C00] mb-2">{ "Rounds" }</h2>
+
+			<button
+				onclick={gameshow_system.collect_rounds.clone().reform(move |_| showseason_id)}
+				class="bg-gray-500 hover:bg-gray-700 text-white font-bold py-2 px-4 rounded mb-4">
+				{ "Fetch Rounds" }
+			</button>
+
+			<div class="mb-2">
+				<input placeholder="Round Number" type="number"
+					value={gameshow_system.round_entry.round_number.to_string()}
+					oninput={edit_round(|round, value| round.round_number = value.parse::<i32>().unwrap_or(1))}
+					class="border rounded px-4 py-2 mr-2"/>
+
+				<input placeholder="Episode Title"
+					value={gameshow_system.round_entry.title.clone()}
+					oninput={edit_round(|round, value| round.title = value)}
+					class="border rounded px-4 py-2 mr-2"/>
+
+				<label class="text-white mr-2">{ "Airs" }</label>
+				<input type="datetime-local"
+					value={gameshow_system.round_entry.air_date.clone().unwrap_or_default()}
+					oninput={edit_round(|round, value| round.air_date = Some(value))}
+					class="border rounded px-4 py-2 mr-2"/>
+
+				<label class="text-white mr-2">{ "Picks lock" }</label>
+				<input type="datetime-local"
+					value={gameshow_system.round_entry.lock_time.clone().unwrap_or_default()}
+					oninput={edit_round(|round, value| round.lock_time = Some(value))}
+					class="border rounded px-4 py-2 mr-2"/>
+
+				<select onchange={on_select_status} class="border rounded px-4 py-2 mr-2">
+				{
+					for ROUND_STATUSES.iter().map(|status: &&str|
+					{
+						html!
+						{
+							<option value={status.to_string()} selected={gameshow_system.round_entry.status == *status}>{ status.to_string() }</option>
+						}
+					})
+				}
+				</select>
+
+				<button
+					onclick={gameshow_system.save_round.clone().reform(move |_| showseason_id)}
+					class="bg-blue-500 hover:bg-blue-700 text-white font-bold py-2 px-4 rounded">
+					{ "Save Round" }
+				</button>
+			</div>
+
+			<ul class="list-disc pl-5">
+			{
+				for (*gameshow_system.rounds).iter().map(|round|
+				{
+					let round_number: i32 = round.round_number;
+					html!
+					{
+						<li class="mb-2">
+							<span class="font-semibold text-[#4a90e2]">
+								{ format!("Round {} {}: airs {}, picks lock {} [{}]",
+									round.round_number,
+									round.title,
+									round.air_date.clone().unwrap_or("TBD".to_string()),
+									round.lock_time.clone().unwrap_or("at air time".to_string()),
+									round.status) }
+							</span>
+
+							<button
+								onclick={gameshow_system.delete_round.clone().reform(move |_| (showseason_id, round_number))}
+								class="ml-4 bg-red-500 hover:bg-red-700 text-white font-bold py-1 px-2 rounded">
+								{ "Delete" }
+							</button>
+						</li>
+					}
+				})
+			}
+			</ul>
+		</div>
+	}
+}
+
+fn build_tribe_management(gameshow_system : &GameShowSystem, contestant_system : &ContestantSystem) -> Html
+{
+	let showseason_id: i32 = gameshow_system.gameshow_state.id.unwrap_or(-1);
+	let contestant_id: i32 = contestant_system.contestant_state.id.unwrap_or(-1);
+
+	let edit_tribe = |apply: fn(&mut TribeState, String)| -> Callback<InputEvent>
+	{
+		let tribe_entry_clone: UseStateHandle<TribeState> = gameshow_system.tribe_entry.clone();
+		Callback::from(move |e: InputEvent|
+		{
+			let input = e.target_dyn_into::<web_sys::HtmlInputElement>().unwrap();
+
+			let mut edited_tribe: TribeState = (*tribe_entry_clone).clone();
+			apply(&mut edited_tribe, input.value());
+
+			tribe_entry_clone.set(edited_tribe);
+		})
+	};
+
+	let on_select_tribe: Callback<Event> =
+	{
+		let assignment_entry_clone: UseStateHandle<TribeAssignmentState> = gameshow_system.tribe_assignment_entry.clone();
+		Callback::from(move |e: Event|
+		{
+			let select = e.target_dyn_into::<web_sys::HtmlSelectElement>().unwrap();
+
+			let mut edited_assignment: TribeAssignmentState = (*assignment_entry_clone).clone();
+			edited_assignment.tribe_id = select.value().parse::<i32>().unwrap_or(-1);
+
+			assignment_entry_clone.set(edited_assignment);
+		})
+	};
+
+	let on_edit_from_round: Callback<InputEvent> =
+	{
+		let assignment_entry_clone: UseStateHandle<TribeAssignmentState> = gameshow_system.tribe_assignment_entry.clone();
+		Callback::from(move |e: InputEvent|
+		{
+			let input = e.target_dyn_into::<web_sys::HtmlInputElement>().unwrap();
+
+			let mut edited_assignment: TribeAssignmentState = (*assignment_entry_clone).clone();
+			edited_assignment.from_round = input.value().parse::<i32>().unwrap_or(1);
+
+			assignment_entry_clone.set(edited_assignment);
+		})
+	};
+
+	html!
+	{
+		<div class="mb-4">
+			<h2 class="text-2xl font-bold text-[#FF8C00] mb-2">{ "Tribes" }</h2>
+
+			<button
+				onclick={gameshow_system.collect_tribes.clone().reform(move |_| showseason_id)}
+				class="bg-gray-500 hover:bg-gray-700 text-white font-bold py-2 px-4 rounded mb-4 mr-2">
+				{ "Fetch Tribes" }
+			</button>
+
+			<button
+				onclick={contestant_system.fetch_contestants_on_show.clone().reform(move |_| showseason_id)}
+				class="bg-gray-500 hover:bg-gray-700 text-white font-bold py-2 px-4 rounded mb-4">
+				{ "Fetch Cast" }
+			</button>
+
+			<div class="mb-2">
+				<input placeholder="Tribe Name"
+					value={gameshow_system.tribe_entry.name.clone()}
+					oninput={edit_tribe(|tribe, value| tribe.name = value)}
+					class="border rounded px-4 py-2 mr-2"/>
+
+				<input type="color"
+					value={gameshow_system.tribe_entry.color.clone()}
+					oninput={edit_tribe(|tribe, value| tribe.color = value.to_uppercase())}
+					class="border rounded h-10 w-16 mr-2"/>
+
+				<button
+					onclick={gameshow_system.create_tribe.clone().reform(move |_| showseason_id)}
+					class="bg-blue-500 hover:bg-blue-700 text-white font-bold py-2 px-4 rounded">
+					{ "Create Tribe" }
+				</button>
+			</div>
+
+			<ul class="list-disc pl-5">
+			{
+				for (*gameshow_system.tribes).iter().map(|tribe|
+				{
+					let tribe_id: i32 = tribe.id.unwrap_or(-1);
+					html!
+					{
+						<li class="mb-2">
+							<span class="inline-block w-3 h-3 rounded-full mr-2" style={format!("background-color: {}", tribe.color)}></span>
+							<span class="font-semibold text-[#4a90e2]">{ format!("{} ({})", tribe.name, tribe.color) }</span>
+
+							<button
+								onclick={gameshow_system.delete_tribe.clone().reform(move |_| (showseason_id, tribe_id))}
+								class="ml-4 bg-red-500 hover:bg-red-700 text-white font-bold py-1 px-2 rounded">
+								{ "Delete" }
+							</button>
+						</li>
+					}
+				})
+			}
+			</ul>
+
+			<div class="mb-2">
+				<select onchange={on_select_tribe} class="border rounded px-4 py-2 mr-2">
+					<option value="" disabled=true selected={gameshow_system.tribe_assignment_entry.tribe_id == -1}>{ "Select a tribe" }</option>
+					{
+						for (*gameshow_system.tribes).iter().map(|tribe|
+						{
+							let tribe_id: i32 = tribe.id.unwrap_or(-1);
+							html!
+							{
+								<option value={tribe_id.to_string()} selected={gameshow_system.tribe_assignment_entry.tribe_id == tribe_id}>{ tribe.name.clone() }</option>
+							}
+						})
+					}
+				</select>
+
+				<label class="text-white mr-2">{ "From round" }</label>
+				<input type="number"
+					value={gameshow_system.tribe_assignment_entry.from_round.to_string()}
+					oninput={on_edit_from_round}
+					class="border rounded px-4 py-2 mr-2"/>
+
+				<button
+					onclick={gameshow_system.assign_contestant_to_tribe.clone().reform(move |_| (showseason_id, contestant_id))}
+					class="bg-green-500 hover:bg-green-700 text-white font-bold py-2 px-4 rounded mr-2">
+					{ format!("Put {} on Tribe", contestant_system.contestant_state.display_name()) }
+				</button>
+
+				<button
+					onclick={gameshow_system.merge_tribes.clone().reform(move |_| showseason_id)}
+					class="bg-orange-500 hover:bg-orange-700 text-white font-bold py-2 px-4 rounded">
+					{ "Merge Everyone Still In" }
+				</button>
+			</div>
+
+			<ul class="flex flex-wrap gap-2">
+			{
+				for contestant_system.contestants_on_show.iter().map(|contestant|
+				{
+					html!
+					{
+						<li class="p-2 rounded-lg bg-[#1e1e1e] border border-gray-800 border-l-4 w-[200px]"
+							style={contestant.tribe_color.as_ref().map(|color: &String| format!("border-left-color: {}", color))}>
+							<div class="text-white font-semibold">{ contestant.display_name() }</div>
+							{ tribe_badge(contestant) }
+							<div class="text-sm text-gray-400">{ contestant.status.clone().unwrap_or_default().replace('_', " ") }</div>
+						</li>
+					}
+				})
+			}
+			</ul>
+		</div>
+	}
+}
+
+// Per-round stats for the selected contestant. Saving a round again overwrites it.
+// Blank profile fields are left unset rather than saved as empty text.
+fn optional_text(value: String) -> Option<String>
+{
+	if value.trim().is_empty()
+	{
+		return None;
+	}
+
+	return Some(value);
+}
+
+// The selected contestant's profile and photo, which players see on their ranking cards.
+fn build_contestant_profile_management(contestant_system : &ContestantSystem) -> Html
+{
+	let edit_profile = |apply: fn(&mut ContestantState, String)| -> Callback<InputEvent>
+	{
+		let contestant_state_clone: UseStateHandle<ContestantState> = contestant_system.contestant_state.clone();
+		Callback::from(move |e: InputEvent|
+		{
+			let input = e.target_dyn_into::<web_sys::HtmlInputElement>().unwrap();
+
+			let mut edited_contestant: ContestantState = (*contestant_state_clone).clone();
+			apply(&mut edited_contestant, input.value());
+
+			contestant_state_clone.set(edited_contestant);
+		})
+	};
+
+	let contestant: &ContestantState = &contestant_system.contestant_state;
+
+	html!
+	{
+		<div class="mb-4">
+			<h2 class="text-2xl font-bold text-[#FF8C00] mb-2">{ "Contestant Profile" }</h2>
+			<p class="text-white mb-2">{ format!("Editing {} (select a contestant above first)", contestant.display_name()) }</p>
+
+			if let Some(photo_url) = contestant.photo_url()
+			{
+				<img src={photo_url} alt={contestant.display_name()} class="w-24 h-24 object-cover rounded mb-2"/>
+			}
+
+			<div class="mb-2">
+				<input placeholder="Age" type="number"
+					value={contestant.age.map(|age: i32| age.to_string()).unwrap_or_default()}
+					oninput={edit_profile(|contestant, value| contestant.age = value.parse::<i32>().ok())}
+					class="border rounded px-4 py-2 mr-2 w-24"/>
+
+				<input placeholder="Hometown"
+					value={contestant.hometown.clone().unwrap_or_default()}
+					oninput={edit_profile(|contestant, value| contestant.hometown = optional_text(value))}
+					class="border rounded px-4 py-2 mr-2"/>
+
+				<input placeholder="Occupation"
+					value={contestant.occupation.clone().unwrap_or_default()}
+					oninput={edit_profile(|contestant, value| contestant.occupation = optional_text(value))}
+					class="border rounded px-4 py-2 mr-2"/>
+
+				<input placeholder="Prior seasons, comma separated"
+					value={contestant.prior_seasons.join(",")}
+					oninput={edit_profile(|contestant, value| contestant.prior_seasons = value.split(',').map(str::to_string).collect())}
+					class="border rounded px-4 py-2 mr-2"/>
+			</div>
+
+			<div class="mb-2">
+				<input placeholder="Bio"
+					value={contestant.bio.clone().unwrap_or_default()}
+					oninput={edit_profile(|contestant, value| contestant.bio = optional_text(value))}
+					class="border rounded px-4 py-2 mr-2 w-1/2"/>
+
+				<button
+					onclick={contestant_system.update_contestant_profile.clone().reform(
+					{
+						let contestant_to_send: ContestantState = contestant.clone();
+						move |_| contestant_to_send.clone()
+					})}
+					class="bg-blue-500 hover:bg-blue-700 text-white font-bold py-2 px-4 rounded">
+					{ "Save Profile" }
+				</button>
+			</div>
+
+			<div class="mb-2">
+				<label class="font-semibold mr-2 text-white">{ "Photo (PNG, JPEG, WebP or GIF, up to 5 MiB)" }</label>
+				<input type="file" accept="image/png,image/jpeg,image/webp,image/gif"
+					onchange={Callback::from(
+					{
+						let upload_contestant_photo = contestant_system.upload_contestant_photo.clone();
+						let contestant_id = contestant.id;
+						move |e: Event|
+						{
+							let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+							if let Some(file) = input.files().and_then(|files| files.get(0))
+							{
+								upload_contestant_photo.emit((contestant_id, file));
+							}
+
+							input.set_value("");
+						}
+					})}
+					class="border rounded px-4 py-2 mr-2"
+				/>
+			</div>
+		</div>
+	}
+}
+
+// Every season the selected contestant played, for spotting returning players.
+fn build_contestant_history(contestant_system : &ContestantSystem) -> Html
+{
+	let contestant_id: i32 = contestant_system.contestant_state.id.unwrap_or(-1);
+	let format_rank = |rank: Option<f64>| -> String { rank.map_or("-".to_string(), |rank: f64| format!("{:.1}", rank)) };
+
+	html!
+	{
+		<div class="mb-4">
+			<h2 class="text-2xl font-bold text-[#FF8C00] mb-2">{ "Contestant History" }</h2>
+
+			<button
+				onclick={contestant_system.fetch_contestant_history.clone().reform(move |_| contestant_id)}
+				class="bg-gray-500 hover:bg-gray-700 text-white font-bold py-2 px-4 rounded mb-2">
+				{ format!("Fetch History of {}", contestant_system.contestant_state.display_name()) }
+			</button>
+
+			if let Some(history) = (*contestant_system.contestant_history).clone()
+			{
+				<p class="text-white mb-2">
+					{ format!("{}: {} season(s), top pick {} time(s), ranked {} time(s), average rank {}",
+						history.contestant.display_name(), history.seasons.len(), history.times_picked, history.times_ranked, format_rank(history.average_rank)) }
+				</p>
+
+				<table class="text-white border-collapse">
+					<thead>
+						<tr>
+							<th class="px-2 text-left">{ "Season" }</th>
+							<th class="px-2 text-left">{ "Nickname" }</th>
+							<th class="px-2 text-left">{ "Status" }</th>
+							<th class="px-2">{ "Out in round" }</th>
+							<th class="px-2">{ "Finish" }</th>
+							<th class="px-2">{ "Top picks" }</th>
+							<th class="px-2">{ "Ranked" }</th>
+							<th class="px-2">{ "Avg rank" }</th>
+						</tr>
+					</thead>
+					<tbody>
+					{
+						for history.seasons.iter().map(|season: &SeasonAppearanceState| html!
+						{
+							<tr>
+								<td class="px-2">{ season.game_show_name.clone() }</td>
+								<td class="px-2">{ season.nickname.clone().unwrap_or_default() }</td>
+								<td class="px-2">{ format!("{}{}", season.status.replace('_', " "), if season.was_medically_evacuated { " (medevac)" } else { "" }) }</td>
+								<td class="px-2 text-center">{ season.eliminated_in_round.map_or("-".to_string(), |round: i32| round.to_string()) }</td>
+								<td class="px-2 text-center">{ season.finish.map_or("still in".to_string(), |finish: i32| format!("{} of {}", finish, season.cast_size)) }</td>
+								<td class="px-2 text-center">{ season.times_picked }</td>
+								<td class="px-2 text-center">{ season.times_ranked }</td>
+								<td class="px-2 text-center">{ format_rank(season.average_rank) }</td>
+							</tr>
+						})
+					}
+					</tbody>
+				</table>
+			}
+		</div>
+	}
+}
+
+fn build_round_stats_management(gameshow_system : &GameShowSystem, contestant_system : &ContestantSystem) -> Html
+{
+	let showseason_id: i32 = gameshow_system.gameshow_state.id.unwrap_or(-1);
+	let mut stats_to_send: RoundStatsState = (*contestant_system.round_stats_entry).clone();
+	stats_to_send.contestant_id = contestant_system.contestant_state.id.unwrap_or(-1);
+
+	let edit_count = |apply: fn(&mut RoundStatsState, i32)| -> Callback<InputEvent>
+	{
+		let round_stats_entry_clone: UseStateHandle<RoundStatsState> = contestant_system.round_stats_entry.clone();
+		Callback::from(move |e: InputEvent|
+		{
+			let input = e.target_dyn_into::<web_sys::HtmlInputElement>().unwrap();
+
+			let mut edited_stats: RoundStatsState = (*round_stats_entry_clone).clone();
+			apply(&mut edited_stats, input.value().parse::<i32>().unwrap_or(0));
+
+			round_stats_entry_clone.set(edited_stats);
+		})
+	};
+
+	let edit_flag = |apply: fn(&mut RoundStatsState, bool)| -> Callback<Event>
+	{
+		let round_stats_entry_clone: UseStateHandle<RoundStatsState> = contestant_system.round_stats_entry.clone();
+		Callback::from(move |e: Event|
+		{
+			let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+
+			let mut edited_stats: RoundStatsState = (*round_stats_entry_clone).clone();
+			apply(&mut edited_stats, input.checked());
+
+			round_stats_entry_clone.set(edited_stats);
+		})
+	};
+
+	let contestant_name = |contestant_id: i32| -> String
+	{
+		return contestant_system.contestants_on_show.iter()
+			.find(|contestant| contestant.id == Some(contestant_id))
+			.map_or(format!("Contestant {}", contestant_id), |contestant| contestant.display_name());
+	};
+
+	let entry: &RoundStatsState = &contestant_system.round_stats_entry;
+
+	html!
+	{
+		<div class="mb-4">
+			<h2 class="text-2xl font-bold text-[#FF8C00] mb-2">{ "Round Stats" }</h2>
+
+			<button
+				onclick={contestant_system.collect_round_stats.clone().reform(move |_| showseason_id)}
+				class="bg-gray-500 hover:bg-gray-700 text-white font-bold py-2 px-4 rounded mb-4">
+				{ "Fetch Round Stats" }
+			</button>
+
+			<div class="mb-2">
+				<label class="text-white mr-2">{ "Round" }</label>
+				<input type="number"
+					value={entry.round_number.to_string()}
+					oninput={edit_count(|stats, round_number| stats.round_number = round_number)}
+					class="border rounded px-4 py-2 mr-2 w-20"/>
+
+				<label class="text-white mr-2">{ "Immunity" }</label>
+				<input type="checkbox" class="mr-4"
+					checked={entry.won_individual_immunity}
+					onchange={edit_flag(|stats, won| stats.won_individual_immunity = won)}/>
+
+				<label class="text-white mr-2">{ "Reward" }</label>
+				<input type="checkbox" class="mr-4"
+					checked={entry.won_reward}
+					onchange={edit_flag(|stats, won| stats.won_reward = won)}/>
+
+				<label class="text-white mr-2">{ "Voted correctly" }</label>
+				<input type="checkbox" class="mr-4"
+					checked={entry.cast_correct_vote}
+					onchange={edit_flag(|stats, correct| stats.cast_correct_vote = correct)}/>
+			</div>
+
+			<div class="mb-2">
+				<label class="text-white mr-2">{ "Idols found" }</label>
+				<input type="number" min="0"
+					value={entry.idols_found.to_string()}
+					oninput={edit_count(|stats, count| stats.idols_found = count)}
+					class="border rounded px-4 py-2 mr-2 w-20"/>
+
+				<label class="text-white mr-2">{ "Idols played" }</label>
+				<input type="number" min="0"
+					value={entry.idols_played.to_string()}
+					oninput={edit_count(|stats, count| stats.idols_played = count)}
+					class="border rounded px-4 py-2 mr-2 w-20"/>
+
+				<label class="text-white mr-2">{ "Votes received" }</label>
+				<input type="number" min="0"
+					value={entry.votes_received.to_string()}
+					oninput={edit_count(|stats, count| stats.votes_received = count)}
+					class="border rounded px-4 py-2 mr-2 w-20"/>
+
+				<button
+					onclick={contestant_system.record_round_stats.clone().reform(move |_| (showseason_id, stats_to_send.clone()))}
+					class="bg-blue-500 hover:bg-blue-700 text-white font-bold py-2 px-4 rounded">
+					{ format!("Save Stats for {}", contestant_system.contestant_state.display_name()) }
+				</button>
+			</div>
+
+			<ul class="list-disc pl-5">
+			{
+				for contestant_system.round_stats.iter().map(|stats|
+				{
+					html!
+					{
+						<li class="mb-1 text-white">
+							{ format!("Round {}: {}{}{}{} idols found {}, played {}, votes against {}",
+								stats.round_number,
+								contestant_name(stats.contestant_id),
+								if stats.won_individual_immunity { ", immunity" } else { "" },
+								if stats.won_reward { ", reward" } else { "" },
+								if stats.cast_correct_vote { ", voted correctly," } else { "," },
+								stats.idols_found, stats.idols_played, stats.votes_received) }
+						</li>
+					}
+				})
+			}
+			</ul>
+		</div>
+	}
+}
+
 fn build_showseason_mangement(
 	gameshow_system : &GameShowSystem,
 	contestant_system : &ContestantSystem
@@ -217,10 +957,50 @@ fn build_showseason_mangement(
 				for (*gameshow_system.gameshows).iter().map(|gameshow|
 				{
 					let gameshow_id = gameshow.id;
+					let date_input = |value: &Option<String>, set_date: fn(&mut GameShow, Option<String>)|
+					{
+						let update_gameshow = gameshow_system.update_gameshow.clone();
+						let gameshow = gameshow.clone();
+						html!
+						{
+							<input type="date" class="border rounded px-2 py-1 ml-2"
+								value={value.as_deref().map(|date: &str| date.chars().take(10).collect::<String>()).unwrap_or_default()}
+								onchange={Callback::from(move |e: Event|
+								{
+									let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+									let mut edited_gameshow: GameShow = gameshow.clone();
+									set_date(&mut edited_gameshow, Some(input.value()).filter(|date: &String| !date.is_empty()).map(|date: String| date + "T00:00:00Z"));
+									update_gameshow.emit(edited_gameshow);
+								})}
+							/>
+						}
+					};
+
 					html!
 					{
 						<li class="mb-2">
 						<span class="font-semibold text-[#4a90e2]">{ format!("ID: {}, Name: {}", gameshow.id, gameshow.name) }</span>
+						<span class={classes!("ml-2", "px-2", "py-0.5", "rounded-full", "text-xs", "font-semibold", season_status_badge_class(&gameshow.status))}>
+							{ gameshow.status.clone() }
+						</span>
+
+						{ date_input(&gameshow.start_date, |gameshow: &mut GameShow, date: Option<String>| gameshow.start_date = date) }
+						{ date_input(&gameshow.end_date, |gameshow: &mut GameShow, date: Option<String>| gameshow.end_date = date) }
+
+						{
+							for next_season_statuses(&gameshow.status).iter().map(|next_status: &&str|
+							{
+								let edited_gameshow: GameShow = GameShow { status: next_status.to_string(), ..gameshow.clone() };
+								html!
+								{
+									<button
+										onclick={gameshow_system.update_gameshow.clone().reform(move |_| edited_gameshow.clone())}
+										class="ml-2 bg-gray-500 hover:bg-gray-700 text-white font-bold py-1 px-2 rounded">
+										{ format!("Move to {}", next_status) }
+									</button>
+								}
+							})
+						}
 
 						<button
 							onclick={gameshow_system.delete_gameshow.clone().reform(move |_| gameshow_id)}
@@ -243,7 +1023,7 @@ fn build_showseason_mangement(
 							{
 								<option key={show.id} value={show.id.to_string()}>
 								{
-									&show.name
+									format!("{} ({})", show.name, show.status)
 								}
 								</option>
 							}
@@ -252,21 +1032,111 @@ fn build_showseason_mangement(
 				</select>
 			</div>
 
+			<div class="mb-4">
+				<label class="font-semibold mr-2">{ "Import cast list (.csv with a name column, or .json)" }</label>
+				<input type="file" accept=".csv,.json,text/csv,application/json"
+					onchange={Callback::from(
+					{
+						let import_cast = contestant_system.import_cast.clone();
+						let game_show_id = gameshow_system.gameshow_state.id;
+						move |e: Event|
+						{
+							let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+							if let Some(file) = input.files().and_then(|files| files.get(0))
+							{
+								import_cast.emit((game_show_id, file));
+							}
+
+							// Clear the picker so the same file can be sent again after fixing it.
+							input.set_value("");
+						}
+					})}
+					class="border rounded px-4 py-2 mr-2"
+				/>
+
+				<ul class="list-disc pl-5">
+				{
+					for contestant_system.cast_import_results.iter().map(|result|
+					{
+						let detail: String = result.message.clone().unwrap_or(result.outcome.replace('_', " "));
+						let color: &str = if result.outcome == "failed" { "text-red-600" } else { "text-green-700" };
+						html!
+						{
+							<li class={color}>{ format!("Row {}: {} - {}", result.row, result.name, detail) }</li>
+						}
+					})
+				}
+				</ul>
+			</div>
+
 				<div class="mb-4">
 				<input placeholder="Full Name of Contestant"
 					value={contestant_system.contestant_state.name.clone()}
 					oninput={Callback::from(
 					{
 						let contestant_state_clone = contestant_system.contestant_state.clone();
+						let search_contestants = contestant_system.search_contestants.clone();
 						move |e: InputEvent|
 						{
 							let input = e.target_dyn_into::<web_sys::HtmlInputElement>().unwrap();
 
-							let edited_contestant = ContestantState::new(
+							let mut edited_contestant = ContestantState::new(
 								contestant_state_clone.id,
 								input.value(),
 								None
 							);
+							edited_contestant.disambiguator = contestant_state_clone.disambiguator.clone();
+
+							search_contestants.emit(input.value());
+							contestant_state_clone.set(edited_contestant);
+						}
+					})}
+					class="border rounded px-4 py-2 mr-2"
+				/>
+
+				// Suggestions while typing. Picking one selects that contestant, so same-named people stay apart.
+				if !contestant_system.contestant_suggestions.contestants.is_empty()
+				{
+					<ul class="border rounded mb-2 w-1/2 bg-white">
+					{
+						for contestant_system.contestant_suggestions.contestants.iter().map(|suggestion: &ContestantState|
+						{
+							let contestant_state_clone = contestant_system.contestant_state.clone();
+							let suggestions_clone = contestant_system.contestant_suggestions.clone();
+							let picked_contestant: ContestantState = suggestion.clone();
+							html!
+							{
+								<li class="px-4 py-1 cursor-pointer hover:bg-gray-100"
+									onclick={Callback::from(move |_|
+									{
+										contestant_state_clone.set(picked_contestant.clone());
+										suggestions_clone.set(ContestantPage { total: 0, contestants: Vec::new() });
+									})}>
+									{ format!("{} (ID {})", suggestion.display_name(), suggestion.id.unwrap_or(-1)) }
+								</li>
+							}
+						})
+					}
+					if contestant_system.contestant_suggestions.total > contestant_system.contestant_suggestions.contestants.len()
+					{
+						<li class="px-4 py-1 text-gray-500">
+							{ format!("{} more, keep typing to narrow it down", contestant_system.contestant_suggestions.total - contestant_system.contestant_suggestions.contestants.len()) }
+						</li>
+					}
+					</ul>
+				}
+
+				<input placeholder="Disambiguator (only if the name is taken)"
+					value={contestant_system.contestant_state.disambiguator.clone()}
+					oninput={Callback::from(
+					{
+						let contestant_state_clone = contestant_system.contestant_state.clone();
+						move |e: InputEvent|
+						{
+							let input = e.target_dyn_into::<web_sys::HtmlInputElement>().unwrap();
+
+							let mut edited_contestant: ContestantState = (*contestant_state_clone).clone();
+							edited_contestant.disambiguator = input.value();
 
 							contestant_state_clone.set(edited_contestant);
 						}
@@ -325,8 +1195,8 @@ fn build_showseason_mangement(
 				<button
 					onclick={contestant_system.delete_contestant.clone().reform(
 					{
-						let contestant_state_clone = contestant_system.contestant_state.clone();
-						move |_| contestant_state_clone.name.clone()
+						let contestant_id: i32 = contestant_system.contestant_state.id.unwrap_or(-1);
+						move |_| contestant_id
 					})}
 
 					class="bg-red-500 hover:bg-red-700 text-white font-bold py-2 px-4 rounded">
@@ -412,6 +1282,75 @@ fn build_showseason_mangement(
 					}
 				</button>
 
+				<button
+					onclick = 
+					{
+						contestant_system.reinstate_contestant.clone()
+						.reform(
+						{
+							let mut contestant_state_to_send = ContestantState::new(
+								contestant_system.contestant_state.id,
+								contestant_system.contestant_state.name.clone(),
+								gameshow_system.gameshow_state.id);
+
+							contestant_state_to_send.round_number = contestant_system.contestant_state.round_number;
+
+							move |_| contestant_state_to_send.clone()
+						})
+					}
+
+					class="bg-green-500 hover:bg-green-700 text-white font-bold py-2 px-4 rounded ml-2">
+					{
+						"↩️ Reinstate"
+					}
+				</button>
+
+				<select
+					onchange={Callback::from(
+					{
+						let contestant_event_type = contestant_system.contestant_event_type.clone();
+						move |e: Event|
+						{
+							let select: web_sys::HtmlSelectElement = e.target_unchecked_into();
+							contestant_event_type.set(select.value());
+						}
+					})}
+					class="border rounded px-4 py-2 ml-2 mr-2">
+					{
+						for CONTESTANT_EVENT_TYPES.iter().map(|event_type: &&str|
+						{
+							html!
+							{
+								<option value={event_type.to_string()} selected={*contestant_system.contestant_event_type == *event_type}>{ event_type.replace('_', " ") }</option>
+							}
+						})
+					}
+				</select>
+
+				<button
+					onclick =
+					{
+						contestant_system.record_contestant_event.clone()
+						.reform(
+						{
+							let mut contestant_state_to_send = ContestantState::new(
+								contestant_system.contestant_state.id,
+								contestant_system.contestant_state.name.clone(),
+								gameshow_system.gameshow_state.id);
+
+							contestant_state_to_send.round_number = contestant_system.contestant_state.round_number;
+							let event_type: String = (*contestant_system.contestant_event_type).clone();
+
+							move |_| (contestant_state_to_send.clone(), event_type.clone())
+						})
+					}
+
+					class="bg-purple-500 hover:bg-purple-700 text-white font-bold py-2 px-4 rounded">
+					{
+						"Record Event"
+					}
+				</button>
+
 			</div>
 
 		</>
@@ -469,6 +1408,22 @@ fn build_user_management(
 						}
 					})}
 					class="border rounded px-4 py-2 mr-2"/>
+				<input placeholder="Password" type="password"
+					value={user_system.user_state.password.clone()}
+					oninput={Callback::from(
+					{
+						let user_state_clone = user_system.user_state.clone();
+						move |e: InputEvent|
+						{
+							let input = e.target_dyn_into::<web_sys::HtmlInputElement>().unwrap();
+
+							let mut edited_user: UserState = (*user_state_clone).clone();
+							edited_user.password = input.value();
+
+							user_state_clone.set(edited_user);
+						}
+					})}
+					class="border rounded px-4 py-2 mr-2"/>
 
 				<button
 					onclick=
@@ -1,9 +1,11 @@
 use yew::prelude::*;
 use serde::{ Deserialize, Serialize };
-use gloo::net::http::Request;
+use gloo::net::http::{ Request, RequestCredentials };
 use wasm_bindgen_futures::spawn_local;
+use wasm_bindgen::JsValue;
+use web_sys::js_sys::Date;
 
-use crate::web_server::PLATFORM_URL;
+use crate::web_server::{ PLATFORM_URL, error_message };
 use crate::logger;
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -11,6 +13,36 @@ pub struct GameShow
 {
 	pub id: i32,
 	pub name: String,
+	#[serde(default)]
+	pub status: String,
+	#[serde(default)]
+	pub start_date: Option<String>,
+	#[serde(default)]
+	pub end_date: Option<String>
+}
+
+// The statuses a season may move to next, the same as SeasonStatus::can_become in the backend.
+pub fn next_season_statuses(status: &str) -> &'static [&'static str]
+{
+	return match status
+	{
+		"draft" => &["enrollment"],
+		"enrollment" => &["draft", "live"],
+		"live" => &["finished"],
+		"finished" => &["live"],
+		_ => &[]
+	};
+}
+
+pub fn season_status_badge_class(status: &str) -> &'static str
+{
+	return match status
+	{
+		"enrollment" => "bg-yellow-100 text-yellow-800",
+		"live" => "bg-green-100 text-green-800",
+		"finished" => "bg-gray-200 text-gray-700",
+		_ => "bg-blue-100 text-blue-800"
+	};
 }
 
 #[derive(Clone, PartialEq)]
@@ -87,6 +119,7 @@ pub fn create_gameshow(gameshow_state: &UseStateHandle<GameShowState>,
 				let gameshow_data: serde_json::Value = serde_json::json!({ "name": gameshow_state.name });
 				let url:&str = concat!(PLATFORM_URL!(), "/gameshows");
 				let response: Result<gloo::net::http::Response, gloo::net::Error> = Request::post(url)
+					.credentials(RequestCredentials::Include)
 					.header("Content-Type", "application/json")
 					.body(gameshow_data.to_string())
 					.send().await;
@@ -124,7 +157,7 @@ pub fn delete_gameshow(message: &UseStateHandle<String>,
 			spawn_local(async move
 			{
 				let url:String  = format!(concat!(PLATFORM_URL!(), "/gameshows/{}"), id);
-				let response: Result<gloo::net::http::Response, gloo::net::Error> = Request::delete(&url).send().await;
+				let response: Result<gloo::net::http::Response, gloo::net::Error> = Request::delete(&url).credentials(RequestCredentials::Include).send().await;
 
 				match response
 				{
@@ -141,6 +174,402 @@ pub fn delete_gameshow(message: &UseStateHandle<String>,
 	};
 }
 
+// Sends the whole season back, so a status change or a new date is a copy of the listed one.
+pub fn update_gameshow(message: &UseStateHandle<String>,
+	get_gameshows: Callback<()>) -> Callback<GameShow>
+{
+	return
+	{
+		let message: UseStateHandle<String> = message.clone();
+		let get_gameshows: Callback<()> = get_gameshows.clone();
+
+		Callback::from(move |gameshow: GameShow|
+		{
+			let message: UseStateHandle<String> = message.clone();
+			let get_gameshows: Callback<()> = get_gameshows.clone();
+
+			spawn_local(async move
+			{
+				let url: String = format!(concat!(PLATFORM_URL!(), "/gameshows/{}"), gameshow.id);
+				let response: Result<gloo::net::http::Response, gloo::net::Error> = Request::put(&url)
+					.credentials(RequestCredentials::Include)
+					.header("Content-Type", "application/json")
+					.body(serde_json::to_string(&gameshow).unwrap_or_default())
+					.send().await;
+
+				match response
+				{
+					Ok(resp) if resp.ok() =>
+					{
+						message.set(format!("Game Show [{}] is now {}", gameshow.name, gameshow.status));
+						get_gameshows.emit(());
+					}
+
+					Ok(resp) => message.set(error_message(resp, "Failed to update game show").await),
+					_ => message.set("Failed to update game show".into()),
+				}
+			});
+		})
+	};
+}
+
+pub const ROUND_STATUSES : [&str; 4] = ["upcoming", "open", "locked", "scored"];
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct RoundState
+{
+	pub id: Option<i32>,
+	pub game_show_id: Option<i32>,
+	pub round_number: i32,
+	pub title: String,
+	pub air_date: Option<String>,
+	pub lock_time: Option<String>,
+	pub status: String
+}
+
+impl RoundState
+{
+	pub fn from_default() -> Self
+	{
+		RoundState
+		{
+			id: None,
+			game_show_id: None,
+			round_number: 1,
+			title: "".to_string(),
+			air_date: None,
+			lock_time: None,
+			status: "upcoming".to_string()
+		}
+	}
+}
+
+// datetime-local inputs give the browser's local time with no zone, the backend wants UTC.
+fn local_time_to_utc(local_time: &Option<String>) -> Option<String>
+{
+	return local_time.as_ref()
+		.filter(|local_time: &&String| !local_time.is_empty())
+		.map(|local_time: &String| Date::new(&JsValue::from_str(local_time)).to_iso_string().into());
+}
+
+pub fn collect_rounds(rounds: &UseStateHandle<Vec<RoundState>>,
+	message: &UseStateHandle<String>) -> Callback<i32>
+{
+	let rounds: UseStateHandle<Vec<RoundState>> = rounds.clone();
+	let message: UseStateHandle<String> = message.clone();
+	Callback::from(move |game_show_id: i32|
+	{
+		let rounds: UseStateHandle<Vec<RoundState>> = rounds.clone();
+		let message: UseStateHandle<String> = message.clone();
+		spawn_local(async move
+		{
+			let url: String = format!(concat!(PLATFORM_URL!(), "/gameshows/{}/rounds"), game_show_id);
+			match Request::get(&url).send().await
+			{
+				Ok(resp) if resp.ok() =>
+				{
+					let fetched_rounds: Vec<RoundState> = resp.json().await.unwrap_or_default();
+					rounds.set(fetched_rounds);
+				}
+
+				_ => message.set(format!("Failed to fetch the rounds for showseason [{}]", game_show_id)),
+			}
+		});
+	})
+}
+
+// Saving a round number that already exists edits it, anything else adds a new round.
+pub fn save_round(round_entry: &UseStateHandle<RoundState>,
+	rounds: &UseStateHandle<Vec<RoundState>>,
+	message: &UseStateHandle<String>) -> Callback<i32>
+{
+	return
+	{
+		let round_entry: UseStateHandle<RoundState> = round_entry.clone();
+		let rounds: UseStateHandle<Vec<RoundState>> = rounds.clone();
+		let message: UseStateHandle<String> = message.clone();
+		Callback::from(move |game_show_id: i32|
+		{
+			let round_entry: UseStateHandle<RoundState> = round_entry.clone();
+			let rounds: UseStateHandle<Vec<RoundState>> = rounds.clone();
+			let message: UseStateHandle<String> = message.clone();
+
+			spawn_local(async move
+			{
+				let round_data: serde_json::Value = serde_json::json!({
+					"id": null,
+					"game_show_id": game_show_id,
+					"round_number": round_entry.round_number,
+					"title": round_entry.title,
+					"air_date": local_time_to_utc(&round_entry.air_date),
+					"lock_time": local_time_to_utc(&round_entry.lock_time),
+					"status": round_entry.status
+				});
+
+				let round_exists: bool = rounds.iter().any(|round: &RoundState| round.round_number == round_entry.round_number);
+				let request: gloo::net::http::Request = if round_exists
+				{
+					Request::put(&format!(concat!(PLATFORM_URL!(), "/gameshows/{}/rounds/{}"), game_show_id, round_entry.round_number))
+				}
+				else
+				{
+					Request::post(&format!(concat!(PLATFORM_URL!(), "/gameshows/{}/rounds"), game_show_id))
+				};
+
+				let response: Result<gloo::net::http::Response, gloo::net::Error> = request
+					.header("Content-Type", "application/json")
+					.credentials(RequestCredentials::Include)
+					.body(round_data.to_string())
+					.send().await;
+
+				match response
+				{
+					Ok(resp) if resp.ok() =>
+					{
+						let saved_rounds: Vec<RoundState> = resp.json().await.unwrap_or_default();
+						rounds.set(saved_rounds);
+						message.set(format!("Round [{}] saved", round_entry.round_number));
+					}
+
+					Ok(resp) => message.set(error_message(resp, "Failed to save round").await),
+					_ => message.set("Failed to save round".into()),
+				}
+			});
+		})
+	};
+}
+
+pub fn delete_round(rounds: &UseStateHandle<Vec<RoundState>>,
+	message: &UseStateHandle<String>) -> Callback<(i32, i32)>
+{
+	return
+	{
+		let rounds: UseStateHandle<Vec<RoundState>> = rounds.clone();
+		let message: UseStateHandle<String> = message.clone();
+		Callback::from(move | (game_show_id, round_number) : (i32, i32) |
+		{
+			let rounds: UseStateHandle<Vec<RoundState>> = rounds.clone();
+			let message: UseStateHandle<String> = message.clone();
+
+			spawn_local(async move
+			{
+				let url: String = format!(concat!(PLATFORM_URL!(), "/gameshows/{}/rounds/{}"), game_show_id, round_number);
+				let response: Result<gloo::net::http::Response, gloo::net::Error> = Request::delete(&url)
+					.credentials(RequestCredentials::Include)
+					.send().await;
+
+				match response
+				{
+					Ok(resp) if resp.ok() =>
+					{
+						let remaining_rounds: Vec<RoundState> = resp.json().await.unwrap_or_default();
+						rounds.set(remaining_rounds);
+						message.set(format!("Round [{}] deleted", round_number));
+					}
+
+					_ => message.set(format!("Failed to delete round [{}]", round_number)),
+				}
+			});
+		})
+	};
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct TribeState
+{
+	pub id: Option<i32>,
+	pub game_show_id: Option<i32>,
+	pub name: String,
+	pub color: String
+}
+
+impl TribeState
+{
+	pub fn from_default() -> Self
+	{
+		TribeState
+		{
+			id: None,
+			game_show_id: None,
+			name: "".to_string(),
+			color: "#808080".to_string()
+		}
+	}
+}
+
+// Which tribe the next assignment or merge puts contestants on, and from which round.
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct TribeAssignmentState
+{
+	pub contestant_id: i32,
+	pub tribe_id: i32,
+	pub from_round: i32
+}
+
+pub fn collect_tribes(tribes: &UseStateHandle<Vec<TribeState>>,
+	message: &UseStateHandle<String>) -> Callback<i32>
+{
+	let tribes: UseStateHandle<Vec<TribeState>> = tribes.clone();
+	let message: UseStateHandle<String> = message.clone();
+	Callback::from(move |game_show_id: i32|
+	{
+		let tribes: UseStateHandle<Vec<TribeState>> = tribes.clone();
+		let message: UseStateHandle<String> = message.clone();
+		spawn_local(async move
+		{
+			let url: String = format!(concat!(PLATFORM_URL!(), "/gameshows/{}/tribes"), game_show_id);
+			match Request::get(&url).send().await
+			{
+				Ok(resp) if resp.ok() =>
+				{
+					let fetched_tribes: Vec<TribeState> = resp.json().await.unwrap_or_default();
+					tribes.set(fetched_tribes);
+				}
+
+				_ => message.set(format!("Failed to fetch the tribes for showseason [{}]", game_show_id)),
+			}
+		});
+	})
+}
+
+pub fn create_tribe(tribe_entry: &UseStateHandle<TribeState>,
+	tribes: &UseStateHandle<Vec<TribeState>>,
+	message: &UseStateHandle<String>) -> Callback<i32>
+{
+	return
+	{
+		let tribe_entry: UseStateHandle<TribeState> = tribe_entry.clone();
+		let tribes: UseStateHandle<Vec<TribeState>> = tribes.clone();
+		let message: UseStateHandle<String> = message.clone();
+		Callback::from(move |game_show_id: i32|
+		{
+			let tribe_entry: UseStateHandle<TribeState> = tribe_entry.clone();
+			let tribes: UseStateHandle<Vec<TribeState>> = tribes.clone();
+			let message: UseStateHandle<String> = message.clone();
+
+			spawn_local(async move
+			{
+				let response: Result<gloo::net::http::Response, gloo::net::Error> = Request::post(&format!(concat!(PLATFORM_URL!(), "/gameshows/{}/tribes"), game_show_id))
+					.header("Content-Type", "application/json")
+					.credentials(RequestCredentials::Include)
+					.body(serde_json::to_string(&*tribe_entry).unwrap_or_default())
+					.send().await;
+
+				match response
+				{
+					Ok(resp) if resp.ok() =>
+					{
+						let saved_tribes: Vec<TribeState> = resp.json().await.unwrap_or_default();
+						tribes.set(saved_tribes);
+						message.set(format!("Tribe [{}] created", tribe_entry.name));
+					}
+
+					Ok(resp) => message.set(error_message(resp, "Failed to create tribe").await),
+					_ => message.set("Failed to create tribe".into()),
+				}
+			});
+		})
+	};
+}
+
+pub fn delete_tribe(tribes: &UseStateHandle<Vec<TribeState>>,
+	message: &UseStateHandle<String>) -> Callback<(i32, i32)>
+{
+	return
+	{
+		let tribes: UseStateHandle<Vec<TribeState>> = tribes.clone();
+		let message: UseStateHandle<String> = message.clone();
+		Callback::from(move | (game_show_id, tribe_id) : (i32, i32) |
+		{
+			let tribes: UseStateHandle<Vec<TribeState>> = tribes.clone();
+			let message: UseStateHandle<String> = message.clone();
+
+			spawn_local(async move
+			{
+				let url: String = format!(concat!(PLATFORM_URL!(), "/gameshows/{}/tribes/{}"), game_show_id, tribe_id);
+				let response: Result<gloo::net::http::Response, gloo::net::Error> = Request::delete(&url)
+					.credentials(RequestCredentials::Include)
+					.send().await;
+
+				match response
+				{
+					Ok(resp) if resp.ok() =>
+					{
+						let remaining_tribes: Vec<TribeState> = resp.json().await.unwrap_or_default();
+						tribes.set(remaining_tribes);
+						message.set(format!("Tribe [{}] deleted", tribe_id));
+					}
+
+					_ => message.set(format!("Failed to delete tribe [{}]", tribe_id)),
+				}
+			});
+		})
+	};
+}
+
+// Assigning a contestant again from a later round records a swap.
+pub fn assign_contestant_to_tribe(assignment_entry: &UseStateHandle<TribeAssignmentState>,
+	message: &UseStateHandle<String>) -> Callback<(i32, i32)>
+{
+	return
+	{
+		let assignment_entry: UseStateHandle<TribeAssignmentState> = assignment_entry.clone();
+		let message: UseStateHandle<String> = message.clone();
+		Callback::from(move | (game_show_id, contestant_id) : (i32, i32) |
+		{
+			let assignment: TribeAssignmentState = TribeAssignmentState { contestant_id, ..(*assignment_entry).clone() };
+			let message: UseStateHandle<String> = message.clone();
+
+			spawn_local(async move
+			{
+				let response: Result<gloo::net::http::Response, gloo::net::Error> = Request::post(&format!(concat!(PLATFORM_URL!(), "/gameshows/{}/tribe_assignments"), game_show_id))
+					.header("Content-Type", "application/json")
+					.credentials(RequestCredentials::Include)
+					.body(serde_json::to_string(&assignment).unwrap_or_default())
+					.send().await;
+
+				match response
+				{
+					Ok(resp) if resp.ok() => message.set(format!("Contestant [{}] is on tribe [{}] from round [{}]", contestant_id, assignment.tribe_id, assignment.from_round)),
+					Ok(resp) => message.set(error_message(resp, "Failed to assign the tribe").await),
+					_ => message.set("Failed to assign the tribe".into()),
+				}
+			});
+		})
+	};
+}
+
+// Puts everyone still in the game on the chosen tribe from the chosen round.
+pub fn merge_tribes(assignment_entry: &UseStateHandle<TribeAssignmentState>,
+	message: &UseStateHandle<String>) -> Callback<i32>
+{
+	return
+	{
+		let assignment_entry: UseStateHandle<TribeAssignmentState> = assignment_entry.clone();
+		let message: UseStateHandle<String> = message.clone();
+		Callback::from(move |game_show_id: i32|
+		{
+			let assignment: TribeAssignmentState = (*assignment_entry).clone();
+			let message: UseStateHandle<String> = message.clone();
+
+			spawn_local(async move
+			{
+				let url: String = format!(concat!(PLATFORM_URL!(), "/gameshows/{}/tribes/{}/merge?from_round={}"), game_show_id, assignment.tribe_id, assignment.from_round);
+				let response: Result<gloo::net::http::Response, gloo::net::Error> = Request::post(&url)
+					.credentials(RequestCredentials::Include)
+					.send().await;
+
+				match response
+				{
+					Ok(resp) if resp.ok() => message.set(format!("Merged onto tribe [{}] from round [{}]", assignment.tribe_id, assignment.from_round)),
+					Ok(resp) => message.set(error_message(resp, "Failed to merge the tribes").await),
+					_ => message.set("Failed to merge the tribes".into()),
+				}
+			});
+		})
+	};
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct League
 {
@@ -188,6 +617,20 @@ pub struct GameShowSystem
 	pub get_gameshows: Callback<()>,
 	pub create_gameshow: yew::Callback<yew::MouseEvent>,
 	pub delete_gameshow: Callback<i32>,
+	pub update_gameshow: Callback<GameShow>,
+	pub round_entry: UseStateHandle<RoundState>,
+	pub rounds: UseStateHandle<Vec<RoundState>>,
+	pub collect_rounds: Callback<i32>,
+	pub save_round: Callback<i32>,
+	pub delete_round: Callback<(i32, i32)>,
+	pub tribe_entry: UseStateHandle<TribeState>,
+	pub tribes: UseStateHandle<Vec<TribeState>>,
+	pub tribe_assignment_entry: UseStateHandle<TribeAssignmentState>,
+	pub collect_tribes: Callback<i32>,
+	pub create_tribe: Callback<i32>,
+	pub delete_tribe: Callback<(i32, i32)>,
+	pub assign_contestant_to_tribe: Callback<(i32, i32)>,
+	pub merge_tribes: Callback<i32>,
 	pub league_state : UseStateHandle<LeagueState>,
 	pub leagues : UseStateHandle<Vec<League>>,
 	pub collect_leagues: Callback<i32>,
@@ -245,6 +688,7 @@ pub fn create_league(league_state_in: &UseStateHandle<LeagueState>,
 				let league_data: serde_json::Value = serde_json::json!( { "name": league_state.name, "id_showseason": showseason_id } );
 				let url:&str = concat!(PLATFORM_URL!(), "/leagues");
 				let response: Result<gloo::net::http::Response, gloo::net::Error> = Request::post(url)
+					.credentials(RequestCredentials::Include)
 					.header("Content-Type", "application/json")
 					.body(league_data.to_string())
 					.send().await;
@@ -277,7 +721,7 @@ pub fn delete_league(message: &UseStateHandle<String>) -> Callback<i32>
 			spawn_local(async move
 			{
 				let url:String  = format!(concat!(PLATFORM_URL!(), "/leagues/{}"), id);
-				let response: Result<gloo::net::http::Response, gloo::net::Error> = Request::delete(&url).send().await;
+				let response: Result<gloo::net::http::Response, gloo::net::Error> = Request::delete(&url).credentials(RequestCredentials::Include).send().await;
 
 				match response
 				{
@@ -308,6 +752,7 @@ pub fn enter_user_into_league(message: &UseStateHandle<String>) -> Callback<(i32
 			{
 				let url:String = format!(concat!(PLATFORM_URL!(), "/leagues?user_id={}&league_id={}"), id_user, id_league);
 				let response: Result<gloo::net::http::Response, gloo::net::Error> = Request::post(&url)
+					.credentials(RequestCredentials::Include)
 					.header("Content-Type", "application/json")
 					.send().await;
 
@@ -340,6 +785,7 @@ fn remove_user_from_league(message: &UseStateHandle<String>) -> Callback<(i32, i
 			{
 				let url:String = format!(concat!(PLATFORM_URL!(), "/leagues?user_id={}&league_id={}"), id_user, id_league);
 				let response: Result<gloo::net::http::Response, gloo::net::Error> = Request::delete(&url)
+					.credentials(RequestCredentials::Include)
 					.header("Content-Type", "application/json")
 					.send().await;
 
@@ -366,6 +812,22 @@ pub fn use_compile_gameshow_system(message: UseStateHandle<String>) -> GameShowS
 	let get_gameshows: Callback<()> = get_gameshows(&gameshows, &message);
 	let create_gameshow: yew::Callback<yew::MouseEvent> = create_gameshow(&gameshow_state, &message, get_gameshows.clone());
 	let delete_gameshow: Callback<i32> = delete_gameshow(&message, get_gameshows.clone());
+	let update_gameshow: Callback<GameShow> = update_gameshow(&message, get_gameshows.clone());
+
+	let round_entry: UseStateHandle<RoundState> = use_state(RoundState::from_default);
+	let rounds: UseStateHandle<Vec<RoundState>> = use_state(Vec::new);
+	let collect_rounds: Callback<i32> = collect_rounds(&rounds, &message);
+	let save_round: Callback<i32> = save_round(&round_entry, &rounds, &message);
+	let delete_round: Callback<(i32, i32)> = delete_round(&rounds, &message);
+
+	let tribe_entry: UseStateHandle<TribeState> = use_state(TribeState::from_default);
+	let tribes: UseStateHandle<Vec<TribeState>> = use_state(Vec::new);
+	let tribe_assignment_entry: UseStateHandle<TribeAssignmentState> = use_state(|| TribeAssignmentState { contestant_id: -1, tribe_id: -1, from_round: 1 });
+	let collect_tribes: Callback<i32> = collect_tribes(&tribes, &message);
+	let create_tribe: Callback<i32> = create_tribe(&tribe_entry, &tribes, &message);
+	let delete_tribe: Callback<(i32, i32)> = delete_tribe(&tribes, &message);
+	let assign_contestant_to_tribe: Callback<(i32, i32)> = assign_contestant_to_tribe(&tribe_assignment_entry, &message);
+	let merge_tribes: Callback<i32> = merge_tribes(&tribe_assignment_entry, &message);
 
 	let league_state : UseStateHandle<LeagueState> = use_state(|| LeagueState::from_default());
 	let leagues : UseStateHandle<Vec<League>> = use_state(Vec::new);
@@ -375,6 +837,8 @@ pub fn use_compile_gameshow_system(message: UseStateHandle<String>) -> GameShowS
 	let enter_user_into_league : yew::Callback<(i32, i32)> = enter_user_into_league(&message);
 	let remove_user_from_league : yew::Callback<(i32, i32)> = remove_user_from_league(&message);
 
-	return GameShowSystem { gameshow_state, gameshows, get_gameshows, create_gameshow, delete_gameshow,
+	return GameShowSystem { gameshow_state, gameshows, get_gameshows, create_gameshow, delete_gameshow, update_gameshow,
+		round_entry, rounds, collect_rounds, save_round, delete_round,
+		tribe_entry, tribes, tribe_assignment_entry, collect_tribes, create_tribe, delete_tribe, assign_contestant_to_tribe, merge_tribes,
 		league_state, leagues, collect_leagues, create_league, delete_league, enter_user_into_league, remove_user_from_league };
 }
\ No newline at end of file
@@ -1,14 +1,11 @@
 use yew::prelude::*;
 use serde::{ Deserialize, Serialize };
-use gloo::net::http::Request;
+use gloo::net::http::{ Request, RequestCredentials };
 use wasm_bindgen_futures::spawn_local;
 
-use crate::web_server::PLATFORM_URL;
+use crate::web_server::{ PLATFORM_URL, error_message };
 use crate::logger;
 
-const player_id : i32 = 1;
-const league_id : i32 = 1;
-
 #[derive(Clone, PartialEq)]
 pub struct ContestantPickState
 {
@@ -43,18 +40,55 @@ pub struct RoundPickState
 	pub picks: Vec<ContestantPickState>
 }
 
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct PlayerLeagueState
+{
+	pub id: i32,
+	pub name: String,
+	pub id_showseason: Option<i32>
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct RoundScoreState
+{
+	pub round_number: i32,
+	pub points: i32,
+	pub cumulative_points: i32
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct StandingState
+{
+	pub user_id: i32,
+	pub user_name: String,
+	pub rank: i32,
+	pub previous_rank: Option<i32>,
+	pub movement: i32,
+	pub total_points: i32,
+	pub rounds: Vec<RoundScoreState>
+}
+
 #[derive(Clone)]
 pub struct LeagueSystem
 {
 	pub picks_state: UseStateHandle<Vec<RoundPickState>>,
-	pub submit_picks : yew::Callback<yew::MouseEvent>
+	pub pick_round_number: UseStateHandle<i32>,
+	pub submit_picks : yew::Callback<yew::MouseEvent>,
+	pub player_leagues : UseStateHandle<Vec<PlayerLeagueState>>,
+	pub selected_league : UseStateHandle<Option<PlayerLeagueState>>,
+	pub fetch_player_leagues : Callback<i32>,
+	pub standings : UseStateHandle<Vec<StandingState>>,
+	pub fetch_standings : Callback<i32>
 }
 
-pub fn submit_picks(message: &UseStateHandle<String>, picks: &UseStateHandle<Vec<RoundPickState>>) -> yew::Callback<yew::MouseEvent>
+pub fn submit_picks(message: &UseStateHandle<String>,
+	picks: &UseStateHandle<Vec<RoundPickState>>,
+	selected_league: &UseStateHandle<Option<PlayerLeagueState>>) -> yew::Callback<yew::MouseEvent>
 {
 	return
 	{
 		let picks_state: UseStateHandle<Vec<RoundPickState>> = picks.clone();
+		let selected_league: UseStateHandle<Option<PlayerLeagueState>> = selected_league.clone();
 		let message: UseStateHandle<String> = message.clone();
 
 		Callback::from(move |_|
@@ -62,6 +96,13 @@ pub fn submit_picks(message: &UseStateHandle<String>, picks: &UseStateHandle<Vec
 			let picks_state: UseStateHandle<Vec<RoundPickState>> = picks_state.clone();
 			let message: UseStateHandle<String> = message.clone();
 
+			let Some(league) = (*selected_league).clone() else
+			{
+				message.set("Select a league before submitting picks".into());
+				return;
+			};
+			let league_id: i32 = league.id;
+
 			spawn_local(async move
 			{
 
@@ -73,9 +114,10 @@ pub fn submit_picks(message: &UseStateHandle<String>, picks: &UseStateHandle<Vec
 					{
 						logger::logger::log(format!("Submitting pick for contestant [{}] with rank [{}]", contestant_pick.contestant_id, contestant_pick.rank_pick));
 
-						let url:String = format!(concat!(PLATFORM_URL!(), "/leagues/set_pick?user_id={}&league_id={}&round_number={}&contestant_id={}&rank_pick={}"), player_id, league_id, round_pick_state.round_number, contestant_pick.contestant_id, contestant_pick.rank_pick);
+						let url:String = format!(concat!(PLATFORM_URL!(), "/leagues/set_pick?league_id={}&round_number={}&contestant_id={}&rank_pick={}"), league_id, round_pick_state.round_number, contestant_pick.contestant_id, contestant_pick.rank_pick);
 						let response: Result<gloo::net::http::Response, gloo::net::Error> = Request::post(&url)
 							.header("Content-Type", "application/json")
+							.credentials(RequestCredentials::Include)
 							.send().await;
 
 						match response
@@ -85,7 +127,19 @@ pub fn submit_picks(message: &UseStateHandle<String>, picks: &UseStateHandle<Vec
 								message.set(format!("Pick [{}] entered successfully onto league [{}]", contestant_pick.rank_pick, league_id.to_string()).into());
 							}
 
-							_ => message.set(format!("Failed to enter pick [{}] onto league [{}]", contestant_pick.rank_pick, league_id.to_string()).into()),
+							// A locked round refuses every pick the same way, so stop at the first refusal and show why.
+							Ok(resp) =>
+							{
+								let reason: String = error_message(resp, "no reason given").await;
+								message.set(format!("Failed to enter pick [{}] onto league [{}]: {}", contestant_pick.rank_pick, league_id, reason));
+								return;
+							}
+
+							_ =>
+							{
+								message.set(format!("Failed to enter pick [{}] onto league [{}]", contestant_pick.rank_pick, league_id));
+								return;
+							}
 						}
 					}
 				}
@@ -97,6 +151,7 @@ pub fn submit_picks(message: &UseStateHandle<String>, picks: &UseStateHandle<Vec
 	};
 }
 
+// The backend records the pick against whoever is logged in; id_user is only used for messages.
 pub fn set_pick(message: &UseStateHandle<String>) -> Callback<(i32, i32, i32, i32, i32)>
 {
 	return
@@ -109,9 +164,10 @@ pub fn set_pick(message: &UseStateHandle<String>) -> Callback<(i32, i32, i32, i3
 
 			spawn_local(async move
 			{
-				let url:String = format!(concat!(PLATFORM_URL!(), "/leagues/set_pick?user_id={}&league_id={}&round_number={}&contestant_id={}&rank_pick={}"), id_user, id_league, round_number, id_contestant, rank_pick);
+				let url:String = format!(concat!(PLATFORM_URL!(), "/leagues/set_pick?league_id={}&round_number={}&contestant_id={}&rank_pick={}"), id_league, round_number, id_contestant, rank_pick);
 				let response: Result<gloo::net::http::Response, gloo::net::Error> = Request::post(&url)
 					.header("Content-Type", "application/json")
+					.credentials(RequestCredentials::Include)
 					.send().await;
 
 				match response
@@ -128,12 +184,81 @@ pub fn set_pick(message: &UseStateHandle<String>) -> Callback<(i32, i32, i32, i3
 	};
 }
 
+pub fn fetch_player_leagues(player_leagues: &UseStateHandle<Vec<PlayerLeagueState>>,
+	message: &UseStateHandle<String>) -> Callback<i32>
+{
+	return
+	{
+		let player_leagues: UseStateHandle<Vec<PlayerLeagueState>> = player_leagues.clone();
+		let message: UseStateHandle<String> = message.clone();
+		Callback::from(move |id_user: i32|
+		{
+			let player_leagues: UseStateHandle<Vec<PlayerLeagueState>> = player_leagues.clone();
+			let message: UseStateHandle<String> = message.clone();
+
+			spawn_local(async move
+			{
+				let url: String = format!(concat!(PLATFORM_URL!(), "/users/{}/leagues"), id_user);
+				match Request::get(&url).credentials(RequestCredentials::Include).send().await
+				{
+					Ok(resp) if resp.ok() =>
+					{
+						let fetched_leagues: Vec<PlayerLeagueState> = resp.json().await.unwrap_or_default();
+						message.set(format!("You are in {} league(s)", fetched_leagues.len()));
+						player_leagues.set(fetched_leagues);
+					}
+
+					_ => message.set("Failed to fetch your leagues".into()),
+				}
+			});
+		})
+	};
+}
+
+pub fn fetch_standings(standings: &UseStateHandle<Vec<StandingState>>,
+	message: &UseStateHandle<String>) -> Callback<i32>
+{
+	return
+	{
+		let standings: UseStateHandle<Vec<StandingState>> = standings.clone();
+		let message: UseStateHandle<String> = message.clone();
+		Callback::from(move |id_league: i32|
+		{
+			let standings: UseStateHandle<Vec<StandingState>> = standings.clone();
+			let message: UseStateHandle<String> = message.clone();
+
+			spawn_local(async move
+			{
+				let url: String = format!(concat!(PLATFORM_URL!(), "/leagues/{}/standings"), id_league);
+				match Request::get(&url).send().await
+				{
+					Ok(resp) if resp.ok() =>
+					{
+						let fetched_standings: Vec<StandingState> = resp.json().await.unwrap_or_default();
+						standings.set(fetched_standings);
+					}
+
+					_ => message.set(format!("Failed to fetch standings for league [{}]", id_league)),
+				}
+			});
+		})
+	};
+}
+
 #[hook]
 pub fn use_create_league_system(message: UseStateHandle<String>) -> LeagueSystem
 {
 	let picks_state: UseStateHandle<Vec<RoundPickState>> = use_state(Vec::new);
 
-	let submit_picks: yew::Callback<yew::MouseEvent> = submit_picks(&message, &picks_state);
+	let pick_round_number: UseStateHandle<i32> = use_state(|| 1);
+	let player_leagues: UseStateHandle<Vec<PlayerLeagueState>> = use_state(Vec::new);
+	let selected_league: UseStateHandle<Option<PlayerLeagueState>> = use_state(|| None);
+	let fetch_player_leagues: Callback<i32> = fetch_player_leagues(&player_leagues, &message);
+
+	let submit_picks: yew::Callback<yew::MouseEvent> = submit_picks(&message, &picks_state, &selected_league);
+
+	let standings: UseStateHandle<Vec<StandingState>> = use_state(Vec::new);
+	let fetch_standings: Callback<i32> = fetch_standings(&standings, &message);
 
-	return LeagueSystem { picks_state, submit_picks };
+	return LeagueSystem { picks_state, pick_round_number, submit_picks, player_leagues, selected_league, fetch_player_leagues, standings, fetch_standings };
 }
\ No newline at end of file
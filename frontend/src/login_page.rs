@@ -0,0 +1 @@
+pub mod login_page;
\ No newline at end of file
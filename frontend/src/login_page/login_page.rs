@@ -0,0 +1,83 @@
+use yew::prelude::*;
+
+use crate::session::session::*;
+
+pub fn build_login_page(
+	message: &UseStateHandle<String>,
+	session_system: &SessionSystem
+) -> Html
+{
+	html!
+	{
+		<body class="bg-[#121212]  min-h-screen">
+			<div class="container mx-auto p-4">
+				<h1 class="text-4xl font-bold text-[#FF8C00] mb-4">{ "Log In" }</h1>
+
+				if let Some(user) = (*session_system.session_user).clone()
+				{
+					<p class="text-white mb-4">{ format!("Logged in as {} ({})", user.name, user.email) }</p>
+
+					<div class="mb-4">
+						<a href="/player-portal" class="text-blue-500 hover:underline mr-4">{ "Go to Player Portal" }</a>
+						<a href="/gamemaster-portal" class="text-blue-500 hover:underline">{ "Go to Game Master Portal" }</a>
+					</div>
+
+					<button
+						onclick={session_system.logout.clone()}
+						class="bg-red-500 hover:bg-red-700 text-white font-bold py-2 px-4 rounded">
+						{ "Log Out" }
+					</button>
+				}
+				else
+				{
+					<div class="mb-4">
+						<input placeholder="Email"
+							value={session_system.login_state.email.clone()}
+							oninput={Callback::from(
+							{
+								let login_state_clone = session_system.login_state.clone();
+								move |e: InputEvent|
+								{
+									let input = e.target_dyn_into::<web_sys::HtmlInputElement>().unwrap();
+
+									let mut edited_login: LoginState = (*login_state_clone).clone();
+									edited_login.email = input.value();
+
+									login_state_clone.set(edited_login);
+								}
+							})}
+							class="border rounded px-4 py-2 mr-2"/>
+
+						<input placeholder="Password" type="password"
+							value={session_system.login_state.password.clone()}
+							oninput={Callback::from(
+							{
+								let login_state_clone = session_system.login_state.clone();
+								move |e: InputEvent|
+								{
+									let input = e.target_dyn_into::<web_sys::HtmlInputElement>().unwrap();
+
+									let mut edited_login: LoginState = (*login_state_clone).clone();
+									edited_login.password = input.value();
+
+									login_state_clone.set(edited_login);
+								}
+							})}
+							class="border rounded px-4 py-2 mr-2"/>
+
+						<button
+							onclick={session_system.login.clone()}
+							class="bg-blue-500 hover:bg-blue-700 text-white font-bold py-2 px-4 rounded">
+							{ "Log In" }
+						</button>
+					</div>
+				}
+
+				if !message.is_empty()
+				{
+					<p class="text-green-500 mt-2">{ &**message }</p>
+				}
+			</div>
+		</body>
+	}
+}
@@ -9,11 +9,16 @@ mod logger;
 mod gamemaster_portal;
 mod player_portal;
 mod leagues;
+mod scoring;
+mod session;
+mod login_page;
 
 use crate::users::users::*;
 use crate::gameshows::gameshows::*;
 use crate::contestants::contestants::*;
 use crate::leagues::leagues::*;
+use crate::scoring::scoring::*;
+use crate::session::session::*;
 
 fn main()
 {
@@ -27,6 +32,8 @@ enum Route
 	GameMasterPortal,
 	#[at("/player-portal")]
 	PlayerPortal,
+	#[at("/login")]
+	Login,
     #[at("/")]
     Home
 }
@@ -39,6 +46,8 @@ fn app() -> Html
 	let gameshow_system: GameShowSystem = gameshows::gameshows::use_compile_gameshow_system(message.clone());
 	let contestant_system: ContestantSystem = contestants::contestants::use_compile_contestant_system(message.clone());
 	let league_system: LeagueSystem = leagues::leagues::use_create_league_system(message.clone());
+	let scoring_system: ScoringSystem = scoring::scoring::use_compile_scoring_system(message.clone());
+	let session_system: SessionSystem = session::session::use_compile_session_system(message.clone());
 
 	let portal_router = 
 	{
@@ -47,6 +56,8 @@ fn app() -> Html
 		let gameshow_system: GameShowSystem = gameshow_system.clone();
 		let contestant_system: ContestantSystem = contestant_system.clone();
 		let league_system: LeagueSystem = league_system.clone();
+		let scoring_system: ScoringSystem = scoring_system.clone();
+		let session_system: SessionSystem = session_system.clone();
 
 		let dragged_index: UseStateHandle<Option<usize>> = use_state(|| None::<usize>);
 
@@ -62,10 +73,25 @@ fn app() -> Html
 			});
 		}
 
+		// Effect: Keep the picks to submit in step with the player's ranking and chosen round
+		{
+			let picks_state: UseStateHandle<Vec<RoundPickState>> = league_system.picks_state.clone();
+			let dependencies = (ranked_contestants.clone(), league_system.pick_round_number.clone());
+			use_effect_with(dependencies, move |(ranked, round_number): &(UseStateHandle<Vec<ContestantState>>, UseStateHandle<i32>)|
+			{
+				let picks: Vec<ContestantPickState> = ranked.iter().enumerate()
+					.filter_map(|(index, contestant)| contestant.id.map(|id: i32| ContestantPickState::new(id, index as i32 + 1)))
+					.collect();
+
+				picks_state.set(vec![RoundPickState { round_number: **round_number, picks }]);
+			});
+		}
+
 		move | routes: Route | match routes
 		{
-			Route::GameMasterPortal => gamemaster_portal::gamemaster_portal::build_gamemaster_portal_page(&message, &user_system, &gameshow_system, &contestant_system),
-			Route::PlayerPortal => player_portal::player_portal::build_player_portal_page(&message, &contestant_system, &dragged_index, &ranked_contestants, &league_system),
+			Route::GameMasterPortal => gamemaster_portal::gamemaster_portal::build_gamemaster_portal_page(&message, &user_system, &gameshow_system, &contestant_system, &scoring_system, &session_system),
+			Route::PlayerPortal => player_portal::player_portal::build_player_portal_page(&message, &contestant_system, &dragged_index, &ranked_contestants, &league_system, &session_system),
+			Route::Login => login_page::login_page::build_login_page(&message, &session_system),
 
             Route::Home => {
                 let msg = message.clone();
@@ -76,9 +102,12 @@ fn app() -> Html
                             <a href="/gamemaster-portal" class="text-blue-500 hover:underline mr-4">
                                 {"Go to Game Master Portal"}
                             </a>
-                            <a href="/player-portal" class="text-blue-500 hover:underline">
+                            <a href="/player-portal" class="text-blue-500 hover:underline mr-4">
                                 {"Go to Player Portal"}
                             </a>
+                            <a href="/login" class="text-blue-500 hover:underline">
+                                {"Log In"}
+                            </a>
                         </div>
                     </div>
                 }
@@ -3,24 +3,55 @@ use crate::users::users::*;
 use crate::gameshows::gameshows::*;
 use crate::contestants::contestants::*;
 use crate::leagues::leagues::*;
+use crate::session::session::*;
 use crate::logger;
 
-// Testing - To be replaced with login screen //
-const player_id : i32 = 1;
-const league_id : i32 = 1;
-const game_show_id : i32 = 1;
-
 pub fn build_player_portal_page(
 	message: &UseStateHandle<String>,
 	contestant_system : &ContestantSystem,
 	dragged_index: &UseStateHandle<Option<usize>>,
 	ranked_list: &UseStateHandle<Vec<ContestantState>>,
-	league_system: &LeagueSystem
+	league_system: &LeagueSystem,
+	session_system: &SessionSystem
 ) -> Html
 {
+	let Some(player) = (*session_system.session_user).clone() else
+	{
+		return html!
+		{
+			<body class="bg-[#121212]  min-h-screen">
+				<div class="container mx-auto p-4">
+					<h1 class="text-4xl font-bold text-[#FF8C00] mb-4">{ "Survivor Fantasy League" }</h1>
+					<a href="/login" class="text-blue-500 hover:underline">{ "Log in to make your picks" }</a>
+				</div>
+			</body>
+		};
+	};
 
 	let ranked_list_for_submit: UseStateHandle<Vec<ContestantState>> = (*ranked_list).clone();
 
+	let selected_league: Option<PlayerLeagueState> = (*league_system.selected_league).clone();
+	let league_id: i32 = selected_league.as_ref().map_or(-1, |league: &PlayerLeagueState| league.id);
+	let game_show_id: i32 = selected_league.as_ref().and_then(|league: &PlayerLeagueState| league.id_showseason).unwrap_or(-1);
+
+	let on_select_league: Callback<Event> =
+	{
+		let player_leagues = league_system.player_leagues.clone();
+		let selected_league_clone = league_system.selected_league.clone();
+		Callback::from(move |e: Event|
+		{
+			let input: web_sys::HtmlSelectElement = e.target_unchecked_into();
+			let value: i32 = input.value().parse().unwrap_or(-1);
+
+			let league: Option<PlayerLeagueState> = player_leagues.iter().find(|league: &&PlayerLeagueState| league.id == value).cloned();
+			selected_league_clone.set(league);
+
+			logger::logger::log(format!("Selected League ID [{}]", value));
+		})
+	};
+
+	let player_id: i32 = player.id;
+
 	html!
 	{
 		<body class="bg-[#121212]  min-h-screen">
@@ -28,8 +59,35 @@ pub fn build_player_portal_page(
 
 				<h1 class="text-4xl font-bold text-[#FF8C00] mb-4">{ "Survivor Fantasy League" }</h1>
 
+				<p class="text-white mb-4">{ format!("Playing as {}", player.name) }</p>
+
+				<div class="mb-4">
+					<button
+						onclick={league_system.fetch_player_leagues.reform(move |_| player_id)}
+						class="bg-gray-500 hover:bg-gray-700 text-white font-bold py-2 px-4 rounded mr-2">
+						{ "Fetch My Leagues" }
+					</button>
+
+					<select onchange={on_select_league}>
+						<option value="" disabled=true selected=true>{"Select a league"}</option>
+						{
+							league_system.player_leagues.iter().map(|league|
+							{
+								html!
+								{
+									<option key={league.id} value={league.id.to_string()}>
+									{
+										&league.name
+									}
+									</option>
+								}
+							}).collect::<Html>()
+						}
+					</select>
+				</div>
+
 				<button
-					onclick={contestant_system.fetch_contestants_on_show.reform(|_| (game_show_id))}
+					onclick={contestant_system.fetch_contestants_on_show.reform(move |_| game_show_id)}
 					class="bg-gray-500 hover:bg-gray-700 text-white font-bold py-2 px-4 rounded mb-4">
 					{ "Fetch Contestants" }
 				</button>
@@ -77,14 +135,31 @@ pub fn build_player_portal_page(
 								class={classes!(
 										"flex", "items-center", "p-4", "mb-2", "rounded-lg", "cursor-grab", 
 										"border", "transition-all", "group",
-										"w-[250px]",
+										"w-[320px]",
 										{ "bg-[#1e1e1e] border-gray-800" },
+										"border-l-4",
 										"hover:border-[#4a90e2]"
 								)}
+								style={contestant.tribe_color.as_ref().map(|color: &String| format!("border-left-color: {}", color))}
 							>
 
+								if let Some(photo_url) = contestant.photo_url()
+								{
+									<img src={photo_url} alt={contestant.display_name()} class="w-14 h-14 object-cover rounded-full mr-3"/>
+								}
+
 								<div class="flex-grow text-center">
-										<span class="text-white font-semibold text-lg">{ &contestant.name }</span>
+										<span class="text-white font-semibold text-lg">{ contestant.display_name() }</span>
+										<div>{ tribe_badge(contestant) }</div>
+										<div class="text-sm text-gray-400">{ contestant.profile_summary() }</div>
+										if !contestant.prior_seasons.is_empty()
+										{
+											<div class="text-xs text-gray-500">{ format!("Played: {}", contestant.prior_seasons.join(", ")) }</div>
+										}
+										if let Some(bio) = contestant.bio.clone()
+										{
+											<p class="text-xs text-gray-400 mt-1">{ bio }</p>
+										}
 								</div>
 
 								<div class="text-gray-600 group-hover:text-[#4a90e2] font-mono">
@@ -97,6 +172,19 @@ pub fn build_player_portal_page(
 				</ul>
 
 				<div class="mt-6">
+					<input placeholder="Round Number" type="number"
+						value={league_system.pick_round_number.to_string()}
+						oninput={Callback::from(
+						{
+							let pick_round_number_clone = league_system.pick_round_number.clone();
+							move |e: InputEvent|
+							{
+								let input = e.target_dyn_into::<web_sys::HtmlInputElement>().unwrap();
+								pick_round_number_clone.set(input.value().parse::<i32>().unwrap_or(1));
+							}
+						})}
+						class="border rounded px-4 py-2 mr-2"/>
+
 					<button
 						onclick={league_system.submit_picks.clone()}
 						class="bg-green-500 hover:bg-green-700 text-white font-bold py-2 px-4 rounded">
@@ -104,7 +192,81 @@ pub fn build_player_portal_page(
 					</button>
 				</div>
 
+				{
+					build_leaderboard(league_system, league_id)
+				}
+
 			</div>
 		</body>
 	}
 }
+
+fn build_leaderboard(league_system: &LeagueSystem, league_id: i32) -> Html
+{
+	let round_numbers: Vec<i32> = league_system.standings.iter()
+		.flat_map(|standing: &StandingState| standing.rounds.iter().map(|round: &RoundScoreState| round.round_number))
+		.fold(Vec::new(), |mut rounds: Vec<i32>, round_number: i32|
+		{
+			if !rounds.contains(&round_number) { rounds.push(round_number); }
+			rounds
+		});
+
+	html!
+	{
+		<div class="mt-8">
+			<h2 class="text-2xl font-bold text-[#FF8C00] mb-2">{ "Leaderboard" }</h2>
+
+			<button
+				onclick={league_system.fetch_standings.reform(move |_| league_id)}
+				class="bg-gray-500 hover:bg-gray-700 text-white font-bold py-2 px-4 rounded mb-4">
+				{ "Refresh Standings" }
+			</button>
+
+			<table class="text-white border-collapse">
+				<thead>
+					<tr class="text-[#4a90e2]">
+						<th class="px-3 py-1 text-left">{ "Rank" }</th>
+						<th class="px-3 py-1"></th>
+						<th class="px-3 py-1 text-left">{ "Player" }</th>
+						{
+							for round_numbers.iter().map(|round_number| html!
+							{
+								<th class="px-3 py-1">{ format!("R{}", round_number) }</th>
+							})
+						}
+						<th class="px-3 py-1">{ "Total" }</th>
+					</tr>
+				</thead>
+				<tbody>
+				{
+					for league_system.standings.iter().map(|standing: &StandingState|
+					{
+						let (arrow, arrow_color) = match standing.movement
+						{
+							movement if movement > 0 => (format!("▲{}", movement), "text-green-500"),
+							movement if movement < 0 => (format!("▼{}", -movement), "text-red-500"),
+							_ => ("–".to_string(), "text-gray-500"),
+						};
+
+						html!
+						{
+							<tr key={standing.user_id} class="border-t border-gray-800">
+								<td class="px-3 py-1">{ standing.rank }</td>
+								<td class={classes!("px-3", "py-1", arrow_color)}>{ arrow }</td>
+								<td class="px-3 py-1 font-semibold">{ &standing.user_name }</td>
+								{
+									for standing.rounds.iter().map(|round: &RoundScoreState| html!
+									{
+										<td class="px-3 py-1 text-center">{ round.points }</td>
+									})
+								}
+								<td class="px-3 py-1 text-center font-bold">{ standing.total_points }</td>
+							</tr>
+						}
+					})
+				}
+				</tbody>
+			</table>
+		</div>
+	}
+}
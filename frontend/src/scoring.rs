@@ -0,0 +1 @@
+pub mod scoring;
\ No newline at end of file
@@ -0,0 +1,183 @@
+use yew::prelude::*;
+use serde::{ Deserialize, Serialize };
+use gloo::net::http::{ Request, RequestCredentials };
+use wasm_bindgen_futures::spawn_local;
+
+use crate::web_server::PLATFORM_URL;
+use crate::logger;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct ScoringRulesState
+{
+	pub id: Option<i32>,
+	pub league_id: Option<i32>,
+	pub survival_points: i32,
+	pub boot_prediction_bonus: i32,
+	pub medevac_points: i32,
+	pub medevac_counts_as_boot: bool,
+	#[serde(default)]
+	pub immunity_win_points: i32,
+	#[serde(default)]
+	pub reward_win_points: i32,
+	#[serde(default)]
+	pub idol_found_points: i32,
+	#[serde(default)]
+	pub idol_played_points: i32,
+	#[serde(default)]
+	pub vote_received_points: i32,
+	#[serde(default)]
+	pub correct_vote_points: i32
+}
+
+impl ScoringRulesState
+{
+	pub fn from_default() -> Self
+	{
+		ScoringRulesState
+		{
+			id: None,
+			league_id: None,
+			survival_points: 1,
+			boot_prediction_bonus: 3,
+			medevac_points: 0,
+			medevac_counts_as_boot: false,
+			immunity_win_points: 0,
+			reward_win_points: 0,
+			idol_found_points: 0,
+			idol_played_points: 0,
+			vote_received_points: 0,
+			correct_vote_points: 0
+		}
+	}
+}
+
+#[derive(Clone)]
+pub struct ScoringSystem
+{
+	pub scoring_rules_state: UseStateHandle<ScoringRulesState>,
+	pub fetch_scoring_rules: Callback<i32>,
+	pub save_scoring_rules: Callback<i32>,
+	pub delete_scoring_rules: Callback<i32>
+}
+
+fn fetch_scoring_rules(scoring_rules_state: &UseStateHandle<ScoringRulesState>,
+	message: &UseStateHandle<String>) -> Callback<i32>
+{
+	return
+	{
+		let scoring_rules_state: UseStateHandle<ScoringRulesState> = scoring_rules_state.clone();
+		let message: UseStateHandle<String> = message.clone();
+		Callback::from(move |league_id: i32|
+		{
+			let scoring_rules_state: UseStateHandle<ScoringRulesState> = scoring_rules_state.clone();
+			let message: UseStateHandle<String> = message.clone();
+
+			spawn_local(async move
+			{
+				let url: String = format!(concat!(PLATFORM_URL!(), "/leagues/{}/scoring_rules"), league_id);
+				match Request::get(&url).send().await
+				{
+					Ok(resp) if resp.ok() =>
+					{
+						if let Ok(fetched_rules) = resp.json::<ScoringRulesState>().await
+						{
+							scoring_rules_state.set(fetched_rules);
+							message.set(format!("Fetched scoring rules for league [{}]", league_id));
+						}
+					}
+
+					_ => message.set(format!("Failed to fetch scoring rules for league [{}]", league_id)),
+				}
+			});
+		})
+	};
+}
+
+fn save_scoring_rules(scoring_rules_state: &UseStateHandle<ScoringRulesState>,
+	message: &UseStateHandle<String>) -> Callback<i32>
+{
+	return
+	{
+		let scoring_rules_state: UseStateHandle<ScoringRulesState> = scoring_rules_state.clone();
+		let message: UseStateHandle<String> = message.clone();
+		Callback::from(move |league_id: i32|
+		{
+			let scoring_rules_state: UseStateHandle<ScoringRulesState> = scoring_rules_state.clone();
+			let message: UseStateHandle<String> = message.clone();
+
+			spawn_local(async move
+			{
+				logger::logger::log(format!("Saving scoring rules for league [{}]", league_id));
+
+				let rules_data: String = serde_json::to_string(&*scoring_rules_state).unwrap_or_default();
+				let url: String = format!(concat!(PLATFORM_URL!(), "/leagues/{}/scoring_rules"), league_id);
+
+				// Rules that came back from the server with an id already exist and get updated in place.
+				let request = if scoring_rules_state.id.is_some() { Request::put(&url) } else { Request::post(&url) };
+				let response: Result<gloo::net::http::Response, gloo::net::Error> = request
+					.header("Content-Type", "application/json")
+					.credentials(RequestCredentials::Include)
+					.body(rules_data)
+					.send().await;
+
+				match response
+				{
+					Ok(resp) if resp.ok() =>
+					{
+						if let Ok(saved_rules) = resp.json::<ScoringRulesState>().await
+						{
+							scoring_rules_state.set(saved_rules);
+						}
+						message.set(format!("Scoring rules saved for league [{}]", league_id));
+					}
+
+					_ => message.set(format!("Failed to save scoring rules for league [{}]", league_id)),
+				}
+			});
+		})
+	};
+}
+
+fn delete_scoring_rules(scoring_rules_state: &UseStateHandle<ScoringRulesState>,
+	message: &UseStateHandle<String>) -> Callback<i32>
+{
+	return
+	{
+		let scoring_rules_state: UseStateHandle<ScoringRulesState> = scoring_rules_state.clone();
+		let message: UseStateHandle<String> = message.clone();
+		Callback::from(move |league_id: i32|
+		{
+			let scoring_rules_state: UseStateHandle<ScoringRulesState> = scoring_rules_state.clone();
+			let message: UseStateHandle<String> = message.clone();
+
+			spawn_local(async move
+			{
+				let url: String = format!(concat!(PLATFORM_URL!(), "/leagues/{}/scoring_rules"), league_id);
+				let response: Result<gloo::net::http::Response, gloo::net::Error> = Request::delete(&url).credentials(RequestCredentials::Include).send().await;
+
+				match response
+				{
+					Ok(resp) if resp.ok() =>
+					{
+						scoring_rules_state.set(ScoringRulesState::from_default());
+						message.set(format!("League [{}] is back on the default scoring rules", league_id));
+					}
+
+					_ => message.set(format!("Failed to delete scoring rules for league [{}]", league_id)),
+				}
+			});
+		})
+	};
+}
+
+#[hook]
+pub fn use_compile_scoring_system(message: UseStateHandle<String>) -> ScoringSystem
+{
+	let scoring_rules_state: UseStateHandle<ScoringRulesState> = use_state(ScoringRulesState::from_default);
+
+	let fetch_scoring_rules: Callback<i32> = fetch_scoring_rules(&scoring_rules_state, &message);
+	let save_scoring_rules: Callback<i32> = save_scoring_rules(&scoring_rules_state, &message);
+	let delete_scoring_rules: Callback<i32> = delete_scoring_rules(&scoring_rules_state, &message);
+
+	return ScoringSystem { scoring_rules_state, fetch_scoring_rules, save_scoring_rules, delete_scoring_rules };
+}
@@ -0,0 +1 @@
+pub mod session;
\ No newline at end of file
@@ -0,0 +1,165 @@
+use yew::prelude::*;
+use serde::{ Deserialize, Serialize };
+use gloo::net::http::{ Request, RequestCredentials };
+use wasm_bindgen_futures::spawn_local;
+
+use crate::web_server::PLATFORM_URL;
+use crate::logger;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct SessionUser
+{
+	pub id: i32,
+	pub name: String,
+	pub email: String,
+	pub account_type: String,
+}
+
+#[derive(Clone, PartialEq)]
+pub struct LoginState
+{
+	pub email: String,
+	pub password: String,
+}
+
+impl LoginState
+{
+	pub fn from_default() -> Self
+	{
+		LoginState
+		{
+			email: "".to_string(),
+			password: "".to_string(),
+		}
+	}
+}
+
+#[derive(Clone)]
+pub struct SessionSystem
+{
+	pub session_user: UseStateHandle<Option<SessionUser>>,
+	pub login_state: UseStateHandle<LoginState>,
+	pub login: Callback<MouseEvent>,
+	pub logout: Callback<MouseEvent>,
+}
+
+fn login(login_state: &UseStateHandle<LoginState>,
+	session_user: &UseStateHandle<Option<SessionUser>>,
+	message: &UseStateHandle<String>) -> Callback<MouseEvent>
+{
+	return
+	{
+		let login_state: UseStateHandle<LoginState> = login_state.clone();
+		let session_user: UseStateHandle<Option<SessionUser>> = session_user.clone();
+		let message: UseStateHandle<String> = message.clone();
+		Callback::from(move |_|
+		{
+			let login_state: UseStateHandle<LoginState> = login_state.clone();
+			let session_user: UseStateHandle<Option<SessionUser>> = session_user.clone();
+			let message: UseStateHandle<String> = message.clone();
+
+			spawn_local(async move
+			{
+				let login_data: serde_json::Value = serde_json::json!({ "email": login_state.email, "password": login_state.password });
+				let url: &str = concat!(PLATFORM_URL!(), "/auth/login");
+				let response: Result<gloo::net::http::Response, gloo::net::Error> = Request::post(url)
+					.header("Content-Type", "application/json")
+					.credentials(RequestCredentials::Include)
+					.body(login_data.to_string())
+					.send().await;
+
+				match response
+				{
+					Ok(resp) if resp.ok() =>
+					{
+						if let Ok(user) = resp.json::<SessionUser>().await
+						{
+							message.set(format!("Welcome back, {}", user.name));
+							session_user.set(Some(user));
+						}
+					}
+
+					_ => message.set("Login failed. Check your email and password.".into()),
+				}
+
+				login_state.set(LoginState::from_default());
+			});
+		})
+	};
+}
+
+fn logout(session_user: &UseStateHandle<Option<SessionUser>>,
+	message: &UseStateHandle<String>) -> Callback<MouseEvent>
+{
+	return
+	{
+		let session_user: UseStateHandle<Option<SessionUser>> = session_user.clone();
+		let message: UseStateHandle<String> = message.clone();
+		Callback::from(move |_|
+		{
+			let session_user: UseStateHandle<Option<SessionUser>> = session_user.clone();
+			let message: UseStateHandle<String> = message.clone();
+
+			spawn_local(async move
+			{
+				let url: &str = concat!(PLATFORM_URL!(), "/auth/logout");
+				let response: Result<gloo::net::http::Response, gloo::net::Error> = Request::post(url)
+					.credentials(RequestCredentials::Include)
+					.send().await;
+
+				match response
+				{
+					Ok(resp) if resp.ok() =>
+					{
+						session_user.set(None);
+						message.set("Logged out".into());
+					}
+
+					_ => message.set("Failed to log out".into()),
+				}
+			});
+		})
+	};
+}
+
+// Picks up an existing session cookie so a page refresh doesn't log the player out.
+fn restore_session(session_user: &UseStateHandle<Option<SessionUser>>) -> Callback<()>
+{
+	let session_user: UseStateHandle<Option<SessionUser>> = session_user.clone();
+	Callback::from(move |_|
+	{
+		let session_user: UseStateHandle<Option<SessionUser>> = session_user.clone();
+		spawn_local(async move
+		{
+			let url: &str = concat!(PLATFORM_URL!(), "/auth/session");
+			match Request::get(url).credentials(RequestCredentials::Include).send().await
+			{
+				Ok(resp) if resp.ok() =>
+				{
+					if let Ok(user) = resp.json::<SessionUser>().await
+					{
+						logger::logger::log(format!("Restored session for user [{}]", user.id));
+						session_user.set(Some(user));
+					}
+				}
+
+				_ => session_user.set(None),
+			}
+		});
+	})
+}
+
+#[hook]
+pub fn use_compile_session_system(message: UseStateHandle<String>) -> SessionSystem
+{
+	let session_user: UseStateHandle<Option<SessionUser>> = use_state(|| None);
+	let login_state: UseStateHandle<LoginState> = use_state(LoginState::from_default);
+
+	let login: Callback<MouseEvent> = login(&login_state, &session_user, &message);
+	let logout: Callback<MouseEvent> = logout(&session_user, &message);
+	let restore_session: Callback<()> = restore_session(&session_user);
+
+	use_effect_with((), move |_| restore_session.emit(()));
+
+	return SessionSystem { session_user, login_state, login, logout };
+}
@@ -1,6 +1,6 @@
 use yew::prelude::*;
 use serde::{ Deserialize, Serialize };
-use gloo::net::http::Request;
+use gloo::net::http::{ Request, RequestCredentials };
 use wasm_bindgen_futures::spawn_local;
 
 use crate::web_server::PLATFORM_URL;
@@ -12,6 +12,7 @@ pub struct UserState
 	pub email: String,
 	pub account_type: String,
 	pub id: Option<i32>,
+	pub password: String,
 }
 
 impl UserState
@@ -24,6 +25,7 @@ impl UserState
 			email: "".to_string(),
 			account_type: "".to_string(),
 			id: None,
+			password: "".to_string(),
 		}
 	}
 
@@ -35,6 +37,7 @@ impl UserState
 			email : email_in,
 			account_type : account_type_in,
 			id : id_in,
+			password : "".to_string(),
 		}
 	}
 }
@@ -60,7 +63,7 @@ pub fn get_users(users: &UseStateHandle<Vec<User>>,
 		spawn_local(async move
 		{
 			let url = concat!(PLATFORM_URL!(), "/users");
-			match Request::get(&url).send().await
+			match Request::get(&url).credentials(RequestCredentials::Include).send().await
 			{
 				Ok(resp) if resp.ok() =>
 				{
@@ -88,13 +91,15 @@ pub fn create_user(user_state: &UseStateHandle<UserState>,
 			let user_state: UseStateHandle<UserState> = user_state.clone();
 			let message: UseStateHandle<String> = message.clone();
 			let get_users: Callback<()> = get_users.clone();
-			let account_type_in: &str = "Developer"; // GameMaster, Admin, Player
+			// Signups from the portal are players, gamemasters are promoted by another gamemaster.
+			let account_type_in: &str = "Player";
 
 			spawn_local(async move
 			{
-				let user_data: serde_json::Value = serde_json::json!({ "name": user_state.name, "email": user_state.email, "account_type" : account_type_in });
+				let user_data: serde_json::Value = serde_json::json!({ "name": user_state.name, "email": user_state.email, "account_type" : account_type_in, "password": user_state.password });
 				let url = concat!(PLATFORM_URL!(), "/users");
 				let response: Result<gloo::net::http::Response, gloo::net::Error> = Request::post(&url)
+					.credentials(RequestCredentials::Include)
 					.header("Content-Type", "application/json")
 					.body(user_data.to_string())
 					.send().await;
@@ -139,6 +144,7 @@ pub fn update_user(user_state: &UseStateHandle<UserState>,
 				{
 					let url = format!(concat!(PLATFORM_URL!(), "/users/{}"), id);
 					let response: Result<gloo::net::http::Response, gloo::net::Error> = Request::put(&url)
+						.credentials(RequestCredentials::Include)
 						.header("Content-Type", "application/json")
 						.body( serde_json::to_string(&(id, user_state.name.as_str(), user_state.email.as_str(), user_state.account_type.as_str() )).unwrap())
 						.send().await;
@@ -177,7 +183,7 @@ pub fn delete_user(message: &UseStateHandle<String>,
 			spawn_local(async move
 			{
 				let url: String = format!(concat!(PLATFORM_URL!(), "/users/{}"), id);
-				let response: Result<gloo::net::http::Response, gloo::net::Error> = Request::delete(&url).send().await;
+				let response: Result<gloo::net::http::Response, gloo::net::Error> = Request::delete(&url).credentials(RequestCredentials::Include).send().await;
 
 				match response
 				{
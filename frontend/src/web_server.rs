@@ -1,3 +1,4 @@
 pub mod web_server;
 
-pub(crate) use self::web_server::PLATFORM_URL; 
\ No newline at end of file
+pub(crate) use self::web_server::PLATFORM_URL;
+pub(crate) use self::web_server::error_message;
@@ -1,2 +1,19 @@
+use serde::Deserialize;
+use gloo::net::http::Response;
+
 macro_rules! PLATFORM_URL { () => { "http://127.0.0.1:8000/api" } }
-pub(crate) use PLATFORM_URL;
\ No newline at end of file
+pub(crate) use PLATFORM_URL;
+
+// Every error the backend sends back is a JSON body of { error, message }.
+#[derive(Deserialize)]
+struct ErrorBody
+{
+	message: String,
+}
+
+pub async fn error_message(resp: Response, fallback: &str) -> String
+{
+	return resp.json::<ErrorBody>().await
+		.map(|body: ErrorBody| body.message)
+		.unwrap_or(fallback.to_string());
+}